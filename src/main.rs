@@ -1,10 +1,13 @@
+pub mod api_types;
+
 pub mod widgets;
 pub use widgets::{MainNavigation, MainNavigationSelection};
 
 pub mod pages;
 use pages::{
-    PageLogin, PageLxcStatus, PageLxcTasks, PageNodeStatus, PageNodeTasks, PageNotFound,
-    PageQemuStatus, PageQemuTasks, PageSettings, PageStorageStatus, PageTaskStatus,
+    PageBackupJobs, PageCeph, PageCreateLxc, PageCreateQemu, PageHa, PageLogin, PageLogs,
+    PageLxcStatus, PageLxcTasks, PageNodeStatus, PageNodeTasks, PageNotFound, PageQemuStatus,
+    PageQemuTasks, PageSettings, PageStorageStatus, PageTaskStatus,
 };
 
 use gloo_utils::format::JsValueSerdeExt;
@@ -71,6 +74,11 @@ enum Route {
         endtime: i64,
     },
 
+    #[at("/resources/create-qemu")]
+    CreateQemu,
+    #[at("/resources/create-lxc")]
+    CreateLxc,
+
     #[at("/resources/node/:nodename")]
     Node { nodename: String },
     #[at("/resources/node/:nodename/tasks")]
@@ -83,10 +91,18 @@ enum Route {
     },
     #[at("/resources/node/:nodename/storage/:name")]
     Storage { nodename: String, name: String },
-    // #[at("/logs")]
-    // Logs,
+    #[at("/logs")]
+    Logs,
+    #[at("/logs/tasks/:upid/:endtime")]
+    LogsTaskStatus { upid: String, endtime: i64 },
     #[at("/configuration")]
     Configuration,
+    #[at("/ceph")]
+    Ceph,
+    #[at("/ha")]
+    Ha,
+    #[at("/backup-jobs")]
+    BackupJobs,
     #[not_found]
     #[at("/404")]
     NotFound,
@@ -118,6 +134,8 @@ fn switch_route(route: Route) -> Vec<Html> {
             switch_route(Route::Configuration),
             PageSettings::new().into(),
         ),
+        Route::CreateQemu => (switch_route(Route::Resources), PageCreateQemu::new().into()),
+        Route::CreateLxc => (switch_route(Route::Resources), PageCreateLxc::new().into()),
         Route::Qemu { vmid, nodename } => (
             switch_route(Route::Resources),
             PageQemuStatus::new(nodename, vmid).into(),
@@ -218,6 +236,27 @@ fn switch_route(route: Route) -> Vec<Html> {
             switch_route(Route::Resources),
             PageStorageStatus::new(nodename, name).into(),
         ),
+        Route::Logs => (switch_route(Route::Configuration), PageLogs::new().into()),
+        Route::Ceph => (switch_route(Route::Configuration), PageCeph::new().into()),
+        Route::Ha => (switch_route(Route::Configuration), PageHa::new().into()),
+        Route::BackupJobs => (
+            switch_route(Route::Configuration),
+            PageBackupJobs::new().into(),
+        ),
+        Route::LogsTaskStatus { upid, endtime } => {
+            // UPID:<node>:<pid>:<pstart>:<starttime>:<type>:<id>:<user>:
+            let nodename = upid.split(':').nth(1).unwrap_or("localhost").to_string();
+            (
+                switch_route(Route::Logs),
+                PageTaskStatus::new(
+                    format!("/nodes/{}/tasks", percent_encode_component(&nodename)),
+                    upid,
+                )
+                .endtime(endtime)
+                .back("/logs")
+                .into(),
+            )
+        }
         Route::NotFound => (vec![], html! { <PageNotFound/> }),
     };
 
@@ -1,6 +1,12 @@
 mod page_dashboard;
 pub use page_dashboard::PageDashboard;
 
+mod page_create_lxc;
+pub use page_create_lxc::PageCreateLxc;
+
+mod page_create_qemu;
+pub use page_create_qemu::PageCreateQemu;
+
 mod page_resources;
 pub use page_resources::{PageResources, ResourceFilter};
 
@@ -31,8 +37,17 @@ pub use page_node_tasks::PageNodeTasks;
 mod page_task_status;
 pub use page_task_status::PageTaskStatus;
 
-// mod page_logs;
-// pub use page_logs::PageLogs;
+mod page_logs;
+pub use page_logs::PageLogs;
+
+mod page_ceph;
+pub use page_ceph::PageCeph;
+
+mod page_ha;
+pub use page_ha::PageHa;
+
+mod page_backup_jobs;
+pub use page_backup_jobs::PageBackupJobs;
 
 mod page_settings;
 pub use page_settings::PageSettings;
@@ -0,0 +1,454 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+use serde_json::{Value, json};
+
+use yew::virtual_dom::{Key, VComp, VNode};
+use yew_router::scope_ext::RouterScopeExt;
+
+use pwt::AsyncAbortGuard;
+use pwt::prelude::*;
+use pwt::touch::SideDialog;
+use pwt::widget::{
+    Button, Column, ConfirmDialog, Container, Fa, List, ListTile, MiniScroll, MiniScrollMode, Row,
+};
+
+use pve_api_types::{ClusterNodeIndexResponse, ClusterNodeIndexResponseStatus};
+
+use proxmox_yew_comp::layout::card::standard_card;
+use proxmox_yew_comp::layout::list_tile::{icon_list_tile, title_subtitle_column};
+use proxmox_yew_comp::layout::render_loaded_data;
+use proxmox_yew_comp::{http_get, http_post, http_put, percent_encoding::percent_encode_component};
+
+use crate::widgets::{
+    TasksListButton, TopNavBar, backup_job_next_run, backup_job_selection, format_property_string,
+};
+
+// Job properties which are not vzdump parameters
+const JOB_ONLY_PROPERTIES: &[&str] = &[
+    "id",
+    "type",
+    "schedule",
+    "enabled",
+    "next-run",
+    "comment",
+    "node",
+    "repeat-missed",
+    "starttime",
+    "dow",
+];
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct PageBackupJobs {}
+
+impl PageBackupJobs {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+pub struct BackupJobsData {
+    // `GET /cluster/backup`
+    jobs: Vec<Value>,
+    // `GET /cluster/backup-info/not-backed-up`
+    not_backed_up: Vec<Value>,
+}
+
+pub enum Msg {
+    Load,
+    LoadResult(Result<BackupJobsData, Error>),
+    ShowJob(Option<String>),
+    ConfirmRun(bool),
+    RunNow,
+    /// Started task (or error) for each node
+    RunResult(Result<Vec<(String, Result<String, Error>)>, Error>),
+    SetEnabled(bool),
+    CommandResult(Result<Value, Error>),
+    TaskFinished((String, Option<String>)),
+}
+
+pub struct PvePageBackupJobs {
+    data: Option<Result<BackupJobsData, String>>,
+    load_guard: Option<AsyncAbortGuard>,
+    cmd_guard: Option<AsyncAbortGuard>,
+    selected: Option<String>,
+    confirm_run: bool,
+    // started backup tasks, the button tracks the first one
+    running_upids: Vec<String>,
+}
+
+fn job_enabled(job: &Value) -> bool {
+    !(job["enabled"].as_u64() == Some(0) || job["enabled"].as_bool() == Some(false))
+}
+
+fn job_title(job: &Value) -> String {
+    match job["comment"].as_str() {
+        Some(comment) if !comment.is_empty() => comment.to_string(),
+        _ => job["id"].as_str().unwrap_or("").to_string(),
+    }
+}
+
+// The API returns some properties (i.e. `prune-backups`) as objects, but
+// vzdump expects them as property strings.
+fn property_string_value(value: &Value) -> Value {
+    let Value::Object(map) = value else {
+        return value.clone();
+    };
+    let list: Vec<(String, String)> = map
+        .iter()
+        .map(|(key, value)| {
+            let value = match value {
+                Value::String(text) => text.clone(),
+                other => other.to_string(),
+            };
+            (key.clone(), value)
+        })
+        .collect();
+    format_property_string(&list, None).into()
+}
+
+// Start the job on all online nodes (or the node it is restricted to), like the web interface.
+async fn run_job_now(job: Value) -> Result<Vec<(String, Result<String, Error>)>, Error> {
+    let nodes = match job["node"].as_str() {
+        Some(node) => vec![node.to_string()],
+        None => {
+            let nodes: Vec<ClusterNodeIndexResponse> = http_get("/nodes", None).await?;
+            nodes
+                .into_iter()
+                .filter(|item| item.status == ClusterNodeIndexResponseStatus::Online)
+                .map(|item| item.node)
+                .collect()
+        }
+    };
+
+    let mut param = job;
+    if let Value::Object(map) = &mut param {
+        for key in JOB_ONLY_PROPERTIES {
+            map.remove(*key);
+        }
+        for value in map.values_mut() {
+            *value = property_string_value(value);
+        }
+    }
+
+    // a failing node must not prevent the backup on the other nodes
+    let mut results = Vec::new();
+    for node in nodes {
+        let url = format!("/nodes/{}/vzdump", percent_encode_component(&node));
+        let result = http_post(&url, Some(param.clone())).await;
+        results.push((node, result));
+    }
+    Ok(results)
+}
+
+impl PvePageBackupJobs {
+    fn selected_job<'a>(&self, data: &'a BackupJobsData) -> Option<&'a Value> {
+        let id = self.selected.as_deref()?;
+        data.jobs.iter().find(|job| job["id"] == id)
+    }
+
+    fn view_jobs(&self, ctx: &Context<Self>, data: &BackupJobsData) -> Html {
+        let tiles: Vec<ListTile> = data
+            .jobs
+            .iter()
+            .map(|job| {
+                let id = job["id"].as_str().unwrap_or("").to_string();
+                let enabled = job_enabled(job);
+                let subtitle = format!(
+                    "{} - {}: {}",
+                    backup_job_selection(job),
+                    tr!("Storage"),
+                    job["storage"].as_str().unwrap_or("-"),
+                );
+                let icon = Fa::new("floppy-o").class(enabled.then_some("pwt-color-primary"));
+
+                icon_list_tile(icon, job_title(job), subtitle, backup_job_next_run(job))
+                    .key(Key::from(id.clone()))
+                    .interactive(true)
+                    .onclick(ctx.link().callback(move |_| Msg::ShowJob(Some(id.clone()))))
+            })
+            .collect();
+
+        let content: Html = if tiles.is_empty() {
+            Container::new()
+                .padding(2)
+                .with_child(tr!("No backup jobs configured."))
+                .into()
+        } else {
+            List::from_tiles(tiles)
+                .grid_template_columns("auto 1fr auto")
+                .into()
+        };
+
+        standard_card(
+            tr!("Backup Jobs"),
+            tr!("Tap a job to run or disable it."),
+            (),
+        )
+        .with_child(content)
+        .into()
+    }
+
+    fn view_not_backed_up(&self, data: &BackupJobsData) -> Html {
+        let tiles: Vec<ListTile> = data
+            .not_backed_up
+            .iter()
+            .map(|guest| {
+                let vmid = guest["vmid"].as_u64().unwrap_or(0);
+                let icon = if guest["type"].as_str() == Some("lxc") {
+                    "cube"
+                } else {
+                    "desktop"
+                };
+                icon_list_tile(
+                    Fa::new(icon),
+                    format!("{} {}", vmid, guest["name"].as_str().unwrap_or("")),
+                    (),
+                    (),
+                )
+                .key(Key::from(vmid.to_string()))
+            })
+            .collect();
+
+        let content: Html = if tiles.is_empty() {
+            List::from_tiles(vec![icon_list_tile(
+                Fa::new("check").class("pwt-color-primary"),
+                tr!("All guests are covered by a backup job."),
+                (),
+                (),
+            )])
+            .grid_template_columns("auto 1fr")
+            .into()
+        } else {
+            List::from_tiles(tiles)
+                .grid_template_columns("auto 1fr")
+                .into()
+        };
+
+        standard_card(tr!("Guests without Backup Job"), (), ())
+            .with_child(content)
+            .into()
+    }
+
+    fn view_job_dialog(&self, ctx: &Context<Self>, data: &BackupJobsData) -> Option<Html> {
+        let job = self.selected_job(data)?;
+        let enabled = job_enabled(job);
+        let busy = self.cmd_guard.is_some();
+
+        let mut info = Column::new().gap(1);
+        for (label, value) in [
+            (tr!("Schedule"), job["schedule"].as_str().map(String::from)),
+            (tr!("Next Run"), Some(backup_job_next_run(job))),
+            (tr!("Selection"), Some(backup_job_selection(job))),
+            (tr!("Storage"), job["storage"].as_str().map(String::from)),
+            (tr!("Mode"), job["mode"].as_str().map(String::from)),
+        ] {
+            if let Some(value) = value {
+                info.add_child(title_subtitle_column(label, value));
+            }
+        }
+
+        let row = Row::new()
+            .gap(2)
+            .with_child(
+                Button::new(tr!("Run now"))
+                    .icon_class("fa fa-play")
+                    .disabled(busy)
+                    .on_activate(ctx.link().callback(|_| Msg::ConfirmRun(true))),
+            )
+            .with_child(
+                Button::new(if enabled {
+                    tr!("Disable")
+                } else {
+                    tr!("Enable")
+                })
+                .icon_class(if enabled {
+                    "fa fa-toggle-off"
+                } else {
+                    "fa fa-toggle-on"
+                })
+                .disabled(busy)
+                .on_activate(ctx.link().callback(move |_| Msg::SetEnabled(!enabled))),
+            );
+
+        let confirm = self.confirm_run.then(|| {
+            ConfirmDialog::default()
+                .confirm_message(tr!("Start backup job '{0}' now?", job_title(job)))
+                .on_close(ctx.link().callback(|_| Msg::ConfirmRun(false)))
+                .on_confirm(ctx.link().callback(|_| Msg::RunNow))
+        });
+
+        Some(
+            SideDialog::new()
+                .location(pwt::touch::SideDialogLocation::Bottom)
+                .on_close(ctx.link().callback(|_| Msg::ShowJob(None)))
+                .with_child(
+                    Column::new()
+                        .padding(2)
+                        .gap(2)
+                        .with_child(
+                            Container::new()
+                                .class("pwt-font-size-title-large")
+                                .with_child(job_title(job)),
+                        )
+                        .with_child(info)
+                        .with_child(
+                            MiniScroll::new(row)
+                                .scroll_mode(MiniScrollMode::Native)
+                                .class(pwt::css::Flex::None),
+                        )
+                        .with_optional_child(confirm),
+                )
+                .into(),
+        )
+    }
+
+    fn task_button(&self, ctx: &Context<Self>) -> Html {
+        TasksListButton::new()
+            .running_upid(self.running_upids.first().cloned())
+            .on_task_finished(ctx.link().callback(Msg::TaskFinished))
+            .on_show_task_list({
+                let navigator = ctx.link().navigator().clone().unwrap();
+                move |_| navigator.push(&crate::Route::Logs)
+            })
+            .into()
+    }
+}
+
+impl Component for PvePageBackupJobs {
+    type Message = Msg;
+    type Properties = PageBackupJobs;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link().send_message(Msg::Load);
+        Self {
+            data: None,
+            load_guard: None,
+            cmd_guard: None,
+            selected: None,
+            confirm_run: false,
+            running_upids: Vec::new(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let link = ctx.link().clone();
+        match msg {
+            Msg::Load => {
+                self.load_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = async {
+                        let jobs = http_get("/cluster/backup", None).await?;
+                        let not_backed_up =
+                            http_get("/cluster/backup-info/not-backed-up", None).await?;
+                        Ok(BackupJobsData {
+                            jobs,
+                            not_backed_up,
+                        })
+                    }
+                    .await;
+                    link.send_message(Msg::LoadResult(result));
+                }));
+            }
+            Msg::LoadResult(result) => {
+                self.load_guard = None;
+                self.data = Some(result.map_err(|err| err.to_string()));
+            }
+            Msg::ShowJob(id) => {
+                self.selected = id;
+                self.confirm_run = false;
+            }
+            Msg::ConfirmRun(show) => self.confirm_run = show,
+            Msg::RunNow => {
+                self.confirm_run = false;
+                let job = match &self.data {
+                    Some(Ok(data)) => self.selected_job(data).cloned(),
+                    _ => None,
+                };
+                let Some(job) = job else {
+                    return true;
+                };
+                self.cmd_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = run_job_now(job).await;
+                    link.send_message(Msg::RunResult(result));
+                }));
+            }
+            Msg::RunResult(result) => {
+                self.cmd_guard = None;
+                match result {
+                    Ok(results) => {
+                        self.selected = None;
+                        let mut errors = Vec::new();
+                        for (node, result) in results {
+                            match result {
+                                Ok(upid) => self.running_upids.push(upid),
+                                Err(err) => errors.push(format!("{node}: {err}")),
+                            }
+                        }
+                        if !errors.is_empty() {
+                            crate::show_failed_command_error(ctx.link(), errors.join(", "));
+                        }
+                    }
+                    Err(err) => crate::show_failed_command_error(ctx.link(), err),
+                }
+            }
+            Msg::SetEnabled(enabled) => {
+                let Some(id) = self.selected.clone() else {
+                    return true;
+                };
+                let url = format!("/cluster/backup/{}", percent_encode_component(&id));
+                let param = json!({ "enabled": enabled });
+                self.cmd_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_put(&url, Some(param)).await;
+                    link.send_message(Msg::CommandResult(result));
+                }));
+            }
+            Msg::CommandResult(result) => {
+                self.cmd_guard = None;
+                if let Err(err) = result {
+                    crate::show_failed_command_error(ctx.link(), err);
+                }
+                ctx.link().send_message(Msg::Load);
+            }
+            Msg::TaskFinished((upid, _)) => {
+                self.running_upids.retain(|running| *running != upid);
+                ctx.link().send_message(Msg::Load);
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let content = render_loaded_data(&self.data, |data| {
+            Column::new()
+                .gap(2)
+                .with_child(self.task_button(ctx))
+                .with_child(self.view_jobs(ctx, data))
+                .with_child(self.view_not_backed_up(data))
+                .with_optional_child(self.view_job_dialog(ctx, data))
+                .into()
+        });
+
+        Column::new()
+            .class("pwt-fit")
+            .with_child(
+                TopNavBar::new()
+                    .title(tr!("Backup Jobs"))
+                    .back("/configuration"),
+            )
+            .with_child(
+                Column::new()
+                    .class("pwt-flex-fill")
+                    .class("pwt-overflow-auto")
+                    .padding(2)
+                    .with_child(content),
+            )
+            .into()
+    }
+}
+
+impl From<PageBackupJobs> for VNode {
+    fn from(props: PageBackupJobs) -> Self {
+        let comp = VComp::new::<PvePageBackupJobs>(Rc::new(props), None);
+        VNode::from(comp)
+    }
+}
@@ -0,0 +1,643 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+use gloo_timers::callback::Timeout;
+use serde_json::{Value, json};
+
+use yew::virtual_dom::{Key, VComp, VNode};
+
+use pwt::AsyncAbortGuard;
+use pwt::prelude::*;
+use pwt::touch::SideDialog;
+use pwt::widget::{
+    Button, Column, ConfirmDialog, Container, Fa, List, ListTile, MiniScroll, MiniScrollMode, Row,
+};
+
+use pve_api_types::{ClusterNodeIndexResponse, ClusterNodeIndexResponseStatus};
+
+use proxmox_human_byte::HumanByte;
+use proxmox_yew_comp::layout::card::standard_card;
+use proxmox_yew_comp::layout::list_tile::{icon_list_tile, list_tile_usage, title_subtitle_column};
+use proxmox_yew_comp::layout::render_loaded_data;
+use proxmox_yew_comp::{http_get, http_post, percent_encoding::percent_encode_component};
+
+use crate::widgets::TopNavBar;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct PageCeph {}
+
+impl PageCeph {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+pub struct CephData {
+    // `GET /cluster/ceph/status`
+    status: Value,
+    // node used for the `/nodes/{node}/ceph/*` calls
+    node: Option<String>,
+    osd_tree: Option<Value>,
+    pools: Vec<Value>,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct OsdInfo {
+    id: u64,
+    name: String,
+    host: String,
+    up: bool,
+    is_in: bool,
+    device_class: Option<String>,
+    percent_used: Option<f64>,
+}
+
+pub enum Msg {
+    Load,
+    LoadResult(Result<CephData, Error>),
+    ShowOsd(Option<OsdInfo>),
+    Command(&'static str),
+    Execute(&'static str),
+    Confirm(Option<&'static str>),
+    CommandResult(Result<Value, Error>),
+}
+
+pub struct PvePageCeph {
+    data: Option<Result<CephData, String>>,
+    load_guard: Option<AsyncAbortGuard>,
+    reload_timeout: Option<Timeout>,
+    osd: Option<OsdInfo>,
+    cmd_guard: Option<AsyncAbortGuard>,
+    confirm: Option<&'static str>,
+}
+
+/// Icon for a Ceph `HEALTH_*` status.
+pub fn ceph_health_icon(status: &str) -> Fa {
+    match status {
+        "HEALTH_OK" => Fa::new("check-circle").class("pwt-color-primary"),
+        "HEALTH_WARN" => Fa::new("exclamation-triangle").class("pwt-color-warning"),
+        _ => Fa::new("times-circle").class("pwt-color-error"),
+    }
+}
+
+fn value_names(list: &Value, key: &str) -> Vec<String> {
+    list.as_array()
+        .map(|list| {
+            list.iter()
+                .filter_map(|item| item[key].as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// Older Ceph releases nest the OSD counters in `osdmap.osdmap`.
+fn osd_counts(status: &Value) -> (u64, u64, u64) {
+    let osdmap = &status["osdmap"];
+    let osdmap = if osdmap["osdmap"].is_object() {
+        &osdmap["osdmap"]
+    } else {
+        osdmap
+    };
+    (
+        osdmap["num_osds"].as_u64().unwrap_or(0),
+        osdmap["num_up_osds"].as_u64().unwrap_or(0),
+        osdmap["num_in_osds"].as_u64().unwrap_or(0),
+    )
+}
+
+// Walk the CRUSH tree returned by `GET /nodes/{node}/ceph/osd` and group the OSDs by host.
+fn collect_osds(node: &Value, host: Option<&str>, result: &mut Vec<(String, Vec<OsdInfo>)>) {
+    match node["type"].as_str() {
+        Some("host") => {
+            let name = node["name"].as_str().unwrap_or("").to_string();
+            result.push((name.clone(), Vec::new()));
+            if let Some(children) = node["children"].as_array() {
+                for child in children {
+                    collect_osds(child, Some(&name), result);
+                }
+            }
+        }
+        Some("osd") => {
+            let host = node["host"].as_str().or(host).unwrap_or("").to_string();
+            let osd = OsdInfo {
+                id: node["id"].as_u64().unwrap_or(0),
+                name: node["name"].as_str().unwrap_or("").to_string(),
+                host: host.clone(),
+                up: node["status"].as_str() == Some("up"),
+                is_in: node["in"].as_u64() == Some(1),
+                device_class: node["device_class"].as_str().map(String::from),
+                percent_used: node["percent_used"].as_f64(),
+            };
+            match result.iter_mut().find(|(name, _)| name == &host) {
+                Some((_, list)) => list.push(osd),
+                None => result.push((host, vec![osd])),
+            }
+        }
+        _ => {
+            if let Some(children) = node["children"].as_array() {
+                for child in children {
+                    collect_osds(child, host, result);
+                }
+            }
+        }
+    }
+}
+
+async fn load_ceph_data() -> Result<CephData, Error> {
+    let status: Value = http_get("/cluster/ceph/status", None).await?;
+
+    let nodes: Vec<ClusterNodeIndexResponse> = http_get("/nodes", None).await?;
+    let mut nodes: Vec<String> = nodes
+        .into_iter()
+        .filter(|item| item.status == ClusterNodeIndexResponseStatus::Online)
+        .map(|item| item.node)
+        .collect();
+    nodes.sort();
+
+    // use the first online node which has Ceph installed
+    for node in nodes {
+        let base = format!("/nodes/{}/ceph", percent_encode_component(&node));
+        let osd_tree: Result<Value, Error> = http_get(&format!("{base}/osd"), None).await;
+        let Ok(osd_tree) = osd_tree else {
+            continue;
+        };
+        let pools: Result<Vec<Value>, Error> = http_get(&format!("{base}/pool"), None).await;
+        return Ok(CephData {
+            status,
+            node: Some(node),
+            osd_tree: Some(osd_tree),
+            pools: pools.unwrap_or_default(),
+        });
+    }
+
+    Ok(CephData {
+        status,
+        node: None,
+        osd_tree: None,
+        pools: Vec::new(),
+    })
+}
+
+impl PvePageCeph {
+    fn view_health(&self, data: &CephData) -> Html {
+        let health = &data.status["health"];
+        let status = health["status"].as_str().unwrap_or("HEALTH_ERR");
+
+        let mut tiles = vec![icon_list_tile(
+            ceph_health_icon(status).large(),
+            status.to_string(),
+            (),
+            (),
+        )];
+
+        if let Some(checks) = health["checks"].as_object() {
+            for (name, check) in checks {
+                let severity = check["severity"].as_str().unwrap_or("HEALTH_WARN");
+                let message = check["summary"]["message"].as_str().unwrap_or(name);
+                tiles.push(icon_list_tile(
+                    ceph_health_icon(severity),
+                    message.to_string(),
+                    name.clone(),
+                    (),
+                ));
+            }
+        }
+
+        standard_card(tr!("Health"), (), ())
+            .with_child(List::from_tiles(tiles).grid_template_columns("auto 1fr"))
+            .into()
+    }
+
+    fn view_services(&self, data: &CephData) -> Html {
+        let status = &data.status;
+
+        let mons = value_names(&status["monmap"]["mons"], "name");
+        let quorum: Vec<String> = status["quorum_names"]
+            .as_array()
+            .map(|list| {
+                list.iter()
+                    .filter_map(|name| name.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let out_of_quorum: Vec<String> = mons
+            .iter()
+            .filter(|name| !quorum.contains(name))
+            .cloned()
+            .collect();
+        let mon_subtitle = if out_of_quorum.is_empty() {
+            mons.join(", ")
+        } else {
+            tr!("Not in quorum: {0}", out_of_quorum.join(", "))
+        };
+
+        let mgrmap = &status["mgrmap"];
+        let active_mgr = mgrmap["active_name"].as_str().unwrap_or("");
+        let standby_mgrs = value_names(&mgrmap["standbys"], "name");
+        let mgr_subtitle = match (active_mgr.is_empty(), standby_mgrs.is_empty()) {
+            (true, _) => tr!("No active manager"),
+            (false, true) => tr!("Active: {0}", active_mgr),
+            (false, false) => tr!(
+                "Active: {0}, Standby: {1}",
+                active_mgr,
+                standby_mgrs.join(", ")
+            ),
+        };
+
+        let fsmap = &status["fsmap"];
+        let mds_ranks: Vec<String> = fsmap["by_rank"]
+            .as_array()
+            .map(|list| {
+                list.iter()
+                    .map(|mds| {
+                        format!(
+                            "{} ({})",
+                            mds["name"].as_str().unwrap_or(""),
+                            mds["status"].as_str().unwrap_or("")
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let mds_standby = fsmap["up:standby"].as_u64().unwrap_or(0);
+
+        let state_icon = |ok: bool| {
+            if ok {
+                Fa::new("check-circle").class("pwt-color-primary")
+            } else {
+                Fa::new("exclamation-triangle").class("pwt-color-warning")
+            }
+        };
+
+        let mut tiles = vec![
+            icon_list_tile(
+                state_icon(!mons.is_empty() && out_of_quorum.is_empty()),
+                tr!("Monitors"),
+                mon_subtitle,
+                format!("{}/{}", quorum.len(), mons.len()),
+            ),
+            icon_list_tile(
+                state_icon(!active_mgr.is_empty()),
+                tr!("Managers"),
+                mgr_subtitle,
+                format!(
+                    "{}/{}",
+                    if active_mgr.is_empty() { 0 } else { 1 },
+                    standby_mgrs.len() + if active_mgr.is_empty() { 0 } else { 1 }
+                ),
+            ),
+        ];
+
+        if !mds_ranks.is_empty() || mds_standby > 0 {
+            tiles.push(icon_list_tile(
+                state_icon(!mds_ranks.is_empty()),
+                tr!("Metadata Servers"),
+                mds_ranks.join(", "),
+                tr!("{0} standby", mds_standby),
+            ));
+        }
+
+        standard_card(tr!("Services"), (), ())
+            .with_child(List::from_tiles(tiles).grid_template_columns("auto 1fr auto"))
+            .into()
+    }
+
+    fn view_osds(&self, ctx: &Context<Self>, data: &CephData) -> Html {
+        let (total, up, is_in) = osd_counts(&data.status);
+        let subtitle = tr!("{0} in, {1} up, {2} total", is_in, up, total);
+
+        let mut hosts = Vec::new();
+        if let Some(tree) = &data.osd_tree {
+            collect_osds(&tree["root"], None, &mut hosts);
+        }
+
+        let mut column = Column::new();
+        if data.node.is_none() {
+            column.add_child(
+                Container::new()
+                    .padding(2)
+                    .with_child(tr!("Ceph is not installed on any online node.")),
+            );
+        }
+
+        for (host, osds) in hosts {
+            if osds.is_empty() {
+                continue;
+            }
+            let tiles: Vec<ListTile> = osds
+                .into_iter()
+                .map(|osd| {
+                    let icon = if osd.up && osd.is_in {
+                        Fa::new("hdd-o").class("pwt-color-primary")
+                    } else if osd.up {
+                        Fa::new("hdd-o").class("pwt-color-warning")
+                    } else {
+                        Fa::new("hdd-o").class("pwt-color-error")
+                    };
+                    let mut details = vec![
+                        if osd.up { tr!("up") } else { tr!("down") },
+                        if osd.is_in { tr!("in") } else { tr!("out") },
+                    ];
+                    if let Some(class) = &osd.device_class {
+                        details.push(class.clone());
+                    }
+                    let used = osd
+                        .percent_used
+                        .map(|used| format!("{used:.1}%"))
+                        .unwrap_or_default();
+
+                    icon_list_tile(icon, osd.name.clone(), details.join(", "), used)
+                        .key(Key::from(osd.id.to_string()))
+                        .interactive(true)
+                        .onclick(
+                            ctx.link()
+                                .callback(move |_| Msg::ShowOsd(Some(osd.clone()))),
+                        )
+                })
+                .collect();
+
+            column.add_child(
+                Container::new()
+                    .padding_x(2)
+                    .padding_top(1)
+                    .class("pwt-font-size-title-small")
+                    .with_child(host),
+            );
+            column.add_child(List::from_tiles(tiles).grid_template_columns("auto 1fr auto"));
+        }
+
+        standard_card(tr!("OSDs"), subtitle, ())
+            .with_child(column)
+            .into()
+    }
+
+    fn view_pgs(&self, data: &CephData) -> Html {
+        let pgmap = &data.status["pgmap"];
+
+        let mut states: Vec<(String, u64)> = pgmap["pgs_by_state"]
+            .as_array()
+            .map(|list| {
+                list.iter()
+                    .map(|state| {
+                        (
+                            state["state_name"].as_str().unwrap_or("").to_string(),
+                            state["count"].as_u64().unwrap_or(0),
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        states.sort_by(|a, b| b.1.cmp(&a.1));
+
+        let tiles: Vec<ListTile> = states
+            .into_iter()
+            .map(|(state, count)| {
+                let clean = state == "active+clean";
+                ListTile::new()
+                    .with_child(
+                        Container::new()
+                            .class((!clean).then_some("pwt-color-warning"))
+                            .with_child(state),
+                    )
+                    .with_child(
+                        Container::new()
+                            .style("text-align", "end")
+                            .with_child(count.to_string()),
+                    )
+            })
+            .collect();
+
+        let used = pgmap["bytes_used"].as_f64().unwrap_or(0.0);
+        let total = pgmap["bytes_total"].as_f64().unwrap_or(0.0);
+        let usage = if total > 0.0 { used / total } else { 0.0 };
+
+        standard_card(
+            tr!("Placement Groups"),
+            tr!("{0} PGs", pgmap["num_pgs"].as_u64().unwrap_or(0)),
+            (),
+        )
+        .with_child(
+            List::from_tiles(vec![
+                icon_list_tile(Fa::new("database"), tr!("Usage"), (), ()).with_child(
+                    list_tile_usage(
+                        HumanByte::new_binary(used).to_string(),
+                        HumanByte::new_binary(total).to_string(),
+                        usage as f32,
+                    ),
+                ),
+            ])
+            .grid_template_columns("auto 1fr"),
+        )
+        .with_child(List::from_tiles(tiles).grid_template_columns("1fr auto"))
+        .into()
+    }
+
+    fn view_pools(&self, data: &CephData) -> Html {
+        let tiles: Vec<ListTile> = data
+            .pools
+            .iter()
+            .map(|pool| {
+                let name = pool["pool_name"].as_str().unwrap_or("").to_string();
+                let subtitle = tr!(
+                    "Size/min: {0}/{1}, PGs: {2}",
+                    pool["size"].as_u64().unwrap_or(0),
+                    pool["min_size"].as_u64().unwrap_or(0),
+                    pool["pg_num"].as_u64().unwrap_or(0)
+                );
+                let used = pool["bytes_used"].as_f64().unwrap_or(0.0);
+                // `percent_used` is a fraction (0..1)
+                let percent_used = pool["percent_used"].as_f64().unwrap_or(0.0);
+                let total = if percent_used > 0.0 {
+                    used / percent_used
+                } else {
+                    0.0
+                };
+
+                icon_list_tile(Fa::new("database"), name.clone(), subtitle, ())
+                    .key(Key::from(name))
+                    .with_child(list_tile_usage(
+                        HumanByte::new_binary(used).to_string(),
+                        HumanByte::new_binary(total).to_string(),
+                        percent_used as f32,
+                    ))
+            })
+            .collect();
+
+        let content: Html = if tiles.is_empty() {
+            Container::new()
+                .padding(2)
+                .with_child(tr!("No pools configured."))
+                .into()
+        } else {
+            List::from_tiles(tiles)
+                .grid_template_columns("auto 1fr")
+                .into()
+        };
+
+        standard_card(tr!("Pools"), (), ())
+            .with_child(content)
+            .into()
+    }
+
+    fn view_osd_dialog(&self, ctx: &Context<Self>) -> Option<Html> {
+        let osd = self.osd.as_ref()?;
+        let busy = self.cmd_guard.is_some();
+
+        let button = |label: String, icon: &str, cmd: &'static str, enabled: bool| {
+            Button::new(label)
+                .icon_class(format!("fa fa-{icon}"))
+                .disabled(busy || !enabled)
+                .on_activate(ctx.link().callback(move |_| Msg::Command(cmd)))
+        };
+
+        let row = Row::new()
+            .gap(2)
+            .with_child(button(tr!("In"), "sign-in", "in", !osd.is_in))
+            .with_child(button(tr!("Out"), "sign-out", "out", osd.is_in))
+            .with_child(button(tr!("Start"), "play", "start", !osd.up))
+            .with_child(button(tr!("Stop"), "stop", "stop", osd.up));
+
+        let confirm = self.confirm.map(|cmd| {
+            let message = if cmd == "stop" {
+                tr!("Stop {0}? Data may become unavailable.", osd.name)
+            } else {
+                tr!("Mark {0} as out? This starts rebalancing data.", osd.name)
+            };
+            ConfirmDialog::default()
+                .confirm_message(message)
+                .on_close(ctx.link().callback(|_| Msg::Confirm(None)))
+                .on_confirm(ctx.link().callback(move |_| Msg::Execute(cmd)))
+        });
+
+        Some(
+            SideDialog::new()
+                .location(pwt::touch::SideDialogLocation::Bottom)
+                .on_close(ctx.link().callback(|_| Msg::ShowOsd(None)))
+                .with_child(
+                    Column::new()
+                        .padding(2)
+                        .gap(2)
+                        .with_child(title_subtitle_column(osd.name.clone(), osd.host.clone()))
+                        .with_child(
+                            MiniScroll::new(row)
+                                .scroll_mode(MiniScrollMode::Native)
+                                .class(pwt::css::Flex::None),
+                        )
+                        .with_optional_child(confirm),
+                )
+                .into(),
+        )
+    }
+}
+
+impl Component for PvePageCeph {
+    type Message = Msg;
+    type Properties = PageCeph;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link().send_message(Msg::Load);
+        Self {
+            data: None,
+            load_guard: None,
+            reload_timeout: None,
+            osd: None,
+            cmd_guard: None,
+            confirm: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Load => {
+                let link = ctx.link().clone();
+                self.load_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = load_ceph_data().await;
+                    link.send_message(Msg::LoadResult(result));
+                }));
+            }
+            Msg::LoadResult(result) => {
+                self.load_guard = None;
+                self.data = Some(result.map_err(|err| err.to_string()));
+                let link = ctx.link().clone();
+                self.reload_timeout = Some(Timeout::new(5000, move || {
+                    link.send_message(Msg::Load);
+                }));
+            }
+            Msg::ShowOsd(osd) => {
+                self.osd = osd;
+                self.confirm = None;
+            }
+            Msg::Confirm(cmd) => self.confirm = cmd,
+            Msg::Command(cmd) => {
+                if cmd == "out" || cmd == "stop" {
+                    self.confirm = Some(cmd);
+                } else {
+                    ctx.link().send_message(Msg::Execute(cmd));
+                }
+            }
+            Msg::Execute(cmd) => {
+                self.confirm = None;
+                let Some(osd) = &self.osd else {
+                    return true;
+                };
+                let node = percent_encode_component(&osd.host);
+                let (url, param) = match cmd {
+                    "in" | "out" => (format!("/nodes/{node}/ceph/osd/{}/{cmd}", osd.id), None),
+                    _ => (
+                        format!("/nodes/{node}/ceph/{cmd}"),
+                        Some(json!({ "service": format!("osd.{}", osd.id) })),
+                    ),
+                };
+                let link = ctx.link().clone();
+                self.cmd_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_post(&url, param).await;
+                    link.send_message(Msg::CommandResult(result));
+                }));
+            }
+            Msg::CommandResult(result) => {
+                self.cmd_guard = None;
+                match result {
+                    Ok(_) => {
+                        self.osd = None;
+                        ctx.link().send_message(Msg::Load);
+                    }
+                    Err(err) => crate::show_failed_command_error(ctx.link(), err),
+                }
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let content = render_loaded_data(&self.data, |data| {
+            Column::new()
+                .gap(2)
+                .with_child(self.view_health(data))
+                .with_child(self.view_services(data))
+                .with_child(self.view_osds(ctx, data))
+                .with_child(self.view_pgs(data))
+                .with_child(self.view_pools(data))
+                .into()
+        });
+
+        Column::new()
+            .class("pwt-fit")
+            .with_child(TopNavBar::new().title(tr!("Ceph")).back("/configuration"))
+            .with_child(
+                Column::new()
+                    .class("pwt-flex-fill")
+                    .class("pwt-overflow-auto")
+                    .padding(2)
+                    .with_child(content),
+            )
+            .with_optional_child(self.view_osd_dialog(ctx))
+            .into()
+    }
+}
+
+impl From<PageCeph> for VNode {
+    fn from(props: PageCeph) -> Self {
+        let comp = VComp::new::<PvePageCeph>(Rc::new(props), None);
+        VNode::from(comp)
+    }
+}
@@ -23,6 +23,10 @@ pub struct PvePageConfiguration {}
 
 static CONFIGS: &[(&'static str, &'static str, &'static Route)] = &[
     ("asterisk", "Settings", &Route::Settings),
+    ("list-alt", "Logs", &Route::Logs),
+    ("database", "Ceph", &Route::Ceph),
+    ("heartbeat", "High Availability", &Route::Ha),
+    ("floppy-o", "Backup Jobs", &Route::BackupJobs),
     //("unlock", "Permissions", &Route::Settings),
 
     /*
@@ -35,14 +39,8 @@ static CONFIGS: &[(&'static str, &'static str, &'static Route)] = &[
     ("database", "Storage", || {
         html! {}
     }),
-    ("floppy-o", "Backup", || {
-        html! {}
-    }),
     ("retweet", "Replication", || {
         html! {}
-    }),
-     ("heartbeat", "High Availability", || {
-        html! {}
     }),
     ("certificate", "ACME", || {
         html! {}
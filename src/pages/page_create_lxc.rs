@@ -0,0 +1,555 @@
+use anyhow::Error;
+use serde_json::{Value, json};
+
+use yew::virtual_dom::VNode;
+
+use pwt::prelude::*;
+use pwt::widget::Column;
+use pwt::widget::form::{Checkbox, Combobox, Field, FormContext, InputType, Number, TextArea};
+
+use proxmox_yew_comp::layout::mobile_form::label_field;
+use proxmox_yew_comp::pve_api_types::StorageEntry;
+use proxmox_yew_comp::{http_get, percent_encoding::percent_encode_component};
+
+use pve_api_types::StorageInfo;
+
+use crate::widgets::{
+    CreateWizardSpec, GuestType, WizardState, create_wizard, field_bool, field_string,
+    load_bridge_list,
+};
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct PageCreateLxc {}
+
+impl PageCreateLxc {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum WizardStep {
+    General,
+    Template,
+    Disks,
+    Cpu,
+    Memory,
+    Network,
+    Dns,
+}
+
+// Data depending on the selected target node
+#[derive(Default)]
+pub struct NodeData {
+    templates: Vec<String>,
+    rootdir_storages: Vec<String>,
+    bridges: Vec<String>,
+}
+
+async fn load_node_data(node: &str) -> Result<NodeData, Error> {
+    let storage_url = format!("/nodes/{}/storage", percent_encode_component(node));
+
+    let storage_list: Vec<StorageInfo> =
+        http_get(&storage_url, Some(json!({"content": "rootdir"}))).await?;
+    let mut rootdir_storages: Vec<String> = storage_list
+        .into_iter()
+        .filter(|info| info.enabled.unwrap_or(true))
+        .map(|info| info.storage)
+        .collect();
+    rootdir_storages.sort();
+
+    let storage_list: Vec<StorageInfo> =
+        http_get(&storage_url, Some(json!({"content": "vztmpl"}))).await?;
+    let mut templates = Vec::new();
+    for info in storage_list
+        .iter()
+        .filter(|info| info.enabled.unwrap_or(true))
+    {
+        let url = format!(
+            "{storage_url}/{}/content",
+            percent_encode_component(&info.storage)
+        );
+        let list: Vec<StorageEntry> = http_get(&url, Some(json!({"content": "vztmpl"}))).await?;
+        templates.extend(list.into_iter().map(|item| item.volid));
+    }
+    templates.sort();
+
+    let bridges = load_bridge_list(node).await?;
+
+    Ok(NodeData {
+        templates,
+        rootdir_storages,
+        bridges,
+    })
+}
+
+// Assemble the ip/gw part of the network device for one address family
+fn ip_config(form_ctx: &FormContext, family: &str, param: &mut Vec<String>) {
+    let ip_key = if family == "ipv4" { "ip" } else { "ip6" };
+    let gw_key = if family == "ipv4" { "gw" } else { "gw6" };
+
+    match field_string(form_ctx, &format!("{family}_mode")).as_deref() {
+        Some("static") => {
+            if let Some(cidr) = field_string(form_ctx, &format!("{family}_cidr")) {
+                param.push(format!("{ip_key}={cidr}"));
+            }
+            if let Some(gw) = field_string(form_ctx, &format!("{family}_gw")) {
+                param.push(format!("{gw_key}={gw}"));
+            }
+        }
+        Some("dhcp") => param.push(format!("{ip_key}=dhcp")),
+        Some("slaac") => param.push(format!("{ip_key}=auto")),
+        _ => {}
+    }
+}
+
+// Assemble the parameters for `POST /nodes/{node}/lxc`
+fn create_params(form_ctx: &FormContext) -> Value {
+    let mut param = json!({});
+
+    let text = |name: &str| field_string(form_ctx, name);
+
+    param["vmid"] = text("vmid").unwrap_or_default().into();
+    if let Some(hostname) = text("hostname") {
+        param["hostname"] = hostname.into();
+    }
+    if let Some(password) = text("password") {
+        param["password"] = password.into();
+    }
+    if let Some(keys) = text("ssh-public-keys") {
+        param["ssh-public-keys"] = keys.into();
+    }
+    param["unprivileged"] = field_bool(form_ctx, "unprivileged").into();
+    if field_bool(form_ctx, "nesting") {
+        param["features"] = "nesting=1".into();
+    }
+
+    if let Some(template) = text("ostemplate") {
+        param["ostemplate"] = template.into();
+    }
+
+    let storage = text("rootfs_storage").unwrap_or_default();
+    let size = text("rootfs_size").unwrap_or_else(|| String::from("8"));
+    param["rootfs"] = format!("{storage}:{size}").into();
+
+    if let Some(cores) = text("cores") {
+        param["cores"] = cores.into();
+    }
+    if let Some(memory) = text("memory") {
+        param["memory"] = memory.into();
+    }
+    if let Some(swap) = text("swap") {
+        param["swap"] = swap.into();
+    }
+
+    if !field_bool(form_ctx, "no_network") {
+        let mut net = vec![String::from("name=eth0")];
+        if let Some(bridge) = text("bridge") {
+            net.push(format!("bridge={bridge}"));
+        }
+        if field_bool(form_ctx, "firewall") {
+            net.push(String::from("firewall=1"));
+        }
+        ip_config(form_ctx, "ipv4", &mut net);
+        ip_config(form_ctx, "ipv6", &mut net);
+        param["net0"] = net.join(",").into();
+    }
+
+    if let Some(searchdomain) = text("searchdomain") {
+        param["searchdomain"] = searchdomain.into();
+    }
+    if let Some(nameserver) = text("nameserver") {
+        param["nameserver"] = nameserver.into();
+    }
+
+    if field_bool(form_ctx, "start") {
+        param["start"] = true.into();
+    }
+
+    param
+}
+
+fn general_panel(state: &WizardState<NodeData>) -> Html {
+    let node_selector = state
+        .nodes
+        .iter()
+        .fold(Combobox::new(), |combo, node| combo.with_item(node.clone()))
+        .name("node")
+        .required(true)
+        .force_selection(true)
+        .default(state.nodes.first().cloned().unwrap_or_default());
+
+    let password = field_string(state.form_ctx, "password");
+    let has_keys = field_string(state.form_ctx, "ssh-public-keys").is_some();
+
+    Column::new()
+        .gap(2)
+        .with_child(label_field(tr!("Node"), node_selector, true))
+        .with_child(label_field(
+            tr!("CT ID"),
+            Number::<u32>::new()
+                .name("vmid")
+                .required(true)
+                .min(100)
+                .default(state.next_id),
+            true,
+        ))
+        .with_child(label_field(
+            tr!("Hostname"),
+            Field::new().name("hostname"),
+            true,
+        ))
+        .with_child(label_field(
+            tr!("Password"),
+            Field::new()
+                .name("password")
+                .input_type(InputType::Password)
+                .required(!has_keys),
+            true,
+        ))
+        .with_child(label_field(
+            tr!("Confirm password"),
+            Field::new()
+                .name("confirm-password")
+                .input_type(InputType::Password)
+                .submit(false)
+                .required(password.is_some())
+                .validate(move |value: &String| {
+                    if Some(value) != password.as_ref() {
+                        Err(Error::msg(tr!("Passwords do not match")))
+                    } else {
+                        Ok(())
+                    }
+                }),
+            true,
+        ))
+        .with_child(label_field(
+            tr!("SSH public key"),
+            TextArea::new().name("ssh-public-keys"),
+            true,
+        ))
+        .with_child(
+            Checkbox::new()
+                .name("unprivileged")
+                .default(true)
+                .box_label(tr!("Unprivileged container")),
+        )
+        .with_child(
+            Checkbox::new()
+                .name("nesting")
+                .default(true)
+                .box_label(tr!("Nesting")),
+        )
+        .into()
+}
+
+fn template_panel(state: &WizardState<NodeData>) -> Html {
+    let template_selector = state
+        .node_data
+        .templates
+        .iter()
+        .fold(Combobox::new(), |combo, template| {
+            combo.with_item(template.clone())
+        })
+        .name("ostemplate")
+        .required(true)
+        .force_selection(true);
+
+    let mut panel =
+        Column::new()
+            .gap(2)
+            .with_child(label_field(tr!("Template"), template_selector, true));
+
+    if !state.loading && state.node_data.templates.is_empty() {
+        panel.add_child(pwt::widget::error_message(&tr!(
+            "No templates found. Please download a template to a storage with 'vztmpl' content first."
+        )));
+    }
+
+    panel.into()
+}
+
+fn disks_panel(state: &WizardState<NodeData>) -> Html {
+    let storage_selector = state
+        .node_data
+        .rootdir_storages
+        .iter()
+        .fold(Combobox::new(), |combo, storage| {
+            combo.with_item(storage.clone())
+        })
+        .name("rootfs_storage")
+        .required(true)
+        .force_selection(true)
+        .default(
+            state
+                .node_data
+                .rootdir_storages
+                .first()
+                .cloned()
+                .unwrap_or_default(),
+        );
+
+    Column::new()
+        .gap(2)
+        .with_child(label_field(tr!("Storage"), storage_selector, true))
+        .with_child(label_field(
+            tr!("Disk size") + " (GiB)",
+            Number::<u64>::new()
+                .name("rootfs_size")
+                .required(true)
+                .min(1)
+                .default(8),
+            true,
+        ))
+        .into()
+}
+
+fn cpu_panel() -> Html {
+    Column::new()
+        .gap(2)
+        .with_child(label_field(
+            tr!("Cores"),
+            Number::<u32>::new()
+                .name("cores")
+                .required(true)
+                .min(1)
+                .default(1),
+            true,
+        ))
+        .into()
+}
+
+fn memory_panel() -> Html {
+    Column::new()
+        .gap(2)
+        .with_child(label_field(
+            tr!("Memory") + " (MiB)",
+            Number::<u64>::new()
+                .name("memory")
+                .required(true)
+                .min(16)
+                .default(512),
+            true,
+        ))
+        .with_child(label_field(
+            tr!("Swap") + " (MiB)",
+            Number::<u64>::new()
+                .name("swap")
+                .required(true)
+                .min(0)
+                .default(512),
+            true,
+        ))
+        .into()
+}
+
+fn ip_fields(
+    state: &WizardState<NodeData>,
+    family: &str,
+    label: &str,
+    modes: Vec<(&str, String)>,
+) -> Html {
+    let disabled = field_bool(state.form_ctx, "no_network");
+    let is_static =
+        field_string(state.form_ctx, &format!("{family}_mode")).as_deref() == Some("static");
+
+    let mode_selector = Combobox::from_key_value_pairs(modes)
+        .name(format!("{family}_mode"))
+        .disabled(disabled)
+        .required(true)
+        .force_selection(true)
+        .default("dhcp");
+
+    Column::new()
+        .gap(2)
+        .with_child(label_field(label.to_string(), mode_selector, true))
+        .with_child(label_field(
+            format!("{label} ({})", tr!("CIDR")),
+            Field::new()
+                .name(format!("{family}_cidr"))
+                .disabled(disabled || !is_static)
+                .required(!disabled && is_static),
+            true,
+        ))
+        .with_child(label_field(
+            format!("{label} ({})", tr!("Gateway")),
+            Field::new()
+                .name(format!("{family}_gw"))
+                .disabled(disabled || !is_static),
+            true,
+        ))
+        .into()
+}
+
+fn network_panel(state: &WizardState<NodeData>) -> Html {
+    let disabled = field_bool(state.form_ctx, "no_network");
+
+    let bridge_selector = state
+        .node_data
+        .bridges
+        .iter()
+        .fold(Combobox::new(), |combo, bridge| {
+            combo.with_item(bridge.clone())
+        })
+        .name("bridge")
+        .disabled(disabled)
+        .required(!disabled)
+        .force_selection(true)
+        .default(state.node_data.bridges.first().cloned().unwrap_or_default());
+
+    Column::new()
+        .gap(2)
+        .with_child(
+            Checkbox::new()
+                .name("no_network")
+                .box_label(tr!("No network device")),
+        )
+        .with_child(label_field(tr!("Bridge"), bridge_selector, true))
+        .with_child(
+            Checkbox::new()
+                .name("firewall")
+                .disabled(disabled)
+                .default(true)
+                .box_label(tr!("Firewall")),
+        )
+        .with_child(ip_fields(
+            state,
+            "ipv4",
+            "IPv4",
+            vec![
+                ("dhcp", String::from("DHCP")),
+                ("static", tr!("Static")),
+                ("none", tr!("None")),
+            ],
+        ))
+        .with_child(ip_fields(
+            state,
+            "ipv6",
+            "IPv6",
+            vec![
+                ("dhcp", String::from("DHCP")),
+                ("slaac", String::from("SLAAC")),
+                ("static", tr!("Static")),
+                ("none", tr!("None")),
+            ],
+        ))
+        .into()
+}
+
+fn dns_panel() -> Html {
+    Column::new()
+        .gap(2)
+        .with_child(label_field(
+            tr!("DNS domain"),
+            Field::new()
+                .name("searchdomain")
+                .placeholder(tr!("use host settings")),
+            true,
+        ))
+        .with_child(label_field(
+            tr!("DNS servers"),
+            Field::new()
+                .name("nameserver")
+                .placeholder(tr!("use host settings")),
+            true,
+        ))
+        .into()
+}
+
+impl CreateWizardSpec for PageCreateLxc {
+    type NodeData = NodeData;
+    type Step = WizardStep;
+
+    const GUEST_TYPE: GuestType = GuestType::Lxc;
+    const STEPS: &'static [WizardStep] = &[
+        WizardStep::General,
+        WizardStep::Template,
+        WizardStep::Disks,
+        WizardStep::Cpu,
+        WizardStep::Memory,
+        WizardStep::Network,
+        WizardStep::Dns,
+    ];
+
+    fn title(&self) -> String {
+        tr!("Create CT")
+    }
+
+    fn step_title(&self, step: WizardStep) -> String {
+        match step {
+            WizardStep::General => tr!("General"),
+            WizardStep::Template => tr!("Template"),
+            WizardStep::Disks => tr!("Disks"),
+            WizardStep::Cpu => tr!("CPU"),
+            WizardStep::Memory => tr!("Memory"),
+            WizardStep::Network => tr!("Network"),
+            WizardStep::Dns => tr!("DNS"),
+        }
+    }
+
+    fn step_fields(&self, step: WizardStep) -> &'static [&'static str] {
+        match step {
+            WizardStep::General => &[
+                "node",
+                "vmid",
+                "hostname",
+                "password",
+                "confirm-password",
+                "ssh-public-keys",
+            ],
+            WizardStep::Template => &["ostemplate"],
+            WizardStep::Disks => &["rootfs_storage", "rootfs_size"],
+            WizardStep::Cpu => &["cores"],
+            WizardStep::Memory => &["memory", "swap"],
+            WizardStep::Network => &[
+                "bridge",
+                "ipv4_mode",
+                "ipv4_cidr",
+                "ipv4_gw",
+                "ipv6_mode",
+                "ipv6_cidr",
+                "ipv6_gw",
+            ],
+            WizardStep::Dns => &["searchdomain", "nameserver"],
+        }
+    }
+
+    // password confirmation and network fields depend on other fields
+    fn redraw_on_change(&self, step: WizardStep) -> bool {
+        matches!(step, WizardStep::General | WizardStep::Network)
+    }
+
+    async fn load_node_data(node: &str) -> Result<NodeData, Error> {
+        load_node_data(node).await
+    }
+
+    fn view_step(&self, step: WizardStep, state: &WizardState<NodeData>) -> Html {
+        match step {
+            WizardStep::General => general_panel(state),
+            WizardStep::Template => template_panel(state),
+            WizardStep::Disks => disks_panel(state),
+            WizardStep::Cpu => cpu_panel(),
+            WizardStep::Memory => memory_panel(),
+            WizardStep::Network => network_panel(state),
+            WizardStep::Dns => dns_panel(),
+        }
+    }
+
+    fn create_params(&self, form_ctx: &FormContext) -> Value {
+        create_params(form_ctx)
+    }
+
+    fn confirm_params(&self, form_ctx: &FormContext) -> Value {
+        let mut param = create_params(form_ctx);
+        if param.get("password").is_some() {
+            param["password"] = "********".into();
+        }
+        param
+    }
+}
+
+impl From<PageCreateLxc> for VNode {
+    fn from(props: PageCreateLxc) -> Self {
+        create_wizard(props)
+    }
+}
@@ -0,0 +1,516 @@
+use anyhow::Error;
+use serde_json::{Value, json};
+
+use yew::virtual_dom::VNode;
+
+use pwt::prelude::*;
+use pwt::widget::Column;
+use pwt::widget::form::{Checkbox, Combobox, Field, FormContext, Number};
+
+use proxmox_yew_comp::layout::mobile_form::label_field;
+use proxmox_yew_comp::{http_get, percent_encoding::percent_encode_component};
+
+use pve_api_types::StorageInfo;
+
+use crate::api_types::{QemuCpuModel, QemuMachineInfo, QemuMachineType};
+use crate::widgets::{
+    CreateWizardSpec, GuestType, WizardState, create_wizard, field_bool, field_string,
+    load_bridge_list, load_iso_list,
+};
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct PageCreateQemu {}
+
+impl PageCreateQemu {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum WizardStep {
+    General,
+    Os,
+    System,
+    Disks,
+    Cpu,
+    Memory,
+    Network,
+}
+
+// Data depending on the selected target node
+#[derive(Default)]
+pub struct NodeData {
+    iso_list: Vec<String>,
+    image_storages: Vec<String>,
+    machines: Vec<QemuMachineInfo>,
+    cpu_models: Vec<QemuCpuModel>,
+    bridges: Vec<String>,
+}
+
+async fn load_node_data(node: &str) -> Result<NodeData, Error> {
+    let base_url = format!("/nodes/{}", percent_encode_component(node));
+
+    let storage_url = format!("{base_url}/storage");
+
+    let storage_list: Vec<StorageInfo> =
+        http_get(&storage_url, Some(json!({"content": "images"}))).await?;
+    let mut image_storages: Vec<String> = storage_list
+        .into_iter()
+        .filter(|info| info.enabled.unwrap_or(true))
+        .map(|info| info.storage)
+        .collect();
+    image_storages.sort();
+
+    let iso_list = load_iso_list(node).await?;
+
+    let machines: Vec<QemuMachineInfo> =
+        http_get(&format!("{base_url}/capabilities/qemu/machines"), None).await?;
+
+    let mut cpu_models: Vec<QemuCpuModel> =
+        http_get(&format!("{base_url}/capabilities/qemu/cpu"), None).await?;
+    cpu_models.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let bridges = load_bridge_list(node).await?;
+
+    Ok(NodeData {
+        iso_list,
+        image_storages,
+        machines,
+        cpu_models,
+        bridges,
+    })
+}
+
+// Assemble the parameters for `POST /nodes/{node}/qemu`
+fn create_params(form_ctx: &FormContext) -> Value {
+    let mut param = json!({});
+
+    let text = |name: &str| field_string(form_ctx, name);
+
+    param["vmid"] = text("vmid").unwrap_or_default().into();
+    if let Some(name) = text("name") {
+        param["name"] = name.into();
+    }
+
+    if let Some(ostype) = text("ostype") {
+        param["ostype"] = ostype.into();
+    }
+    param["ide2"] = match text("iso") {
+        Some(iso) => format!("{iso},media=cdrom"),
+        None => String::from("none,media=cdrom"),
+    }
+    .into();
+
+    if let Some(machine) = text("machine") {
+        param["machine"] = machine.into();
+    }
+    let bios = text("bios").unwrap_or_else(|| String::from("seabios"));
+    if let Some(scsihw) = text("scsihw") {
+        param["scsihw"] = scsihw.into();
+    }
+    if field_bool(form_ctx, "agent") {
+        param["agent"] = "1".into();
+    }
+
+    let storage = text("disk_storage").unwrap_or_default();
+    let bus = text("disk_bus").unwrap_or_else(|| String::from("scsi"));
+    let size = text("disk_size").unwrap_or_else(|| String::from("32"));
+    let mut disk = format!("{storage}:{size}");
+    if field_bool(form_ctx, "disk_discard") {
+        disk.push_str(",discard=on");
+    }
+    param[format!("{bus}0")] = disk.into();
+
+    if bios == "ovmf" {
+        let efi_storage = text("efidisk_storage").unwrap_or(storage);
+        param["efidisk0"] = format!("{efi_storage}:1,efitype=4m,pre-enrolled-keys=1").into();
+    }
+    param["bios"] = bios.into();
+
+    if let Some(sockets) = text("sockets") {
+        param["sockets"] = sockets.into();
+    }
+    if let Some(cores) = text("cores") {
+        param["cores"] = cores.into();
+    }
+    if let Some(cpu) = text("cpu") {
+        param["cpu"] = cpu.into();
+    }
+    if let Some(memory) = text("memory") {
+        param["memory"] = memory.into();
+    }
+
+    if !field_bool(form_ctx, "no_network") {
+        let model = text("net_model").unwrap_or_else(|| String::from("virtio"));
+        let mut net = model;
+        if let Some(bridge) = text("bridge") {
+            net.push_str(&format!(",bridge={bridge}"));
+        }
+        if field_bool(form_ctx, "firewall") {
+            net.push_str(",firewall=1");
+        }
+        param["net0"] = net.into();
+    }
+
+    if field_bool(form_ctx, "start") {
+        param["start"] = true.into();
+    }
+
+    param
+}
+
+fn general_panel(state: &WizardState<NodeData>) -> Html {
+    let node_selector = state
+        .nodes
+        .iter()
+        .fold(Combobox::new(), |combo, node| combo.with_item(node.clone()))
+        .name("node")
+        .required(true)
+        .force_selection(true)
+        .default(state.nodes.first().cloned().unwrap_or_default());
+
+    Column::new()
+        .gap(2)
+        .with_child(label_field(tr!("Node"), node_selector, true))
+        .with_child(label_field(
+            tr!("VM ID"),
+            Number::<u32>::new()
+                .name("vmid")
+                .required(true)
+                .min(100)
+                .default(state.next_id),
+            true,
+        ))
+        .with_child(label_field(tr!("Name"), Field::new().name("name"), true))
+        .into()
+}
+
+fn os_panel(state: &WizardState<NodeData>) -> Html {
+    let iso_selector = state
+        .node_data
+        .iso_list
+        .iter()
+        .fold(Combobox::new(), |combo, iso| combo.with_item(iso.clone()))
+        .name("iso")
+        .force_selection(true)
+        .placeholder(tr!("Do not use any media"));
+
+    let ostype_selector = Combobox::from_key_value_pairs([
+        ("l26", String::from("Linux 6.x - 2.6 Kernel")),
+        ("l24", String::from("Linux 2.4 Kernel")),
+        ("win11", String::from("Microsoft Windows 11/2022/2025")),
+        ("win10", String::from("Microsoft Windows 10/2016/2019")),
+        ("win8", String::from("Microsoft Windows 8.x/2012/2012r2")),
+        ("win7", String::from("Microsoft Windows 7/2008r2")),
+        ("solaris", String::from("Solaris Kernel")),
+        ("other", tr!("Other")),
+    ])
+    .name("ostype")
+    .required(true)
+    .force_selection(true)
+    .default("l26");
+
+    Column::new()
+        .gap(2)
+        .with_child(label_field(tr!("ISO Image"), iso_selector, true))
+        .with_child(label_field(tr!("Guest OS"), ostype_selector, true))
+        .into()
+}
+
+fn system_panel(state: &WizardState<NodeData>) -> Html {
+    // Virt is ARM only
+    let machine_selector = state
+        .node_data
+        .machines
+        .iter()
+        .filter(|info| matches!(info.ty, QemuMachineType::Q35 | QemuMachineType::I440fx))
+        .fold(Combobox::new().with_item("q35"), |combo, info| {
+            combo.with_item(info.id.clone())
+        })
+        .name("machine")
+        .force_selection(true)
+        .placeholder(tr!("Default") + " (i440fx)");
+
+    let bios_selector = Combobox::from_key_value_pairs([
+        ("seabios", String::from("SeaBIOS")),
+        ("ovmf", String::from("OVMF (UEFI)")),
+    ])
+    .name("bios")
+    .required(true)
+    .force_selection(true)
+    .default("seabios");
+
+    let scsihw_selector = Combobox::from_key_value_pairs([
+        ("virtio-scsi-single", String::from("VirtIO SCSI single")),
+        ("virtio-scsi-pci", String::from("VirtIO SCSI")),
+        ("lsi", String::from("LSI 53C895A")),
+        ("megasas", String::from("MegaRAID SAS 8708EM2")),
+        ("pvscsi", String::from("VMware PVSCSI")),
+    ])
+    .name("scsihw")
+    .required(true)
+    .force_selection(true)
+    .default("virtio-scsi-single");
+
+    // OVMF needs an EFI disk to store the UEFI variables
+    let ovmf = field_string(state.form_ctx, "bios").as_deref() == Some("ovmf");
+    let efi_storage_selector = state
+        .node_data
+        .image_storages
+        .iter()
+        .fold(Combobox::new(), |combo, storage| {
+            combo.with_item(storage.clone())
+        })
+        .name("efidisk_storage")
+        .disabled(!ovmf)
+        .required(ovmf)
+        .force_selection(true)
+        .default(
+            state
+                .node_data
+                .image_storages
+                .first()
+                .cloned()
+                .unwrap_or_default(),
+        );
+
+    Column::new()
+        .gap(2)
+        .with_child(label_field(tr!("Machine"), machine_selector, true))
+        .with_child(label_field(tr!("BIOS"), bios_selector, true))
+        .with_child(label_field(tr!("EFI Storage"), efi_storage_selector, true))
+        .with_child(label_field(tr!("SCSI Controller"), scsihw_selector, true))
+        .with_child(
+            Checkbox::new()
+                .name("agent")
+                .box_label(tr!("QEMU Guest Agent")),
+        )
+        .into()
+}
+
+fn disks_panel(state: &WizardState<NodeData>) -> Html {
+    let bus_selector = Combobox::from_key_value_pairs([
+        ("scsi", String::from("SCSI")),
+        ("virtio", String::from("VirtIO Block")),
+        ("sata", String::from("SATA")),
+        ("ide", String::from("IDE")),
+    ])
+    .name("disk_bus")
+    .required(true)
+    .force_selection(true)
+    .default("scsi");
+
+    let storage_selector = state
+        .node_data
+        .image_storages
+        .iter()
+        .fold(Combobox::new(), |combo, storage| {
+            combo.with_item(storage.clone())
+        })
+        .name("disk_storage")
+        .required(true)
+        .force_selection(true)
+        .default(
+            state
+                .node_data
+                .image_storages
+                .first()
+                .cloned()
+                .unwrap_or_default(),
+        );
+
+    Column::new()
+        .gap(2)
+        .with_child(label_field(tr!("Bus/Device"), bus_selector, true))
+        .with_child(label_field(tr!("Storage"), storage_selector, true))
+        .with_child(label_field(
+            tr!("Disk size") + " (GiB)",
+            Number::<u64>::new()
+                .name("disk_size")
+                .required(true)
+                .min(1)
+                .default(32),
+            true,
+        ))
+        .with_child(
+            Checkbox::new()
+                .name("disk_discard")
+                .box_label(tr!("Discard")),
+        )
+        .into()
+}
+
+fn cpu_panel(state: &WizardState<NodeData>) -> Html {
+    let cpu_selector = state
+        .node_data
+        .cpu_models
+        .iter()
+        .fold(Combobox::new(), |combo, model| {
+            combo.with_item(model.name.clone())
+        })
+        .name("cpu")
+        .force_selection(true)
+        .placeholder(tr!("Default") + " (x86-64-v2-AES)");
+
+    Column::new()
+        .gap(2)
+        .with_child(label_field(
+            tr!("Sockets"),
+            Number::<u32>::new()
+                .name("sockets")
+                .required(true)
+                .min(1)
+                .max(4)
+                .default(1),
+            true,
+        ))
+        .with_child(label_field(
+            tr!("Cores"),
+            Number::<u32>::new()
+                .name("cores")
+                .required(true)
+                .min(1)
+                .default(1),
+            true,
+        ))
+        .with_child(label_field(tr!("Type"), cpu_selector, true))
+        .into()
+}
+
+fn memory_panel() -> Html {
+    Column::new()
+        .gap(2)
+        .with_child(label_field(
+            tr!("Memory") + " (MiB)",
+            Number::<u64>::new()
+                .name("memory")
+                .required(true)
+                .min(16)
+                .default(2048),
+            true,
+        ))
+        .into()
+}
+
+fn network_panel(state: &WizardState<NodeData>) -> Html {
+    let disabled = field_bool(state.form_ctx, "no_network");
+
+    let bridge_selector = state
+        .node_data
+        .bridges
+        .iter()
+        .fold(Combobox::new(), |combo, bridge| {
+            combo.with_item(bridge.clone())
+        })
+        .name("bridge")
+        .disabled(disabled)
+        .required(!disabled)
+        .force_selection(true)
+        .default(state.node_data.bridges.first().cloned().unwrap_or_default());
+
+    let model_selector = Combobox::from_key_value_pairs([
+        ("virtio", String::from("VirtIO (paravirtualized)")),
+        ("e1000", String::from("Intel E1000")),
+        ("rtl8139", String::from("Realtek RTL8139")),
+        ("vmxnet3", String::from("VMware vmxnet3")),
+    ])
+    .name("net_model")
+    .disabled(disabled)
+    .required(true)
+    .force_selection(true)
+    .default("virtio");
+
+    Column::new()
+        .gap(2)
+        .with_child(
+            Checkbox::new()
+                .name("no_network")
+                .box_label(tr!("No network device")),
+        )
+        .with_child(label_field(tr!("Bridge"), bridge_selector, true))
+        .with_child(label_field(tr!("Model"), model_selector, true))
+        .with_child(
+            Checkbox::new()
+                .name("firewall")
+                .disabled(disabled)
+                .default(true)
+                .box_label(tr!("Firewall")),
+        )
+        .into()
+}
+
+impl CreateWizardSpec for PageCreateQemu {
+    type NodeData = NodeData;
+    type Step = WizardStep;
+
+    const GUEST_TYPE: GuestType = GuestType::Qemu;
+    const STEPS: &'static [WizardStep] = &[
+        WizardStep::General,
+        WizardStep::Os,
+        WizardStep::System,
+        WizardStep::Disks,
+        WizardStep::Cpu,
+        WizardStep::Memory,
+        WizardStep::Network,
+    ];
+
+    fn title(&self) -> String {
+        tr!("Create VM")
+    }
+
+    fn step_title(&self, step: WizardStep) -> String {
+        match step {
+            WizardStep::General => tr!("General"),
+            WizardStep::Os => tr!("OS"),
+            WizardStep::System => tr!("System"),
+            WizardStep::Disks => tr!("Disks"),
+            WizardStep::Cpu => tr!("CPU"),
+            WizardStep::Memory => tr!("Memory"),
+            WizardStep::Network => tr!("Network"),
+        }
+    }
+
+    fn step_fields(&self, step: WizardStep) -> &'static [&'static str] {
+        match step {
+            WizardStep::General => &["node", "vmid", "name"],
+            WizardStep::Os => &["iso", "ostype"],
+            WizardStep::System => &["machine", "bios", "efidisk_storage", "scsihw"],
+            WizardStep::Disks => &["disk_bus", "disk_storage", "disk_size"],
+            WizardStep::Cpu => &["cpu", "sockets", "cores"],
+            WizardStep::Memory => &["memory"],
+            WizardStep::Network => &["bridge", "net_model"],
+        }
+    }
+
+    // the EFI storage depends on the BIOS, the network step on the 'no_network' checkbox
+    fn redraw_on_change(&self, step: WizardStep) -> bool {
+        matches!(step, WizardStep::System | WizardStep::Network)
+    }
+
+    async fn load_node_data(node: &str) -> Result<NodeData, Error> {
+        load_node_data(node).await
+    }
+
+    fn view_step(&self, step: WizardStep, state: &WizardState<NodeData>) -> Html {
+        match step {
+            WizardStep::General => general_panel(state),
+            WizardStep::Os => os_panel(state),
+            WizardStep::System => system_panel(state),
+            WizardStep::Disks => disks_panel(state),
+            WizardStep::Cpu => cpu_panel(state),
+            WizardStep::Memory => memory_panel(),
+            WizardStep::Network => network_panel(state),
+        }
+    }
+
+    fn create_params(&self, form_ctx: &FormContext) -> Value {
+        create_params(form_ctx)
+    }
+}
+
+impl From<PageCreateQemu> for VNode {
+    fn from(props: PageCreateQemu) -> Self {
+        create_wizard(props)
+    }
+}
@@ -2,6 +2,7 @@ use std::cell::RefCell;
 use std::rc::Rc;
 
 use anyhow::Error;
+use serde_json::Value;
 
 use proxmox_human_byte::HumanByte;
 use yew::prelude::*;
@@ -18,8 +19,10 @@ use pve_api_types::{
 use proxmox_yew_comp::layout::card::standard_card;
 use proxmox_yew_comp::layout::list_tile::{icon_list_tile, list_tile_usage};
 use proxmox_yew_comp::layout::render_loaded_data;
-use proxmox_yew_comp::{http_get, SubscriptionAlert};
+use proxmox_yew_comp::{http_get, percent_encoding::percent_encode_component, SubscriptionAlert};
 
+use crate::pages::page_ceph::ceph_health_icon;
+use crate::pages::page_node_status::{ZfsPoolInfo, load_repository_data, repository_warnings};
 use crate::pages::ResourceFilter;
 use crate::widgets::TopNavBar;
 
@@ -38,6 +41,12 @@ struct CachedData {
     resources: Option<Result<Vec<ClusterResource>, Error>>,
     subscription_confirmed: bool,
     subscription_error: Option<String>, // None == Ok
+    // ZFS pools which are not healthy (node, pool, health)
+    zfs_warnings: Vec<(String, String, String)>,
+    // repository setup problems related to the subscription (node, message)
+    repository_warnings: Vec<(String, String)>,
+    // `None` if Ceph is not configured
+    ceph_status: Option<Value>,
 }
 
 thread_local! {
@@ -50,6 +59,9 @@ pub struct PvePageDashboard {
 pub enum Msg {
     NodeLoadResult(Result<Vec<ClusterNodeIndexResponse>, Error>),
     ResourcesLoadResult(Result<Vec<ClusterResource>, Error>),
+    ZfsLoadResult((String, Result<Vec<ZfsPoolInfo>, Error>)),
+    RepositoryWarnings((String, Vec<String>)),
+    CephLoadResult(Result<Value, Error>),
     ConfirmSubscription,
     ShowSubscriptionAlert,
 }
@@ -66,6 +78,11 @@ impl PvePageDashboard {
             let result = http_get("/cluster/resources", None).await;
             link.send_message(Msg::ResourcesLoadResult(result));
         });
+        let link = ctx.link().clone();
+        wasm_bindgen_futures::spawn_local(async move {
+            let result = http_get("/cluster/ceph/status", None).await;
+            link.send_message(Msg::CephLoadResult(result));
+        });
     }
 
     fn create_subscription_card(&self, ctx: &Context<Self>) -> Option<Html> {
@@ -85,6 +102,132 @@ impl PvePageDashboard {
         }
     }
 
+    fn load_zfs_status(&self, ctx: &Context<Self>, nodes: Vec<String>) {
+        for node in nodes {
+            let link = ctx.link().clone();
+            let url = format!("/nodes/{}/disks/zfs", percent_encode_component(&node));
+            wasm_bindgen_futures::spawn_local(async move {
+                let result = http_get(&url, None).await;
+                link.send_message(Msg::ZfsLoadResult((node, result)));
+            });
+        }
+    }
+
+    fn load_repository_status(&self, ctx: &Context<Self>, nodes: Vec<String>) {
+        for node in nodes {
+            let link = ctx.link().clone();
+            wasm_bindgen_futures::spawn_local(async move {
+                // ignore errors, the node status page shows them
+                let warnings = match load_repository_data(&node).await {
+                    Ok(data) => repository_warnings(&data)
+                        .into_iter()
+                        .filter(|warning| warning.kind.affects_subscription())
+                        .map(|warning| warning.message)
+                        .collect(),
+                    Err(_) => Vec::new(),
+                };
+                link.send_message(Msg::RepositoryWarnings((node, warnings)));
+            });
+        }
+    }
+
+    fn create_repository_warning_card(&self, ctx: &Context<Self>) -> Option<Html> {
+        let warnings = CACHE.with_borrow(|cache| cache.repository_warnings.clone());
+        if warnings.is_empty() {
+            return None;
+        }
+
+        let navigator = ctx.link().navigator().clone().unwrap();
+        let tiles: Vec<ListTile> = warnings
+            .into_iter()
+            .map(|(node, message)| {
+                let navigator = navigator.clone();
+                icon_list_tile(
+                    Fa::new("exclamation-triangle").class("pwt-color-warning"),
+                    message,
+                    node.clone(),
+                    (),
+                )
+                .interactive(true)
+                .onclick(move |_: MouseEvent| {
+                    navigator.push(&crate::Route::Node {
+                        nodename: node.clone(),
+                    });
+                })
+            })
+            .collect();
+
+        Some(
+            standard_card(tr!("Repository Warnings"), (), ())
+                .with_child(List::from_tiles(tiles).grid_template_columns("auto 1fr"))
+                .into(),
+        )
+    }
+
+    fn create_zfs_warning_card(&self, ctx: &Context<Self>) -> Option<Html> {
+        let warnings = CACHE.with_borrow(|cache| cache.zfs_warnings.clone());
+        if warnings.is_empty() {
+            return None;
+        }
+
+        let navigator = ctx.link().navigator().clone().unwrap();
+        let tiles: Vec<ListTile> = warnings
+            .into_iter()
+            .map(|(node, pool, health)| {
+                let navigator = navigator.clone();
+                icon_list_tile(
+                    Fa::new("exclamation-triangle").class("pwt-color-warning"),
+                    tr!("ZFS pool '{0}' is {1}", pool, health),
+                    node.clone(),
+                    (),
+                )
+                .interactive(true)
+                .onclick(move |_: MouseEvent| {
+                    navigator.push(&crate::Route::Node {
+                        nodename: node.clone(),
+                    });
+                })
+            })
+            .collect();
+
+        Some(
+            standard_card(tr!("Storage Warnings"), (), ())
+                .with_child(List::from_tiles(tiles).grid_template_columns("auto 1fr"))
+                .into(),
+        )
+    }
+
+    fn create_ceph_card(&self, ctx: &Context<Self>) -> Option<Html> {
+        let status = CACHE.with_borrow(|cache| cache.ceph_status.clone())?;
+
+        let health = status["health"]["status"]
+            .as_str()
+            .unwrap_or("HEALTH_ERR")
+            .to_string();
+        let checks = status["health"]["checks"]
+            .as_object()
+            .map(|checks| checks.len())
+            .unwrap_or(0);
+        let subtitle = if checks > 0 {
+            tr!("{0} health checks", checks)
+        } else {
+            tr!("Ceph cluster")
+        };
+
+        let navigator = ctx.link().navigator().clone().unwrap();
+        let tile = icon_list_tile(ceph_health_icon(&health), health, subtitle, ())
+            .interactive(true)
+            .onclick(move |_: MouseEvent| {
+                navigator.push(&crate::Route::Ceph);
+            });
+
+        Some(
+            standard_card(tr!("Ceph"), (), ())
+                .with_child(List::from_tiles(vec![tile]).grid_template_columns("auto 1fr"))
+                .into(),
+        )
+    }
+
     fn create_analytics_card(&self, _ctx: &Context<Self>) -> Html {
         let content = CACHE.with_borrow(|cache| {
             let data = match (&cache.nodes, &cache.resources) {
@@ -373,12 +516,21 @@ impl Component for PvePageDashboard {
         me
     }
 
-    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::ResourcesLoadResult(result) => {
                 CACHE.with_borrow_mut(|cache| cache.resources = Some(result));
             }
             Msg::NodeLoadResult(result) => {
+                if let Ok(nodes) = &result {
+                    let online: Vec<String> = nodes
+                        .iter()
+                        .filter(|item| item.status == ClusterNodeIndexResponseStatus::Online)
+                        .map(|item| item.node.clone())
+                        .collect();
+                    self.load_zfs_status(ctx, online.clone());
+                    self.load_repository_status(ctx, online);
+                }
                 CACHE.with_borrow_mut(|cache| {
                     cache.nodes = Some(result);
 
@@ -426,6 +578,32 @@ impl Component for PvePageDashboard {
                     }
                 })
             }
+            Msg::ZfsLoadResult((node, result)) => {
+                // nodes without ZFS simply have no pools, so ignore errors
+                let pools = result.unwrap_or_default();
+                CACHE.with_borrow_mut(|cache| {
+                    cache.zfs_warnings.retain(|(n, _, _)| n != &node);
+                    for pool in pools {
+                        if pool.health != "ONLINE" {
+                            cache
+                                .zfs_warnings
+                                .push((node.clone(), pool.name, pool.health));
+                        }
+                    }
+                });
+            }
+            Msg::CephLoadResult(result) => {
+                // the status call fails if Ceph is not configured
+                CACHE.with_borrow_mut(|cache| cache.ceph_status = result.ok());
+            }
+            Msg::RepositoryWarnings((node, warnings)) => {
+                CACHE.with_borrow_mut(|cache| {
+                    cache.repository_warnings.retain(|(n, _)| n != &node);
+                    cache
+                        .repository_warnings
+                        .extend(warnings.into_iter().map(|message| (node.clone(), message)));
+                });
+            }
             Msg::ConfirmSubscription => {
                 self.show_subscription_alert = false;
                 CACHE.with_borrow_mut(|cache| {
@@ -448,6 +626,9 @@ impl Component for PvePageDashboard {
             .padding(2)
             .gap(2)
             .with_optional_child(self.create_subscription_card(ctx))
+            .with_optional_child(self.create_repository_warning_card(ctx))
+            .with_optional_child(self.create_zfs_warning_card(ctx))
+            .with_optional_child(self.create_ceph_card(ctx))
             .with_child(self.create_analytics_card(ctx))
             .with_child(self.create_nodes_card(ctx))
             .with_child(self.create_guests_card(ctx));
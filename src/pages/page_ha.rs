@@ -0,0 +1,247 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+use gloo_timers::callback::Timeout;
+use serde_json::Value;
+
+use yew::virtual_dom::{Key, VComp, VNode};
+use yew_router::scope_ext::RouterScopeExt;
+
+use pwt::AsyncAbortGuard;
+use pwt::prelude::*;
+use pwt::widget::{Column, Container, Fa, List, ListTile};
+
+use proxmox_yew_comp::http_get;
+use proxmox_yew_comp::layout::card::standard_card;
+use proxmox_yew_comp::layout::list_tile::icon_list_tile;
+use proxmox_yew_comp::layout::render_loaded_data;
+
+use crate::widgets::TopNavBar;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct PageHa {}
+
+impl PageHa {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+pub struct HaData {
+    // `GET /cluster/ha/status/current`
+    status: Vec<Value>,
+    // `GET /cluster/ha/resources`
+    resources: Vec<Value>,
+}
+
+pub enum Msg {
+    Load,
+    LoadResult(Result<HaData, Error>),
+}
+
+pub struct PvePageHa {
+    data: Option<Result<HaData, String>>,
+    load_guard: Option<AsyncAbortGuard>,
+    reload_timeout: Option<Timeout>,
+}
+
+fn status_icon(ok: bool) -> Fa {
+    if ok {
+        Fa::new("check-circle").class("pwt-color-primary")
+    } else {
+        Fa::new("exclamation-triangle").class("pwt-color-warning")
+    }
+}
+
+fn value_str(value: &Value, key: &str) -> String {
+    value[key].as_str().unwrap_or("").to_string()
+}
+
+impl PvePageHa {
+    fn view_status(&self, data: &HaData) -> Html {
+        let mut tiles: Vec<ListTile> = Vec::new();
+
+        for entry in data.status.iter() {
+            let status = value_str(entry, "status");
+            match entry["type"].as_str() {
+                Some("quorum") => {
+                    let ok = entry["quorate"].as_u64() == Some(1) || status == "OK";
+                    tiles.push(icon_list_tile(status_icon(ok), tr!("Quorum"), status, ()));
+                }
+                Some("master") => {
+                    tiles.push(icon_list_tile(
+                        status_icon(!status.is_empty()),
+                        tr!("Master"),
+                        status,
+                        value_str(entry, "node"),
+                    ));
+                }
+                Some("lrm") => {
+                    // status looks like "node (active, <timestamp>)"
+                    let ok = status.contains("active") || status.contains("idle");
+                    tiles.push(icon_list_tile(
+                        status_icon(ok),
+                        tr!("LRM {0}", value_str(entry, "node")),
+                        status,
+                        (),
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        if tiles.is_empty() {
+            tiles.push(icon_list_tile(
+                Fa::new("info-circle"),
+                tr!("No HA status available"),
+                (),
+                (),
+            ));
+        }
+
+        standard_card(tr!("Status"), (), ())
+            .with_child(List::from_tiles(tiles).grid_template_columns("auto 1fr auto"))
+            .into()
+    }
+
+    fn view_resources(&self, ctx: &Context<Self>, data: &HaData) -> Html {
+        let navigator = ctx.link().navigator().unwrap();
+
+        let tiles: Vec<ListTile> = data
+            .resources
+            .iter()
+            .map(|resource| {
+                let sid = value_str(resource, "sid");
+                let requested = resource["state"].as_str().unwrap_or("started");
+                let entry = data
+                    .status
+                    .iter()
+                    .find(|entry| entry["sid"] == sid.as_str());
+                let current = entry
+                    .and_then(|entry| entry["state"].as_str().or(entry["crm_state"].as_str()))
+                    .unwrap_or("unknown");
+                let node = entry.map(|entry| value_str(entry, "node"));
+
+                let ok = current == requested
+                    || requested == "ignored"
+                    || (requested == "disabled" && current == "stopped");
+                let subtitle = tr!("Requested: {0}, Current: {1}", requested, current);
+
+                let mut tile = icon_list_tile(
+                    status_icon(ok),
+                    sid.clone(),
+                    subtitle,
+                    node.clone().unwrap_or_default(),
+                )
+                .key(Key::from(sid.clone()));
+
+                let route = match (sid.split_once(':'), node) {
+                    (Some(("vm", vmid)), Some(nodename)) => vmid
+                        .parse()
+                        .ok()
+                        .map(|vmid| crate::Route::Qemu { vmid, nodename }),
+                    (Some(("ct", vmid)), Some(nodename)) => vmid
+                        .parse()
+                        .ok()
+                        .map(|vmid| crate::Route::Lxc { vmid, nodename }),
+                    _ => None,
+                };
+                if let Some(route) = route {
+                    let navigator = navigator.clone();
+                    tile = tile
+                        .interactive(true)
+                        .onclick(move |_: MouseEvent| navigator.push(&route));
+                }
+                tile
+            })
+            .collect();
+
+        let content: Html = if tiles.is_empty() {
+            Container::new()
+                .padding(2)
+                .with_child(tr!("No HA resources configured."))
+                .into()
+        } else {
+            List::from_tiles(tiles)
+                .grid_template_columns("auto 1fr auto")
+                .into()
+        };
+
+        standard_card(tr!("Resources"), (), ())
+            .with_child(content)
+            .into()
+    }
+}
+
+impl Component for PvePageHa {
+    type Message = Msg;
+    type Properties = PageHa;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link().send_message(Msg::Load);
+        Self {
+            data: None,
+            load_guard: None,
+            reload_timeout: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Load => {
+                let link = ctx.link().clone();
+                self.load_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = async {
+                        let status = http_get("/cluster/ha/status/current", None).await?;
+                        let resources = http_get("/cluster/ha/resources", None).await?;
+                        Ok(HaData { status, resources })
+                    }
+                    .await;
+                    link.send_message(Msg::LoadResult(result));
+                }));
+            }
+            Msg::LoadResult(result) => {
+                self.load_guard = None;
+                self.data = Some(result.map_err(|err| err.to_string()));
+                let link = ctx.link().clone();
+                self.reload_timeout = Some(Timeout::new(5000, move || {
+                    link.send_message(Msg::Load);
+                }));
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let content = render_loaded_data(&self.data, |data| {
+            Column::new()
+                .gap(2)
+                .with_child(self.view_status(data))
+                .with_child(self.view_resources(ctx, data))
+                .into()
+        });
+
+        Column::new()
+            .class("pwt-fit")
+            .with_child(
+                TopNavBar::new()
+                    .title(tr!("High Availability"))
+                    .back("/configuration"),
+            )
+            .with_child(
+                Column::new()
+                    .class("pwt-flex-fill")
+                    .class("pwt-overflow-auto")
+                    .padding(2)
+                    .with_child(content),
+            )
+            .into()
+    }
+}
+
+impl From<PageHa> for VNode {
+    fn from(props: PageHa) -> Self {
+        let comp = VComp::new::<PvePageHa>(Rc::new(props), None);
+        VNode::from(comp)
+    }
+}
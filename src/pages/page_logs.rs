@@ -1,23 +1,24 @@
 use std::rc::Rc;
 
+use anyhow::Error;
+use gloo_timers::callback::Timeout;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
 use yew::virtual_dom::{VComp, VNode};
+use yew_router::scope_ext::RouterScopeExt;
 
+use pwt::AsyncAbortGuard;
 use pwt::prelude::*;
-use pwt::widget::form::{Form, FormContext, RadioButton};
-use pwt::widget::Column;
-
-use crate::TopNavBar;
+use pwt::props::StorageLocation;
+use pwt::state::PersistentState;
+use pwt::widget::{Column, Container, Fa, List, ListTile, TabBar, TabBarItem};
 
-fn get_form_context() -> FormContext {
-    static mut FORM_CTX: Option<FormContext> = None;
+use proxmox_yew_comp::http_get;
+use proxmox_yew_comp::layout::render_loaded_data;
+use proxmox_yew_comp::utils::render_epoch_short;
 
-    unsafe {
-        if FORM_CTX.is_none() {
-            FORM_CTX = Some(FormContext::new());
-        }
-        FORM_CTX.as_ref().unwrap().clone()
-    }
-}
+use crate::widgets::{TasksPanel, TopNavBar};
 
 #[derive(Clone, PartialEq, Properties)]
 pub struct PageLogs {}
@@ -27,21 +28,112 @@ impl PageLogs {
         Self {}
     }
 }
+
+#[derive(Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum ViewState {
+    #[default]
+    TaskLog,
+    ClusterLog,
+}
+
+// Cluster log entry (`GET /cluster/log`)
+#[derive(Clone, PartialEq, Deserialize)]
+struct ClusterLogEntry {
+    uid: u64,
+    time: i64,
+    pri: u8,
+    #[serde(default)]
+    tag: String,
+    #[serde(default)]
+    node: String,
+    #[serde(default)]
+    user: String,
+    #[serde(default)]
+    msg: String,
+}
+
 pub struct PvePageLogs {
-    form_ctx: FormContext,
+    view_state: PersistentState<ViewState>,
+    cluster_log: Option<Result<Rc<Vec<ClusterLogEntry>>, String>>,
+    reload_timeout: Option<Timeout>,
+    load_guard: Option<AsyncAbortGuard>,
 }
 
 pub enum Msg {
-    Reload,
+    SetViewState(ViewState),
+    LoadClusterLog,
+    ClusterLogResult(Result<Vec<ClusterLogEntry>, Error>),
+}
+
+// syslog priorities: 0-3 are errors, 4 is a warning, 7 is debug
+fn severity_icon(pri: u8) -> Fa {
+    match pri {
+        0..=3 => Fa::new("times-circle").class(pwt::css::FontColor::Error),
+        4 => Fa::new("exclamation-triangle").class("pwt-color-warning"),
+        7 => Fa::new("bug").class(pwt::css::Opacity::Half),
+        _ => Fa::new("info-circle").class(pwt::css::FontColor::Primary),
+    }
 }
 
 impl PvePageLogs {
-    fn view_task_log(&self, _ctx: &Context<Self>) -> Html {
-        html!("TASK LOG")
+    fn view_task_log(&self, ctx: &Context<Self>) -> Html {
+        let navigator = ctx.link().navigator().unwrap();
+        // the cluster task list does not support paging
+        TasksPanel::new("/cluster/tasks")
+            .paging(false)
+            .auto_reload(true)
+            .on_show_task(move |(upid, endtime): (String, Option<i64>)| {
+                navigator.push(&crate::Route::LogsTaskStatus {
+                    upid,
+                    endtime: endtime.unwrap_or(0),
+                });
+            })
+            .into()
     }
 
     fn view_cluster_log(&self, _ctx: &Context<Self>) -> Html {
-        html!("Cluster LOG")
+        render_loaded_data(&self.cluster_log, |data| {
+            if data.is_empty() {
+                return Container::new()
+                    .padding(2)
+                    .with_child(tr!("List is empty."))
+                    .into();
+            }
+            let data = Rc::clone(data);
+            List::new(data.len() as u64, move |pos| {
+                let item = &data[pos as usize];
+                let mut message = Container::new()
+                    .class("pwt-font-size-title-medium")
+                    .with_child(item.msg.clone());
+                if item.pri <= 3 {
+                    message.add_class(pwt::css::FontColor::Error);
+                } else if item.pri == 4 {
+                    message.add_class("pwt-color-warning");
+                }
+
+                ListTile::new()
+                    .key(item.uid.to_string())
+                    .with_child(severity_icon(item.pri).margin_end(1).large())
+                    .with_child(
+                        Column::new().gap(1).with_child(message).with_child(
+                            Container::new()
+                                .class("pwt-font-size-title-small")
+                                .with_child(format!(
+                                    "{} - {} - {} ({})",
+                                    render_epoch_short(item.time),
+                                    item.node,
+                                    item.tag,
+                                    item.user
+                                )),
+                        ),
+                    )
+            })
+            .min_row_height(50)
+            .separator(true)
+            .class(pwt::css::FlexFit)
+            .grid_template_columns("auto 1fr")
+            .into()
+        })
     }
 }
 
@@ -50,50 +142,85 @@ impl Component for PvePageLogs {
     type Properties = PageLogs;
 
     fn create(ctx: &Context<Self>) -> Self {
-        let form_ctx = get_form_context().on_change(ctx.link().callback(|_| Msg::Reload));
+        let view_state = PersistentState::new(StorageLocation::session("logs-tab-bar-state"));
+
+        if *view_state == ViewState::ClusterLog {
+            ctx.link().send_message(Msg::LoadClusterLog);
+        }
 
-        Self { form_ctx }
+        Self {
+            view_state,
+            cluster_log: None,
+            reload_timeout: None,
+            load_guard: None,
+        }
     }
 
-    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
-            Msg::Reload => true,
+            Msg::SetViewState(view_state) => {
+                self.view_state.update(view_state);
+                if view_state == ViewState::ClusterLog {
+                    ctx.link().send_message(Msg::LoadClusterLog);
+                } else {
+                    // stop polling
+                    self.reload_timeout = None;
+                    self.load_guard = None;
+                }
+            }
+            Msg::LoadClusterLog => {
+                let link = ctx.link().clone();
+                self.load_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_get("/cluster/log", Some(json!({ "max": 200 }))).await;
+                    link.send_message(Msg::ClusterLogResult(result));
+                }));
+            }
+            Msg::ClusterLogResult(result) => {
+                self.cluster_log = Some(result.map(Rc::new).map_err(|err| err.to_string()));
+                if *self.view_state == ViewState::ClusterLog {
+                    let link = ctx.link().clone();
+                    self.reload_timeout = Some(Timeout::new(3000, move || {
+                        link.send_message(Msg::LoadClusterLog);
+                    }));
+                }
+            }
         }
+        true
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        let task_log = self
-            .form_ctx
-            .read()
-            .get_field_value("what")
-            .map(|value| value.as_str().unwrap_or("task") == "task")
-            .unwrap_or(true);
-
-        let switch = Form::new()
-            .class("pwt-d-flex pwt-justify-content-space-evenly pwt-p-2 pwt-navbar")
-            .form_context(get_form_context())
-            .with_child(
-                Column::new()
-                    .class("pwt-align-items-center")
-                    .with_child("Task Log")
-                    .with_child(RadioButton::new("task").default(true).name("what")),
+        let (active_tab, content) = match *self.view_state {
+            ViewState::TaskLog => ("task", self.view_task_log(ctx)),
+            ViewState::ClusterLog => ("cluster", self.view_cluster_log(ctx)),
+        };
+
+        let tab_bar = TabBar::new()
+            .class(pwt::css::JustifyContent::Center)
+            .active(active_tab)
+            .with_item(
+                TabBarItem::new()
+                    .label(tr!("Task Log"))
+                    .key("task")
+                    .on_activate(
+                        ctx.link()
+                            .callback(|_| Msg::SetViewState(ViewState::TaskLog)),
+                    ),
             )
-            .with_child(
-                Column::new()
-                    .class("pwt-align-items-center")
-                    .with_child("Cluster Log")
-                    .with_child(RadioButton::new("cluster").name("what")),
+            .with_item(
+                TabBarItem::new()
+                    .label(tr!("Cluster Log"))
+                    .key("cluster")
+                    .on_activate(
+                        ctx.link()
+                            .callback(|_| Msg::SetViewState(ViewState::ClusterLog)),
+                    ),
             );
 
         Column::new()
             .class("pwt-fit")
-            .with_child(TopNavBar::new().title("Logs"))
-            .with_child(switch)
-            .with_child(if task_log {
-                self.view_task_log(ctx)
-            } else {
-                self.view_cluster_log(ctx)
-            })
+            .with_child(TopNavBar::new().title(tr!("Logs")).back("/configuration"))
+            .with_child(tab_bar)
+            .with_child(content)
             .into()
     }
 }
@@ -4,6 +4,7 @@ use anyhow::Error;
 use gloo_timers::callback::Timeout;
 use proxmox_human_byte::HumanByte;
 
+use serde_json::Value;
 use yew::prelude::*;
 use yew::virtual_dom::{VComp, VNode};
 use yew_router::scope_ext::RouterScopeExt;
@@ -25,7 +26,10 @@ use proxmox_yew_comp::{
 
 use pve_api_types::{IsRunning, LxcStatus};
 
-use crate::widgets::TasksListButton;
+use crate::widgets::{
+    GuestBackupJobsCard, GuestCloneDialog, GuestHaCard, GuestMigrateDialog, GuestType,
+    RrdGraphPanel, TasksListButton, guest_base_url,
+};
 
 use proxmox_yew_comp::configuration::pve::{LxcDnsPanel, LxcNetworkPanel, LxcResourcesPanel};
 
@@ -51,6 +55,12 @@ pub struct PveLxcDashboardPanel {
     cmd_guard: Option<AsyncAbortGuard>,
     running_upid: Option<String>,
     confirm_lxc_command: Option<(String, String)>,
+    show_migrate_dialog: bool,
+    show_clone_dialog: bool,
+    // running migration task (upid, target node)
+    migrate_task: Option<(String, String)>,
+    // requested HA state, `None` if not HA managed
+    ha_state: Option<String>,
 }
 
 #[derive(Copy, Clone, PartialEq)]
@@ -58,6 +68,7 @@ pub enum ConfirmableCommands {
     Reboot,
     Stop,
     Shutdown,
+    Template,
 }
 
 pub enum Msg {
@@ -67,6 +78,12 @@ pub enum Msg {
     StartCommand(String),
     LxcCommand(String),
     Confirm(ConfirmableCommands),
+    ShowMigrateDialog(bool),
+    ShowCloneDialog(bool),
+    TemplateResult(Result<Value, Error>),
+    MigrateStarted((String, String)),
+    TaskFinished((String, Option<String>)),
+    HaState(Option<String>),
     CloseDialog,
 }
 
@@ -94,6 +111,9 @@ fn format_guest_task_confirmation(
         ConfirmableCommands::Reboot => "vzreboot",
         ConfirmableCommands::Shutdown => "vzshutdown",
         ConfirmableCommands::Stop => "vzstop",
+        ConfirmableCommands::Template => {
+            return tr!("Convert CT {0} to a template?", vmid);
+        }
     };
     let lxc_name_or_id = match guest_name {
         Some(name) => name.to_string(),
@@ -105,8 +125,20 @@ fn format_guest_task_confirmation(
 impl PveLxcDashboardPanel {
     fn lxc_command(&mut self, ctx: &Context<Self>, cmd: &str) {
         let props = ctx.props();
-        let url = get_status_url(&props.node, props.vmid, cmd);
         let link = ctx.link().clone();
+        if cmd == "template" {
+            // not a status command, and the result is not always a task
+            let url = format!(
+                "{}/template",
+                guest_base_url(&props.node, GuestType::Lxc, props.vmid)
+            );
+            self.cmd_guard = Some(AsyncAbortGuard::spawn(async move {
+                let result = http_post(&url, None).await;
+                link.send_message(Msg::TemplateResult(result));
+            }));
+            return;
+        }
+        let url = get_status_url(&props.node, props.vmid, cmd);
         self.cmd_guard = Some(AsyncAbortGuard::spawn(async move {
             let result = http_post(&url, None).await;
             link.send_message(Msg::CommandResult(result));
@@ -125,6 +157,7 @@ impl PveLxcDashboardPanel {
             ConfirmableCommands::Reboot => "reboot",
             ConfirmableCommands::Shutdown => "shutdown",
             ConfirmableCommands::Stop => "stop",
+            ConfirmableCommands::Template => "template",
         };
 
         self.confirm_lxc_command = Some((command_str.into(), confirm_msg));
@@ -144,6 +177,15 @@ impl PveLxcDashboardPanel {
             data.status.to_string(),
         ));
 
+        if let Some(ha_state) = &self.ha_state {
+            tiles.push(icon_list_tile(
+                Fa::new("heartbeat").class("pwt-color-primary"),
+                tr!("HA managed"),
+                (),
+                ha_state.clone(),
+            ));
+        }
+
         if let Some(Ok(data)) = &self.data {
             if data.status == IsRunning::Running {
                 if let (Some(cpu), Some(maxcpu)) = (data.cpu, data.cpus) {
@@ -196,6 +238,7 @@ impl PveLxcDashboardPanel {
         let node_name = props.node.clone();
 
         let running = data.status == IsRunning::Running;
+        let template = data.template.unwrap_or(false);
 
         let menu = Menu::new()
             .with_item(
@@ -209,6 +252,18 @@ impl PveLxcDashboardPanel {
                     ctx.link()
                         .callback(|_| Msg::Confirm(ConfirmableCommands::Stop)),
                 ),
+            )
+            .with_item(
+                MenuItem::new(tr!("Clone"))
+                    .on_select(ctx.link().callback(|_| Msg::ShowCloneDialog(true))),
+            )
+            .with_item(
+                MenuItem::new(tr!("Convert to template"))
+                    .disabled(running || template)
+                    .on_select(
+                        ctx.link()
+                            .callback(|_| Msg::Confirm(ConfirmableCommands::Template)),
+                    ),
             );
 
         let shutdown = SplitButton::new(tr!("Shutdown"))
@@ -223,11 +278,12 @@ impl PveLxcDashboardPanel {
             .padding_y(1)
             .gap(2)
             .class(pwt::css::JustifyContent::SpaceBetween)
-            .with_child(
+            // templates cannot be started
+            .with_optional_child((!template).then(|| {
                 Button::new(tr!("Start"))
                     .disabled(running)
-                    .on_activate(ctx.link().callback(|_| Msg::LxcCommand("start".into()))),
-            )
+                    .on_activate(ctx.link().callback(|_| Msg::LxcCommand("start".into())))
+            }))
             .with_child(shutdown)
             .with_child(
                 Button::new(tr!("Console"))
@@ -239,6 +295,12 @@ impl PveLxcDashboardPanel {
                             true,
                         );
                     }),
+            )
+            .with_child(
+                Button::new(tr!("Migrate"))
+                    .icon_class("fa fa-paper-plane")
+                    .disabled(self.migrate_task.is_some())
+                    .on_activate(ctx.link().callback(|_| Msg::ShowMigrateDialog(true))),
             );
 
         MiniScroll::new(row)
@@ -250,6 +312,7 @@ impl PveLxcDashboardPanel {
     fn task_button(&self, ctx: &Context<Self>) -> Html {
         TasksListButton::new()
             .running_upid(self.running_upid.clone())
+            .on_task_finished(ctx.link().callback(Msg::TaskFinished))
             .on_show_task_list({
                 let navigator = ctx.link().navigator().clone().unwrap();
                 let props = ctx.props();
@@ -279,6 +342,10 @@ impl Component for PveLxcDashboardPanel {
             cmd_guard: None,
             running_upid: None,
             confirm_lxc_command: None,
+            show_migrate_dialog: false,
+            show_clone_dialog: false,
+            migrate_task: None,
+            ha_state: None,
         }
     }
 
@@ -313,6 +380,38 @@ impl Component for PveLxcDashboardPanel {
             Msg::Confirm(command) => self.confirmed_lxc_command(ctx, command),
             Msg::LxcCommand(command) => self.lxc_command(ctx, &command),
             Msg::CloseDialog => self.confirm_lxc_command = None,
+            Msg::ShowMigrateDialog(show) => self.show_migrate_dialog = show,
+            Msg::ShowCloneDialog(show) => self.show_clone_dialog = show,
+            Msg::TemplateResult(result) => match result {
+                Ok(Value::String(upid)) => self.running_upid = Some(upid),
+                Ok(_) => ctx.link().send_message(Msg::Load),
+                Err(err) => crate::show_failed_command_error(ctx.link(), err),
+            },
+            Msg::MigrateStarted((target, upid)) => {
+                self.running_upid = Some(upid.clone());
+                self.migrate_task = Some((upid, target));
+            }
+            Msg::HaState(state) => self.ha_state = state,
+            Msg::TaskFinished((upid, exit_status)) => {
+                let is_migration =
+                    matches!(&self.migrate_task, Some((migrate_upid, _)) if *migrate_upid == upid);
+                if !is_migration {
+                    return true;
+                }
+                let success = matches!(
+                    exit_status.as_deref(),
+                    Some(status) if status == "OK" || status.starts_with("WARNINGS")
+                );
+                if let (Some((_, target)), true) = (self.migrate_task.take(), success) {
+                    // the container now lives on the target node, so do not go back to the old one
+                    if let Some(navigator) = ctx.link().navigator() {
+                        navigator.replace(&crate::Route::Lxc {
+                            vmid: props.vmid,
+                            nodename: target,
+                        });
+                    }
+                }
+            }
         }
         true
     }
@@ -361,7 +460,33 @@ impl Component for PveLxcDashboardPanel {
                             .mobile(true),
                     ),
                 )
+                .with_child(
+                    GuestHaCard::new(GuestType::Lxc, props.vmid)
+                        .on_load(ctx.link().callback(Msg::HaState)),
+                )
+                .with_child(GuestBackupJobsCard::new(
+                    props.node.clone(),
+                    GuestType::Lxc,
+                    props.vmid,
+                ))
+                .with_child(RrdGraphPanel::guest(
+                    props.node.clone(),
+                    GuestType::Lxc,
+                    props.vmid,
+                ))
                 .with_optional_child(confirm_dialog)
+                .with_optional_child(self.show_migrate_dialog.then(|| {
+                    GuestMigrateDialog::new(props.node.clone(), GuestType::Lxc, props.vmid)
+                        .running(data.status == IsRunning::Running)
+                        .on_close(ctx.link().callback(|_| Msg::ShowMigrateDialog(false)))
+                        .on_migrate(ctx.link().callback(Msg::MigrateStarted))
+                }))
+                .with_optional_child(self.show_clone_dialog.then(|| {
+                    GuestCloneDialog::new(props.node.clone(), GuestType::Lxc, props.vmid)
+                        .template(data.template.unwrap_or(false))
+                        .on_close(ctx.link().callback(|_| Msg::ShowCloneDialog(false)))
+                        .on_clone(ctx.link().callback(Msg::StartCommand))
+                }))
                 .into()
         })
     }
@@ -10,11 +10,14 @@ use pwt::prelude::*;
 use pwt::state::PersistentState;
 use pwt::widget::{Column, TabBar, TabBarItem};
 
-use crate::widgets::{GuestBackupPanel, TopNavBar};
+use crate::widgets::{GuestBackupPanel, GuestSnapshotPanel, GuestType, TopNavBar};
 
 mod dashboard_panel;
 pub use dashboard_panel::LxcDashboardPanel;
 
+mod resources_panel;
+pub use resources_panel::LxcResourcesPendingPanel;
+
 use proxmox_yew_comp::configuration::pve::LxcOptionsPanel;
 
 #[derive(Clone, PartialEq, Properties)]
@@ -36,7 +39,9 @@ impl PageLxcStatus {
 pub enum ViewState {
     #[default]
     Dashboard,
+    Resources,
     Options,
+    Snapshots,
     Backup,
 }
 
@@ -80,6 +85,14 @@ impl Component for PvePageLxcStatus {
                 "dashboard",
                 LxcDashboardPanel::new(props.node.clone(), props.vmid).into(),
             ),
+            ViewState::Resources => (
+                "resources",
+                LxcResourcesPendingPanel::new(props.node.clone(), props.vmid).into(),
+            ),
+            ViewState::Snapshots => (
+                "snapshots",
+                GuestSnapshotPanel::new(props.node.clone(), GuestType::Lxc, props.vmid).into(),
+            ),
             ViewState::Backup => (
                 "backup",
                 GuestBackupPanel::new(props.node.clone(), props.vmid).into(),
@@ -105,6 +118,15 @@ impl Component for PvePageLxcStatus {
                             .callback(|_| Msg::SetViewState(ViewState::Dashboard)),
                     ),
             )
+            .with_item(
+                TabBarItem::new()
+                    .label(tr!("Resources"))
+                    .key("resources")
+                    .on_activate(
+                        ctx.link()
+                            .callback(|_| Msg::SetViewState(ViewState::Resources)),
+                    ),
+            )
             .with_item(
                 TabBarItem::new()
                     .label("Options")
@@ -114,6 +136,15 @@ impl Component for PvePageLxcStatus {
                             .callback(|_| Msg::SetViewState(ViewState::Options)),
                     ),
             )
+            .with_item(
+                TabBarItem::new()
+                    .label("Snapshots")
+                    .key("snapshots")
+                    .on_activate(
+                        ctx.link()
+                            .callback(|_| Msg::SetViewState(ViewState::Snapshots)),
+                    ),
+            )
             .with_item(
                 TabBarItem::new().label("Backup").key("backup").on_activate(
                     ctx.link()
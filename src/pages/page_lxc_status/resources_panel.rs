@@ -0,0 +1,695 @@
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use anyhow::{Error, format_err};
+use serde_json::{Value, json};
+
+use yew::virtual_dom::{VComp, VNode};
+use yew_router::scope_ext::RouterScopeExt;
+
+use pwt::AsyncAbortGuard;
+use pwt::prelude::*;
+use pwt::props::SubmitCallback;
+use pwt::touch::{Fab, FabSize, SideDialog};
+use pwt::widget::form::{Checkbox, Combobox, Field, Number};
+use pwt::widget::{Column, Container, Fa, List};
+
+use proxmox_human_byte::HumanByte;
+use proxmox_yew_comp::layout::list_tile::icon_list_tile;
+use proxmox_yew_comp::layout::mobile_form::label_field;
+use proxmox_yew_comp::{http_get, http_put, percent_encoding::percent_encode_component};
+
+use pve_api_types::StorageInfo;
+
+use crate::widgets::{
+    EditDialog, EditableProperty, GuestDiskActionDialog, GuestType, PendingPropertyList,
+    PropertyEditorState, TasksListButton, field_bool, field_string, format_property_string,
+    guest_base_url, indexed_keys, load_bridge_list, parse_property_string, set_property_value,
+};
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct LxcResourcesPendingPanel {
+    vmid: u32,
+    node: AttrValue,
+}
+
+impl LxcResourcesPendingPanel {
+    pub fn new(node: impl Into<AttrValue>, vmid: u32) -> Self {
+        Self {
+            node: node.into(),
+            vmid,
+        }
+    }
+}
+
+pub enum Msg {
+    LoadResult(Result<(Vec<String>, Vec<String>), Error>),
+    ConfigKeys(HashSet<String>),
+    ShowAddMenu(bool),
+    AddNetwork,
+    AddMountPoint,
+    CloseAddDialog,
+    ShowDiskAction(Option<String>),
+    StartTask(String),
+}
+
+pub struct PveLxcResourcesPendingPanel {
+    properties: Rc<Vec<EditableProperty>>,
+    // keys of the loaded configuration, only those get a property
+    keys: HashSet<String>,
+    bridges: Rc<Vec<String>>,
+    storages: Rc<Vec<String>>,
+    load_guard: Option<AsyncAbortGuard>,
+    show_add_menu: bool,
+    add_dialog: Option<Html>,
+    disk_action: Option<String>,
+    running_upid: Option<String>,
+}
+
+const MAX_NETWORK_DEVICES: usize = 32;
+const MAX_MOUNT_POINTS: usize = 256;
+
+fn format_mib(mib: u64) -> String {
+    HumanByte::new_binary((mib * 1024 * 1024) as f64).to_string()
+}
+
+// Returns the first unused key `{prefix}{N}`, `keys` must include pending keys
+fn free_key(keys: &HashSet<String>, prefix: &str, max: usize) -> Option<(usize, String)> {
+    (0..max)
+        .map(|i| (i, format!("{prefix}{i}")))
+        .find(|(_, key)| !keys.contains(key))
+}
+
+fn remove_helper_fields(data: &mut Value) {
+    if let Value::Object(map) = data {
+        map.retain(|key, _| !key.starts_with('_'));
+    }
+}
+
+fn memory_property(name: &str, title: String, min: u64) -> EditableProperty {
+    let name = name.to_string();
+    EditableProperty::new(AttrValue::from(name.clone()), title.clone())
+        .required(true)
+        .placeholder(format_mib(512))
+        .renderer(|_name, value, _record| match value.as_u64() {
+            Some(mib) => format_mib(mib).into(),
+            None => value.to_string().into(),
+        })
+        .render_input_panel(move |_| {
+            label_field(
+                format!("{title} (MiB)"),
+                Number::<u64>::new()
+                    .name(name.clone())
+                    .required(true)
+                    .min(min)
+                    .default(512),
+                true,
+            )
+            .into()
+        })
+}
+
+fn cores_property() -> EditableProperty {
+    EditableProperty::new("cores", tr!("Cores"))
+        .required(true)
+        .placeholder(tr!("unlimited"))
+        .submit_hook(Callback::from(|state: PropertyEditorState| {
+            let mut data = state.get_submit_data();
+            if field_string(&state.form_ctx, "cores").is_none() {
+                data["delete"] = "cores".into();
+            }
+            Ok(data)
+        }))
+        .render_input_panel(|_| {
+            label_field(
+                tr!("Cores"),
+                Number::<u64>::new()
+                    .name("cores")
+                    .min(1)
+                    .max(8192)
+                    .placeholder(tr!("unlimited")),
+                true,
+            )
+            .into()
+        })
+}
+
+fn ip_fields(
+    state: &PropertyEditorState,
+    family: &str,
+    label: &str,
+    modes: Vec<(&str, String)>,
+) -> Html {
+    let is_static =
+        field_string(&state.form_ctx, &format!("_{family}_mode")).as_deref() == Some("static");
+
+    let mode_selector = Combobox::from_key_value_pairs(modes)
+        .name(format!("_{family}_mode"))
+        .required(true)
+        .force_selection(true)
+        .default("none");
+
+    Column::new()
+        .gap(2)
+        .with_child(label_field(label.to_string(), mode_selector, true))
+        .with_child(label_field(
+            format!("{label} ({})", tr!("CIDR")),
+            Field::new()
+                .name(format!("_{family}_cidr"))
+                .disabled(!is_static)
+                .required(is_static),
+            true,
+        ))
+        .with_child(label_field(
+            format!("{label} ({})", tr!("Gateway")),
+            Field::new()
+                .name(format!("_{family}_gw"))
+                .disabled(!is_static),
+            true,
+        ))
+        .into()
+}
+
+// `new_index` is the index of a new network device
+fn network_input_panel(
+    state: &PropertyEditorState,
+    bridges: &[String],
+    new_index: Option<usize>,
+) -> Html {
+    let new = new_index.is_some();
+    let bridge_selector = bridges
+        .iter()
+        .fold(Combobox::new(), |combo, bridge| {
+            combo.with_item(bridge.clone())
+        })
+        .name("_bridge")
+        .required(true)
+        .force_selection(true);
+
+    let default_name = new_index.map(|i| format!("eth{i}"));
+
+    Column::new()
+        .gap(2)
+        .with_child(label_field(
+            tr!("Name"),
+            Field::new()
+                .name("_name")
+                .required(true)
+                .default(default_name.unwrap_or_default()),
+            true,
+        ))
+        .with_child(label_field(tr!("Bridge"), bridge_selector, true))
+        .with_child(
+            Checkbox::new()
+                .name("_firewall")
+                .default(new)
+                .box_label(tr!("Firewall")),
+        )
+        .with_child(label_field(
+            tr!("Rate limit") + " (MB/s)",
+            Number::<f64>::new()
+                .name("_rate")
+                .min(0.0)
+                .placeholder(tr!("unlimited")),
+            true,
+        ))
+        .with_child(ip_fields(
+            state,
+            "ipv4",
+            "IPv4",
+            vec![
+                ("dhcp", String::from("DHCP")),
+                ("static", tr!("Static")),
+                ("manual", tr!("Manual")),
+                ("none", tr!("None")),
+            ],
+        ))
+        .with_child(ip_fields(
+            state,
+            "ipv6",
+            "IPv6",
+            vec![
+                ("dhcp", String::from("DHCP")),
+                ("slaac", String::from("SLAAC")),
+                ("static", tr!("Static")),
+                ("manual", tr!("Manual")),
+                ("none", tr!("None")),
+            ],
+        ))
+        .into()
+}
+
+fn network_load_hook(name: String) -> Callback<Value, Result<Value, Error>> {
+    Callback::from(move |mut record: Value| {
+        let list = parse_property_string(record[&name].as_str().unwrap_or(""), "name");
+        record["_ipv4_mode"] = "none".into();
+        record["_ipv6_mode"] = "none".into();
+        for (key, value) in list {
+            match key.as_str() {
+                "name" => record["_name"] = value.into(),
+                "bridge" => record["_bridge"] = value.into(),
+                "firewall" => record["_firewall"] = (value == "1").into(),
+                "rate" => record["_rate"] = value.parse::<f64>().ok().into(),
+                "gw" => record["_ipv4_gw"] = value.into(),
+                "gw6" => record["_ipv6_gw"] = value.into(),
+                "ip" | "ip6" => {
+                    let family = if key == "ip" { "ipv4" } else { "ipv6" };
+                    let mode = match value.as_str() {
+                        "dhcp" => "dhcp",
+                        "auto" => "slaac",
+                        "manual" => "manual",
+                        _ => {
+                            record[format!("_{family}_cidr")] = value.clone().into();
+                            "static"
+                        }
+                    };
+                    record[format!("_{family}_mode")] = mode.into();
+                }
+                _ => {}
+            }
+        }
+        Ok(record)
+    })
+}
+
+// Build the `netN` property string, keeping all options not shown in the editor.
+fn network_submit_data(state: &PropertyEditorState, name: &str) -> Result<Value, Error> {
+    let form_ctx = &state.form_ctx;
+    let mut list = parse_property_string(state.record[name].as_str().unwrap_or(""), "name");
+
+    set_property_value(&mut list, "name", field_string(form_ctx, "_name"));
+    set_property_value(&mut list, "bridge", field_string(form_ctx, "_bridge"));
+    set_property_value(
+        &mut list,
+        "firewall",
+        field_bool(form_ctx, "_firewall").then(|| String::from("1")),
+    );
+    set_property_value(&mut list, "rate", field_string(form_ctx, "_rate"));
+
+    for (family, ip_key, gw_key) in [("ipv4", "ip", "gw"), ("ipv6", "ip6", "gw6")] {
+        let (ip, gw) = match field_string(form_ctx, &format!("_{family}_mode")).as_deref() {
+            Some("static") => (
+                field_string(form_ctx, &format!("_{family}_cidr")),
+                field_string(form_ctx, &format!("_{family}_gw")),
+            ),
+            Some("dhcp") => (Some(String::from("dhcp")), None),
+            Some("slaac") => (Some(String::from("auto")), None),
+            Some("manual") => (Some(String::from("manual")), None),
+            _ => (None, None),
+        };
+        set_property_value(&mut list, ip_key, ip);
+        set_property_value(&mut list, gw_key, gw);
+    }
+
+    let mut data = state.get_submit_data();
+    remove_helper_fields(&mut data);
+    // the `netN` schema has no default key, so `name=` must always be written
+    data[name] = format_property_string(&list, None).into();
+    Ok(data)
+}
+
+fn network_property(name: &str, bridges: Rc<Vec<String>>) -> EditableProperty {
+    let name = name.to_string();
+    EditableProperty::new(
+        AttrValue::from(name.clone()),
+        format!("{} ({name})", tr!("Network Device")),
+    )
+    .load_hook(network_load_hook(name.clone()))
+    .submit_hook({
+        let name = name.clone();
+        Callback::from(move |state: PropertyEditorState| network_submit_data(&state, &name))
+    })
+    .render_input_panel(move |state: PropertyEditorState| {
+        network_input_panel(&state, &bridges, None)
+    })
+}
+
+fn mount_point_input_panel(storages: &[String], new: bool) -> Html {
+    let mut panel = Column::new().gap(2);
+
+    // volume and size of existing mount points are managed with the disk actions
+    if new {
+        let storage_selector = storages
+            .iter()
+            .fold(Combobox::new(), |combo, storage| {
+                combo.with_item(storage.clone())
+            })
+            .name("_storage")
+            .required(true)
+            .force_selection(true)
+            .default(storages.first().cloned().unwrap_or_default());
+
+        panel.add_child(label_field(tr!("Storage"), storage_selector, true));
+        panel.add_child(label_field(
+            tr!("Disk size") + " (GiB)",
+            Number::<u64>::new()
+                .name("_size")
+                .required(true)
+                .min(1)
+                .default(8),
+            true,
+        ));
+    }
+
+    panel
+        .with_child(label_field(
+            tr!("Path"),
+            Field::new()
+                .name("_mp")
+                .required(true)
+                .placeholder("/some/path"),
+            true,
+        ))
+        .with_child(Checkbox::new().name("_backup").box_label(tr!("Backup")))
+        .with_child(Checkbox::new().name("_ro").box_label(tr!("Read-only")))
+        .into()
+}
+
+// `new` allocates a new volume for the mount point
+fn mount_point_submit_data(
+    state: &PropertyEditorState,
+    name: &str,
+    new: bool,
+) -> Result<Value, Error> {
+    let form_ctx = &state.form_ctx;
+
+    let mut list = if new {
+        let storage = field_string(form_ctx, "_storage").unwrap_or_default();
+        let size = field_string(form_ctx, "_size").unwrap_or_default();
+        vec![(String::from("volume"), format!("{storage}:{size}"))]
+    } else {
+        parse_property_string(state.record[name].as_str().unwrap_or(""), "volume")
+    };
+
+    set_property_value(&mut list, "mp", field_string(form_ctx, "_mp"));
+    set_property_value(
+        &mut list,
+        "backup",
+        field_bool(form_ctx, "_backup").then(|| String::from("1")),
+    );
+    set_property_value(
+        &mut list,
+        "ro",
+        field_bool(form_ctx, "_ro").then(|| String::from("1")),
+    );
+
+    let mut data = state.get_submit_data();
+    remove_helper_fields(&mut data);
+    data[name] = format_property_string(&list, Some("volume")).into();
+    Ok(data)
+}
+
+fn mount_point_property(name: &str, on_disk_action: Callback<String>) -> EditableProperty {
+    let name = name.to_string();
+    EditableProperty::new(
+        AttrValue::from(name.clone()),
+        format!("{} ({name})", tr!("Mount Point")),
+    )
+    .action_icon("fa fa-ellipsis-v")
+    .on_action({
+        let name = name.clone();
+        move |_| on_disk_action.emit(name.clone())
+    })
+    .load_hook({
+        let name = name.clone();
+        Callback::from(move |mut record: Value| {
+            let list = parse_property_string(record[&name].as_str().unwrap_or(""), "volume");
+            for (key, value) in list {
+                match key.as_str() {
+                    "mp" => record["_mp"] = value.into(),
+                    "backup" => record["_backup"] = (value == "1").into(),
+                    "ro" => record["_ro"] = (value == "1").into(),
+                    _ => {}
+                }
+            }
+            Ok(record)
+        })
+    })
+    .submit_hook({
+        let name = name.clone();
+        Callback::from(move |state: PropertyEditorState| {
+            mount_point_submit_data(&state, &name, false)
+        })
+    })
+    .render_input_panel(|_| mount_point_input_panel(&[], false))
+}
+
+fn resource_properties(
+    keys: &HashSet<String>,
+    bridges: Rc<Vec<String>>,
+    on_disk_action: Callback<String>,
+) -> Vec<EditableProperty> {
+    let mut properties = vec![
+        memory_property("memory", tr!("Memory"), 16),
+        memory_property("swap", tr!("Swap"), 0),
+        cores_property(),
+        EditableProperty::new("rootfs", tr!("Root Disk"))
+            .required(true)
+            .action_icon("fa fa-ellipsis-v")
+            .on_action({
+                let on_disk_action = on_disk_action.clone();
+                move |_| on_disk_action.emit(String::from("rootfs"))
+            }),
+    ];
+
+    for name in indexed_keys(keys, "mp", MAX_MOUNT_POINTS) {
+        properties.push(mount_point_property(&name, on_disk_action.clone()));
+    }
+
+    for name in indexed_keys(keys, "net", MAX_NETWORK_DEVICES) {
+        properties.push(network_property(&name, bridges.clone()));
+    }
+
+    properties
+}
+
+impl PveLxcResourcesPendingPanel {
+    fn update_properties(&mut self, ctx: &Context<Self>) {
+        self.properties = Rc::new(resource_properties(
+            &self.keys,
+            self.bridges.clone(),
+            ctx.link().callback(|disk| Msg::ShowDiskAction(Some(disk))),
+        ));
+    }
+
+    fn config_url(&self, ctx: &Context<Self>) -> String {
+        let props = ctx.props();
+        format!(
+            "{}/config",
+            guest_base_url(&props.node, GuestType::Lxc, props.vmid)
+        )
+    }
+
+    fn submit_callback(&self, ctx: &Context<Self>) -> SubmitCallback<Value> {
+        let url = self.config_url(ctx);
+        SubmitCallback::new(move |data: Value| {
+            let url = url.clone();
+            async move {
+                let _: Value = http_put(&url, Some(data)).await?;
+                Ok(())
+            }
+        })
+    }
+
+    fn add_menu(&self, ctx: &Context<Self>) -> Html {
+        let tiles = vec![
+            icon_list_tile(Fa::new("exchange"), tr!("Network Device"), (), ())
+                .interactive(true)
+                .onclick(ctx.link().callback(|_| Msg::AddNetwork)),
+            icon_list_tile(Fa::new("hdd-o"), tr!("Mount Point"), (), ())
+                .interactive(true)
+                .onclick(ctx.link().callback(|_| Msg::AddMountPoint)),
+        ];
+
+        List::from_tiles(tiles)
+            .grid_template_columns("auto 1fr")
+            .into()
+    }
+}
+
+impl Component for PveLxcResourcesPendingPanel {
+    type Message = Msg;
+    type Properties = LxcResourcesPendingPanel;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let props = ctx.props();
+
+        // bridges and storages are only needed by the editors
+        let node = props.node.clone();
+        let link = ctx.link().clone();
+        let load_guard = AsyncAbortGuard::spawn(async move {
+            let result = async {
+                let bridges = load_bridge_list(&node).await?;
+                let url = format!("/nodes/{}/storage", percent_encode_component(&node));
+                let storage_list: Vec<StorageInfo> =
+                    http_get(&url, Some(json!({"content": "rootdir"}))).await?;
+                let mut storages: Vec<String> = storage_list
+                    .into_iter()
+                    .filter(|info| info.enabled.unwrap_or(true))
+                    .map(|info| info.storage)
+                    .collect();
+                storages.sort();
+                Ok((bridges, storages))
+            }
+            .await;
+            link.send_message(Msg::LoadResult(result));
+        });
+
+        let mut me = Self {
+            properties: Rc::new(Vec::new()),
+            keys: HashSet::new(),
+            bridges: Rc::new(Vec::new()),
+            storages: Rc::new(Vec::new()),
+            load_guard: Some(load_guard),
+            show_add_menu: false,
+            add_dialog: None,
+            disk_action: None,
+            running_upid: None,
+        };
+        me.update_properties(ctx);
+        me
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::LoadResult(result) => {
+                self.load_guard = None;
+                match result {
+                    Ok((bridges, storages)) => {
+                        self.bridges = Rc::new(bridges);
+                        self.storages = Rc::new(storages);
+                        self.update_properties(ctx);
+                    }
+                    Err(err) => crate::show_failed_command_error(ctx.link(), err),
+                }
+            }
+            Msg::ConfigKeys(keys) => {
+                if keys == self.keys {
+                    return false;
+                }
+                self.keys = keys;
+                self.update_properties(ctx);
+            }
+            Msg::ShowAddMenu(show) => self.show_add_menu = show,
+            Msg::AddNetwork => {
+                let bridges = self.bridges.clone();
+                self.show_add_menu = false;
+                let Some((index, name)) = free_key(&self.keys, "net", MAX_NETWORK_DEVICES) else {
+                    crate::show_failed_command_error(
+                        ctx.link(),
+                        format_err!("no free network device slot"),
+                    );
+                    return true;
+                };
+                self.add_dialog = Some(
+                    EditDialog::new(tr!("Add") + ": " + &tr!("Network Device"))
+                        .loader(self.config_url(ctx))
+                        .edit(false)
+                        .renderer(move |state: PropertyEditorState| {
+                            network_input_panel(&state, &bridges, Some(index))
+                        })
+                        .submit_hook(Callback::from(move |state: PropertyEditorState| {
+                            network_submit_data(&state, &name)
+                        }))
+                        .on_submit(self.submit_callback(ctx))
+                        .on_done(ctx.link().callback(|_| Msg::CloseAddDialog))
+                        .into(),
+                );
+            }
+            Msg::AddMountPoint => {
+                let storages = self.storages.clone();
+                self.show_add_menu = false;
+                let Some((_, name)) = free_key(&self.keys, "mp", MAX_MOUNT_POINTS) else {
+                    crate::show_failed_command_error(
+                        ctx.link(),
+                        format_err!("no free mount point slot"),
+                    );
+                    return true;
+                };
+                self.add_dialog = Some(
+                    EditDialog::new(tr!("Add") + ": " + &tr!("Mount Point"))
+                        .loader(self.config_url(ctx))
+                        .edit(false)
+                        .renderer(move |_| mount_point_input_panel(&storages, true))
+                        .submit_hook(Callback::from(move |state: PropertyEditorState| {
+                            mount_point_submit_data(&state, &name, true)
+                        }))
+                        .on_submit(self.submit_callback(ctx))
+                        .on_done(ctx.link().callback(|_| Msg::CloseAddDialog))
+                        .into(),
+                );
+            }
+            Msg::CloseAddDialog => self.add_dialog = None,
+            Msg::ShowDiskAction(disk) => self.disk_action = disk,
+            Msg::StartTask(upid) => self.running_upid = Some(upid),
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let base_url = guest_base_url(&props.node, GuestType::Lxc, props.vmid);
+
+        let list = PendingPropertyList::new(self.properties.clone())
+            .pending_loader(format!("{base_url}/pending"))
+            .editor_loader(self.config_url(ctx))
+            .on_load(ctx.link().callback(Msg::ConfigKeys))
+            .on_submit(self.submit_callback(ctx))
+            .pending_hint(tr!(
+                "Highlighted values are pending and take effect after the next container restart."
+            ));
+
+        let fab = Fab::new("fa fa-plus")
+            .size(FabSize::Small)
+            .text(tr!("Add"))
+            .class("pwt-position-absolute")
+            .style("right", "var(--pwt-spacer-2)")
+            .style("bottom", "var(--pwt-spacer-2)")
+            .on_activate(ctx.link().callback(|_| Msg::ShowAddMenu(true)));
+
+        let add_menu = self.show_add_menu.then(|| {
+            SideDialog::new()
+                .location(pwt::touch::SideDialogLocation::Bottom)
+                .on_close(ctx.link().callback(|_| Msg::ShowAddMenu(false)))
+                .with_child(self.add_menu(ctx))
+        });
+
+        let task_button = TasksListButton::new()
+            .running_upid(self.running_upid.clone())
+            .on_show_task_list({
+                let navigator = ctx.link().navigator().clone().unwrap();
+                let node = props.node.clone();
+                let vmid = props.vmid;
+                move |_| {
+                    navigator.push(&crate::Route::LxcTasks {
+                        vmid,
+                        nodename: node.to_string(),
+                    });
+                }
+            });
+
+        let disk_dialog = self.disk_action.as_ref().map(|disk| {
+            GuestDiskActionDialog::new(props.node.clone(), GuestType::Lxc, props.vmid, disk.clone())
+                .on_close(ctx.link().callback(|_| Msg::ShowDiskAction(None)))
+                .on_start_task(ctx.link().callback(Msg::StartTask))
+        });
+
+        Column::new()
+            .class(pwt::css::FlexFit)
+            .class("pwt-position-relative")
+            .with_child(Container::new().padding(2).with_child(task_button))
+            .with_child(list)
+            .with_child(fab)
+            .with_optional_child(add_menu)
+            .with_optional_child(self.add_dialog.clone())
+            .with_optional_child(disk_dialog)
+            .into()
+    }
+}
+
+impl From<LxcResourcesPendingPanel> for VNode {
+    fn from(props: LxcResourcesPendingPanel) -> Self {
+        let comp = VComp::new::<PveLxcResourcesPendingPanel>(Rc::new(props), None);
+        VNode::from(comp)
+    }
+}
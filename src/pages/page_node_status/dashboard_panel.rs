@@ -21,7 +21,7 @@ use proxmox_yew_comp::{http_get, http_post, percent_encoding::percent_encode_com
 
 use pve_api_types::NodeStatus;
 
-use crate::widgets::TasksListButton;
+use crate::widgets::{RrdGraphPanel, TasksListButton};
 
 //use super::NodeResourcesPanel;
 
@@ -218,6 +218,7 @@ impl Component for PveNodeDashboardPanel {
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
         render_loaded_data(&self.data, |data| {
             Column::new()
                 .class(pwt::css::FlexFit)
@@ -227,6 +228,7 @@ impl Component for PveNodeDashboardPanel {
                 .with_child(self.view_actions(ctx, data))
                 .with_child(self.task_button(ctx))
                 //.with_child(NodeResourcesPanel::new(props.node.clone(), props.vmid))
+                .with_child(RrdGraphPanel::node(props.node.clone()))
                 .into()
         })
     }
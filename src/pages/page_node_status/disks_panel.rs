@@ -0,0 +1,340 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use yew::virtual_dom::{Key, VComp, VNode};
+
+use pwt::AsyncAbortGuard;
+use pwt::widget::{Column, Container, Fa, List, ListTile};
+use pwt::{prelude::*, widget::Row};
+
+use proxmox_human_byte::HumanByte;
+use proxmox_yew_comp::layout::list_tile::title_subtitle_column;
+use proxmox_yew_comp::layout::render_loaded_data;
+use proxmox_yew_comp::{http_get, percent_encoding::percent_encode_component};
+
+use crate::widgets::{HealthState, bottom_sheet, bottom_sheet_title, health_label, value_text};
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct NodeDisksPanel {
+    node: AttrValue,
+}
+
+impl NodeDisksPanel {
+    pub fn new(node: impl Into<AttrValue>) -> Self {
+        Self { node: node.into() }
+    }
+}
+
+// Disk list entry (`GET /nodes/{node}/disks/list`)
+#[derive(Clone, PartialEq, Deserialize)]
+pub struct DiskInfo {
+    devpath: String,
+    #[serde(default)]
+    model: Option<String>,
+    #[serde(default)]
+    serial: Option<String>,
+    #[serde(default)]
+    size: u64,
+    #[serde(rename = "type", default)]
+    disk_type: Option<String>,
+    // remaining life in percent, or "N/A"
+    #[serde(default)]
+    wearout: Value,
+    #[serde(default)]
+    used: Option<String>,
+    #[serde(default)]
+    health: Option<String>,
+}
+
+// SMART attribute (`GET /nodes/{node}/disks/smart`)
+#[derive(Clone, PartialEq, Deserialize)]
+pub struct SmartAttribute {
+    #[serde(default)]
+    id: Value,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    value: Value,
+    #[serde(default)]
+    worst: Value,
+    #[serde(default)]
+    threshold: Value,
+    #[serde(default)]
+    raw: Value,
+    #[serde(default)]
+    fail: Value,
+}
+
+#[derive(Clone, PartialEq, Deserialize)]
+pub struct SmartData {
+    #[serde(default)]
+    health: Option<String>,
+    #[serde(default)]
+    attributes: Vec<SmartAttribute>,
+    #[serde(default)]
+    text: Option<String>,
+}
+
+pub enum Msg {
+    Load,
+    LoadResult(Result<Vec<DiskInfo>, Error>),
+    ShowSmart(Option<String>),
+    SmartResult(Result<SmartData, Error>),
+}
+
+pub struct PveNodeDisksPanel {
+    data: Option<Result<Vec<DiskInfo>, String>>,
+    load_guard: Option<AsyncAbortGuard>,
+    // device path and loaded SMART data
+    smart: Option<(String, Option<Result<SmartData, String>>)>,
+    smart_guard: Option<AsyncAbortGuard>,
+}
+
+fn health_ok(health: Option<&str>) -> Option<bool> {
+    match health {
+        Some("PASSED") | Some("OK") => Some(true),
+        Some("UNKNOWN") | Some("") | None => None,
+        Some(_) => Some(false),
+    }
+}
+
+fn disk_health_label(health: Option<&str>) -> Container {
+    let state = match health_ok(health) {
+        Some(true) => HealthState::Ok,
+        Some(false) => HealthState::Error,
+        None => HealthState::Unknown,
+    };
+    health_label(state, health.unwrap_or("UNKNOWN").to_string())
+}
+
+impl DiskInfo {
+    fn type_label(&self) -> String {
+        match self.disk_type.as_deref() {
+            Some("ssd") => String::from("SSD"),
+            Some("hdd") => String::from("HDD"),
+            Some("nvme") => String::from("NVMe"),
+            Some("usb") => String::from("USB"),
+            Some(other) => other.to_string(),
+            None => tr!("unknown"),
+        }
+    }
+
+    fn wearout_text(&self) -> Option<String> {
+        // like the web interface, show used life instead of the remaining life
+        let remaining = match &self.wearout {
+            Value::Number(n) => n.as_f64(),
+            Value::String(s) => s.parse::<f64>().ok(),
+            _ => None,
+        }?;
+        Some(format!("{}: {:.0}%", tr!("Wearout"), 100.0 - remaining))
+    }
+
+    fn usage_text(&self) -> String {
+        match self.used.as_deref() {
+            Some("") | None => tr!("No usage"),
+            Some(used) => used.to_string(),
+        }
+    }
+}
+
+impl PveNodeDisksPanel {
+    fn view_disks(&self, ctx: &Context<Self>, disks: &[DiskInfo]) -> Html {
+        if disks.is_empty() {
+            return Container::new()
+                .padding(2)
+                .with_child(tr!("List is empty."))
+                .into();
+        }
+
+        let tiles: Vec<ListTile> = disks
+            .iter()
+            .map(|disk| {
+                let mut details = vec![
+                    disk.type_label(),
+                    HumanByte::new_binary(disk.size as f64).to_string(),
+                    disk.usage_text(),
+                ];
+                if let Some(wearout) = disk.wearout_text() {
+                    details.push(wearout);
+                }
+
+                let title = match &disk.model {
+                    Some(model) => format!("{} ({model})", disk.devpath),
+                    None => disk.devpath.clone(),
+                };
+                let subtitle = match &disk.serial {
+                    Some(serial) if !serial.is_empty() => {
+                        format!("{} - {}", details.join(", "), serial)
+                    }
+                    _ => details.join(", "),
+                };
+
+                let devpath = disk.devpath.clone();
+                ListTile::new()
+                    .key(Key::from(disk.devpath.clone()))
+                    .interactive(true)
+                    .onclick(
+                        ctx.link()
+                            .callback(move |_| Msg::ShowSmart(Some(devpath.clone()))),
+                    )
+                    .with_child(Fa::new("hdd-o").fixed_width().large().padding_end(1))
+                    .with_child(title_subtitle_column(title, subtitle))
+                    .with_child(disk_health_label(disk.health.as_deref()))
+            })
+            .collect();
+
+        List::from_tiles(tiles)
+            .class(pwt::css::FlexFit)
+            .grid_template_columns("auto 1fr auto")
+            .min_row_height(50)
+            .into()
+    }
+
+    fn view_smart_data(&self, data: &SmartData) -> Html {
+        let mut column = Column::new().gap(1).with_child(
+            Row::new()
+                .padding_x(2)
+                .gap(2)
+                .with_child(tr!("Health"))
+                .with_flex_spacer()
+                .with_child(disk_health_label(data.health.as_deref())),
+        );
+
+        if !data.attributes.is_empty() {
+            let tiles: Vec<ListTile> = data
+                .attributes
+                .iter()
+                .map(|attr| {
+                    let failing =
+                        matches!(&attr.fail, Value::String(s) if !s.is_empty() && s != "-");
+                    ListTile::new()
+                        .class(failing.then_some("pwt-color-error"))
+                        .with_child(title_subtitle_column(
+                            format!("{} {}", value_text(&attr.id), attr.name),
+                            format!(
+                                "{}: {} / {}: {} / {}: {}",
+                                tr!("Value"),
+                                value_text(&attr.value),
+                                tr!("Worst"),
+                                value_text(&attr.worst),
+                                tr!("Threshold"),
+                                value_text(&attr.threshold),
+                            ),
+                        ))
+                        .with_child(
+                            Container::new()
+                                .style("text-align", "end")
+                                .with_child(value_text(&attr.raw)),
+                        )
+                })
+                .collect();
+            column.add_child(List::from_tiles(tiles).grid_template_columns("1fr auto"));
+        }
+
+        if let Some(text) = &data.text {
+            column.add_child(
+                Container::from_tag("pre")
+                    .padding(2)
+                    .class("pwt-font-size-body-small")
+                    .style("white-space", "pre-wrap")
+                    .style("overflow-wrap", "anywhere")
+                    .with_child(text.clone()),
+            );
+        }
+
+        column.into()
+    }
+
+    fn view_smart(&self, ctx: &Context<Self>) -> Option<Html> {
+        let (devpath, smart) = self.smart.as_ref()?;
+
+        let content = render_loaded_data(smart, |data| self.view_smart_data(data));
+
+        let title = format!("{} ({devpath})", tr!("S.M.A.R.T. Values"));
+        Some(
+            bottom_sheet(bottom_sheet_title(title), content)
+                .on_close(ctx.link().callback(|_| Msg::ShowSmart(None)))
+                .into(),
+        )
+    }
+}
+
+impl Component for PveNodeDisksPanel {
+    type Message = Msg;
+    type Properties = NodeDisksPanel;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link().send_message(Msg::Load);
+        Self {
+            data: None,
+            load_guard: None,
+            smart: None,
+            smart_guard: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let props = ctx.props();
+        match msg {
+            Msg::Load => {
+                let link = ctx.link().clone();
+                let url = format!(
+                    "/nodes/{}/disks/list",
+                    percent_encode_component(&props.node)
+                );
+                self.load_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_get(&url, Some(json!({ "include-partitions": false }))).await;
+                    link.send_message(Msg::LoadResult(result));
+                }));
+            }
+            Msg::LoadResult(result) => {
+                self.load_guard = None;
+                self.data = Some(result.map_err(|err| err.to_string()));
+            }
+            Msg::ShowSmart(None) => {
+                self.smart = None;
+                self.smart_guard = None;
+            }
+            Msg::ShowSmart(Some(devpath)) => {
+                let link = ctx.link().clone();
+                let url = format!(
+                    "/nodes/{}/disks/smart",
+                    percent_encode_component(&props.node)
+                );
+                let param = json!({ "disk": devpath });
+                self.smart = Some((devpath, None));
+                self.smart_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_get(&url, Some(param)).await;
+                    link.send_message(Msg::SmartResult(result));
+                }));
+            }
+            Msg::SmartResult(result) => {
+                self.smart_guard = None;
+                if let Some((_, smart)) = &mut self.smart {
+                    *smart = Some(result.map_err(|err| err.to_string()));
+                }
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let content = render_loaded_data(&self.data, |disks| self.view_disks(ctx, disks));
+
+        Column::new()
+            .class(pwt::css::FlexFit)
+            .with_child(content)
+            .with_optional_child(self.view_smart(ctx))
+            .into()
+    }
+}
+
+impl From<NodeDisksPanel> for VNode {
+    fn from(props: NodeDisksPanel) -> Self {
+        let comp = VComp::new::<PveNodeDisksPanel>(Rc::new(props), None);
+        VNode::from(comp)
+    }
+}
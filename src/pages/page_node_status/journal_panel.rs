@@ -0,0 +1,362 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+use gloo_timers::callback::Timeout;
+use serde_json::json;
+
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::AsyncAbortGuard;
+use pwt::prelude::*;
+use pwt::widget::form::Field;
+use pwt::widget::{Button, Column, Container, MiniScroll, MiniScrollMode, Row};
+
+use proxmox_yew_comp::layout::render_loaded_data;
+use proxmox_yew_comp::{http_get, percent_encoding::percent_encode_component};
+
+use crate::widgets::toggle_button_class;
+
+// Maximum number of lines kept in the buffer
+const MAX_LINES: usize = 5000;
+
+// Distance (in pixels) from the bottom still considered as scrolled to the bottom
+const BOTTOM_THRESHOLD: i32 = 20;
+
+// Number of lines loaded without a time window
+const LAST_ENTRIES: usize = 500;
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct NodeJournalPanel {
+    node: AttrValue,
+}
+
+impl NodeJournalPanel {
+    pub fn new(node: impl Into<AttrValue>) -> Self {
+        Self { node: node.into() }
+    }
+}
+
+#[derive(Copy, Clone, Default, PartialEq)]
+pub enum JournalWindow {
+    #[default]
+    Recent,
+    Hour,
+    Day,
+}
+
+impl JournalWindow {
+    // no longer windows, the API returns the whole window at once
+    const ALL: [JournalWindow; 3] = [
+        JournalWindow::Recent,
+        JournalWindow::Hour,
+        JournalWindow::Day,
+    ];
+
+    fn label(&self) -> String {
+        match self {
+            JournalWindow::Recent => tr!("Recent"),
+            JournalWindow::Hour => tr!("Last Hour"),
+            JournalWindow::Day => tr!("Last Day"),
+        }
+    }
+
+    fn seconds(&self) -> Option<i64> {
+        match self {
+            JournalWindow::Recent => None,
+            JournalWindow::Hour => Some(3600),
+            JournalWindow::Day => Some(86400),
+        }
+    }
+}
+
+pub enum Msg {
+    Load,
+    LoadResult(Result<Vec<String>, Error>),
+    Follow,
+    FollowResult(Result<Vec<String>, Error>),
+    SetWindow(JournalWindow),
+    ToggleFollow,
+    SetUnitFilter(String),
+    SetSearch(String),
+}
+
+pub struct PveNodeJournalPanel {
+    data: Option<Result<Vec<String>, String>>,
+    end_cursor: Option<String>,
+    window: JournalWindow,
+    follow: bool,
+    unit_filter: String,
+    search: String,
+    load_guard: Option<AsyncAbortGuard>,
+    follow_timeout: Option<Timeout>,
+    log_ref: NodeRef,
+    // scroll to the end of the log after the next render
+    scroll_to_bottom: bool,
+}
+
+// The journal API returns the start cursor as first and the end cursor as last line.
+fn split_cursors(mut lines: Vec<String>) -> (Vec<String>, Option<String>) {
+    if lines.len() < 2 {
+        return (Vec::new(), None);
+    }
+    let end_cursor = lines.pop();
+    lines.remove(0);
+    (lines, end_cursor)
+}
+
+// Only keep the last MAX_LINES lines, the time windows may return a lot more.
+fn truncate_lines(lines: &mut Vec<String>) {
+    if lines.len() > MAX_LINES {
+        lines.drain(..lines.len() - MAX_LINES);
+    }
+}
+
+// Extract the syslog identifier (`pveproxy` from `... host pveproxy[1234]: msg`).
+fn line_identifier(line: &str) -> Option<&str> {
+    let token = line.split_whitespace().nth(4)?;
+    let token = token.trim_end_matches(':');
+    token.split('[').next()
+}
+
+impl PveNodeJournalPanel {
+    fn journal_url(&self, ctx: &Context<Self>) -> String {
+        format!(
+            "/nodes/{}/journal",
+            percent_encode_component(&ctx.props().node)
+        )
+    }
+
+    fn at_bottom(&self) -> bool {
+        match self.log_ref.cast::<web_sys::Element>() {
+            Some(el) => {
+                el.scroll_top() + el.client_height() >= el.scroll_height() - BOTTOM_THRESHOLD
+            }
+            None => true,
+        }
+    }
+
+    fn schedule_follow(&mut self, ctx: &Context<Self>) {
+        if !self.follow {
+            self.follow_timeout = None;
+            return;
+        }
+        let link = ctx.link().clone();
+        self.follow_timeout = Some(Timeout::new(3000, move || {
+            link.send_message(Msg::Follow);
+        }));
+    }
+
+    fn filtered_lines<'a>(&self, lines: &'a [String]) -> Vec<&'a str> {
+        let unit = self.unit_filter.trim().trim_end_matches(".service");
+        let search = self.search.trim().to_lowercase();
+
+        lines
+            .iter()
+            .filter(|line| {
+                unit.is_empty()
+                    || line_identifier(line)
+                        .map(|id| id.contains(unit))
+                        .unwrap_or(false)
+            })
+            .filter(|line| search.is_empty() || line.to_lowercase().contains(&search))
+            .map(|line| line.as_str())
+            .collect()
+    }
+
+    fn view_controls(&self, ctx: &Context<Self>) -> Html {
+        let mut row = Row::new().gap(1);
+        for window in JournalWindow::ALL {
+            row.add_child(
+                Button::new(window.label())
+                    .class(toggle_button_class(self.window == window))
+                    .on_activate(ctx.link().callback(move |_| Msg::SetWindow(window))),
+            );
+        }
+        row.add_child(
+            Button::new(tr!("Follow"))
+                .icon_class("fa fa-play-circle-o")
+                .class(toggle_button_class(self.follow))
+                .on_activate(ctx.link().callback(|_| Msg::ToggleFollow)),
+        );
+
+        Column::new()
+            .padding(2)
+            .gap(1)
+            .with_child(
+                MiniScroll::new(row)
+                    .scroll_mode(MiniScrollMode::Native)
+                    .class(pwt::css::Flex::None),
+            )
+            .with_child(
+                Row::new()
+                    .gap(1)
+                    .with_child(
+                        Field::new()
+                            .class(pwt::css::Flex::Fill)
+                            .value(self.unit_filter.clone())
+                            .placeholder(tr!("Service, e.g. pveproxy"))
+                            .on_change(ctx.link().callback(Msg::SetUnitFilter)),
+                    )
+                    .with_child(
+                        Field::new()
+                            .class(pwt::css::Flex::Fill)
+                            .value(self.search.clone())
+                            .placeholder(tr!("Search"))
+                            .on_change(ctx.link().callback(Msg::SetSearch)),
+                    ),
+            )
+            .into()
+    }
+}
+
+impl Component for PveNodeJournalPanel {
+    type Message = Msg;
+    type Properties = NodeJournalPanel;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link().send_message(Msg::Load);
+        Self {
+            data: None,
+            end_cursor: None,
+            window: JournalWindow::default(),
+            follow: false,
+            unit_filter: String::new(),
+            search: String::new(),
+            load_guard: None,
+            follow_timeout: None,
+            log_ref: NodeRef::default(),
+            scroll_to_bottom: false,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Load => {
+                let url = self.journal_url(ctx);
+                let param = match self.window.seconds() {
+                    Some(seconds) => {
+                        let now = (js_sys::Date::now() / 1000.0) as i64;
+                        json!({ "since": now - seconds })
+                    }
+                    None => json!({ "lastentries": LAST_ENTRIES }),
+                };
+                self.follow_timeout = None;
+                self.data = None;
+                let link = ctx.link().clone();
+                self.load_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_get(&url, Some(param)).await;
+                    link.send_message(Msg::LoadResult(result));
+                }));
+            }
+            Msg::LoadResult(result) => {
+                self.load_guard = None;
+                match result {
+                    Ok(lines) => {
+                        let (mut lines, end_cursor) = split_cursors(lines);
+                        truncate_lines(&mut lines);
+                        self.end_cursor = end_cursor;
+                        self.data = Some(Ok(lines));
+                        self.scroll_to_bottom = self.follow;
+                    }
+                    Err(err) => self.data = Some(Err(err.to_string())),
+                }
+                self.schedule_follow(ctx);
+            }
+            Msg::Follow => {
+                let Some(cursor) = self.end_cursor.clone() else {
+                    return false;
+                };
+                let url = self.journal_url(ctx);
+                let link = ctx.link().clone();
+                self.load_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_get(&url, Some(json!({ "startcursor": cursor }))).await;
+                    link.send_message(Msg::FollowResult(result));
+                }));
+                return false;
+            }
+            Msg::FollowResult(result) => {
+                self.load_guard = None;
+                match result {
+                    Ok(new_lines) => {
+                        let (new_lines, end_cursor) = split_cursors(new_lines);
+                        if end_cursor.is_some() {
+                            self.end_cursor = end_cursor;
+                        }
+                        // keep the position if the user scrolled up
+                        self.scroll_to_bottom = !new_lines.is_empty() && self.at_bottom();
+                        if let Some(Ok(lines)) = &mut self.data {
+                            lines.extend(new_lines);
+                            truncate_lines(lines);
+                        }
+                    }
+                    Err(err) => log::error!("journal follow failed: {err}"),
+                }
+                self.schedule_follow(ctx);
+            }
+            Msg::SetWindow(window) => {
+                self.window = window;
+                ctx.link().send_message(Msg::Load);
+            }
+            Msg::ToggleFollow => {
+                self.follow = !self.follow;
+                if self.follow {
+                    // following only makes sense from the current end of the journal
+                    self.window = JournalWindow::Recent;
+                    ctx.link().send_message(Msg::Load);
+                } else {
+                    self.follow_timeout = None;
+                }
+            }
+            Msg::SetUnitFilter(unit) => self.unit_filter = unit,
+            Msg::SetSearch(search) => self.search = search,
+        }
+        true
+    }
+
+    fn rendered(&mut self, _ctx: &Context<Self>, _first_render: bool) {
+        if self.scroll_to_bottom {
+            self.scroll_to_bottom = false;
+            if let Some(el) = self.log_ref.cast::<web_sys::Element>() {
+                el.set_scroll_top(el.scroll_height());
+            }
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let log = render_loaded_data(&self.data, |lines| {
+            let filtered = self.filtered_lines(lines);
+            Column::new()
+                .class(pwt::css::FlexFit)
+                .with_child(
+                    Container::new()
+                        .padding_x(2)
+                        .class("pwt-font-size-label-medium")
+                        .with_child(tr!("{0} of {1} lines", filtered.len(), lines.len())),
+                )
+                .with_child(html! {
+                    <div ref={self.log_ref.clone()} class="pwt-flex-fit pwt-overflow-auto">
+                        <pre
+                            class="pwt-font-size-body-small pwt-p-2"
+                            style="margin: 0; white-space: pre-wrap; overflow-wrap: anywhere;"
+                        >
+                            {filtered.join("\n")}
+                        </pre>
+                    </div>
+                })
+                .into()
+        });
+
+        Column::new()
+            .class(pwt::css::FlexFit)
+            .with_child(self.view_controls(ctx))
+            .with_child(log)
+            .into()
+    }
+}
+
+impl From<NodeJournalPanel> for VNode {
+    fn from(props: NodeJournalPanel) -> Self {
+        let comp = VComp::new::<PveNodeJournalPanel>(Rc::new(props), None);
+        VNode::from(comp)
+    }
+}
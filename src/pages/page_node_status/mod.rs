@@ -24,9 +24,27 @@ pub use dashboard_panel::NodeDashboardPanel;
 mod services_panel;
 pub use services_panel::NodeServicesPanel;
 
+mod service_action_dialog;
+pub use service_action_dialog::ServiceActionDialog;
+
 mod updates_panel;
 pub use updates_panel::NodeUpdatesPanel;
 
+mod repository_status;
+pub use repository_status::{load_repository_data, repository_warnings};
+
+mod repositories_panel;
+pub use repositories_panel::NodeRepositoriesPanel;
+
+mod journal_panel;
+pub use journal_panel::NodeJournalPanel;
+
+mod disks_panel;
+pub use disks_panel::NodeDisksPanel;
+
+mod zfs_panel;
+pub use zfs_panel::{NodeZfsPanel, ZfsPoolInfo};
+
 #[derive(Clone, PartialEq, Properties)]
 pub struct PageNodeStatus {
     node: AttrValue,
@@ -46,6 +64,10 @@ pub enum ViewState {
     Dashboard,
     Services,
     Updates,
+    Repositories,
+    Journal,
+    Disks,
+    Zfs,
 }
 
 pub enum Msg {
@@ -118,6 +140,13 @@ impl Component for PvePageNodeStatus {
                 NodeServicesPanel::new(props.node.clone(), standalone).into(),
             ),
             ViewState::Updates => ("updates", NodeUpdatesPanel::new(props.node.clone()).into()),
+            ViewState::Repositories => (
+                "repositories",
+                NodeRepositoriesPanel::new(props.node.clone()).into(),
+            ),
+            ViewState::Journal => ("journal", NodeJournalPanel::new(props.node.clone()).into()),
+            ViewState::Disks => ("disks", NodeDisksPanel::new(props.node.clone()).into()),
+            ViewState::Zfs => ("zfs", NodeZfsPanel::new(props.node.clone()).into()),
         };
 
         let tab_bar = TabBar::new()
@@ -149,6 +178,39 @@ impl Component for PvePageNodeStatus {
                         ctx.link()
                             .callback(|_| Msg::SetViewState(ViewState::Updates)),
                     ),
+            )
+            .with_item(
+                TabBarItem::new()
+                    .label(tr!("Repositories"))
+                    .key("repositories")
+                    .on_activate(
+                        ctx.link()
+                            .callback(|_| Msg::SetViewState(ViewState::Repositories)),
+                    ),
+            )
+            .with_item(
+                TabBarItem::new()
+                    .label(tr!("System Log"))
+                    .key("journal")
+                    .on_activate(
+                        ctx.link()
+                            .callback(|_| Msg::SetViewState(ViewState::Journal)),
+                    ),
+            )
+            .with_item(
+                TabBarItem::new()
+                    .label(tr!("Disks"))
+                    .key("disks")
+                    .on_activate(
+                        ctx.link()
+                            .callback(|_| Msg::SetViewState(ViewState::Disks)),
+                    ),
+            )
+            .with_item(
+                TabBarItem::new()
+                    .label("ZFS")
+                    .key("zfs")
+                    .on_activate(ctx.link().callback(|_| Msg::SetViewState(ViewState::Zfs))),
             );
 
         Column::new()
@@ -0,0 +1,285 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+use serde_json::{Value, json};
+
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::AsyncAbortGuard;
+use pwt::widget::{ActionIcon, Column, ConfirmDialog, Fa, List, ListTile};
+use pwt::{prelude::*, widget::Container};
+
+use proxmox_apt_api_types::{APTRepository, APTStandardRepository};
+
+use proxmox_yew_comp::layout::card::standard_card;
+use proxmox_yew_comp::layout::list_tile::{icon_list_tile, title_subtitle_column};
+use proxmox_yew_comp::layout::render_loaded_data;
+use proxmox_yew_comp::{http_post, http_put, percent_encoding::percent_encode_component};
+
+use super::repository_status::{
+    RepositoryData, handle_name, load_repository_data, repository_warnings,
+};
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct NodeRepositoriesPanel {
+    node: AttrValue,
+}
+
+impl NodeRepositoriesPanel {
+    pub fn new(node: impl Into<AttrValue>) -> Self {
+        Self { node: node.into() }
+    }
+}
+
+pub enum Msg {
+    Load,
+    LoadResult(Result<RepositoryData, Error>),
+    // path, index, enabled
+    SetEnabled(String, usize, bool),
+    ConfirmAdd(Option<APTStandardRepository>),
+    Add(String),
+    CommandResult(Result<Value, Error>),
+}
+
+pub struct PveNodeRepositoriesPanel {
+    data: Option<Result<RepositoryData, String>>,
+    load_guard: Option<AsyncAbortGuard>,
+    cmd_guard: Option<AsyncAbortGuard>,
+    confirm_add: Option<APTStandardRepository>,
+}
+
+fn repository_text(repo: &APTRepository) -> String {
+    let types: Vec<String> = repo
+        .types
+        .iter()
+        .filter_map(|ty| serde_plain::to_string(ty).ok())
+        .collect();
+    format!(
+        "{} {} {}",
+        types.join(" "),
+        repo.uris.join(" "),
+        repo.suites.join(" ")
+    )
+}
+
+impl PveNodeRepositoriesPanel {
+    fn view_warnings(&self, data: &RepositoryData) -> Html {
+        let warnings = repository_warnings(data);
+
+        let tiles: Vec<ListTile> = if warnings.is_empty() {
+            vec![icon_list_tile(
+                Fa::new("check").class("pwt-color-primary"),
+                tr!("All OK, you have production-ready repositories configured!"),
+                (),
+                (),
+            )]
+        } else {
+            warnings
+                .into_iter()
+                .map(|warning| {
+                    let icon = if warning.error {
+                        Fa::new("times-circle").class("pwt-color-error")
+                    } else {
+                        Fa::new("exclamation-triangle").class("pwt-color-warning")
+                    };
+                    icon_list_tile(icon, warning.message, (), ())
+                })
+                .collect()
+        };
+
+        standard_card(tr!("Status"), (), ())
+            .with_child(List::from_tiles(tiles).grid_template_columns("auto 1fr"))
+            .into()
+    }
+
+    fn view_standard_repos(&self, ctx: &Context<Self>, data: &RepositoryData) -> Html {
+        let tiles: Vec<ListTile> = data
+            .repositories
+            .standard_repos
+            .iter()
+            .map(|repo| {
+                let status = match repo.status {
+                    Some(true) => tr!("enabled"),
+                    Some(false) => tr!("disabled"),
+                    None => tr!("not configured"),
+                };
+                let mut tile = ListTile::new()
+                    .with_child(title_subtitle_column(
+                        repo.name.clone(),
+                        repo.description.clone(),
+                    ))
+                    .with_child(Container::new().with_child(status));
+                if repo.status.is_none() {
+                    let repo = repo.clone();
+                    tile = tile.interactive(true).onclick(
+                        ctx.link()
+                            .callback(move |_| Msg::ConfirmAdd(Some(repo.clone()))),
+                    );
+                }
+                tile
+            })
+            .collect();
+
+        standard_card(
+            tr!("Standard Repositories"),
+            tr!("Tap an unconfigured repository to add it."),
+            (),
+        )
+        .with_child(List::from_tiles(tiles).grid_template_columns("1fr auto"))
+        .into()
+    }
+
+    fn view_configured_repos(&self, ctx: &Context<Self>, data: &RepositoryData) -> Html {
+        let busy = self.cmd_guard.is_some();
+
+        let mut tiles: Vec<ListTile> = Vec::new();
+        for file in &data.repositories.files {
+            let Some(path) = &file.path else {
+                continue;
+            };
+            for (index, repo) in file.repositories.iter().enumerate() {
+                let enabled = repo.enabled;
+                let icon = if enabled {
+                    "fa fa-toggle-on"
+                } else {
+                    "fa fa-toggle-off"
+                };
+                let path = path.clone();
+                tiles.push(
+                    ListTile::new()
+                        .with_child(title_subtitle_column(
+                            repository_text(repo),
+                            format!("{} ({path}:{index})", repo.components.join(" ")),
+                        ))
+                        .with_child(
+                            ActionIcon::new(icon)
+                                .class("pwt-font-size-title-large")
+                                .disabled(busy)
+                                .on_activate(ctx.link().callback(move |_| {
+                                    Msg::SetEnabled(path.clone(), index, !enabled)
+                                })),
+                        ),
+                );
+            }
+        }
+
+        standard_card(tr!("Repositories"), (), ())
+            .with_child(List::from_tiles(tiles).grid_template_columns("1fr auto"))
+            .into()
+    }
+
+    fn repositories_url(&self, ctx: &Context<Self>) -> String {
+        format!(
+            "/nodes/{}/apt/repositories",
+            percent_encode_component(&ctx.props().node)
+        )
+    }
+
+    fn digest(&self) -> Option<String> {
+        match &self.data {
+            Some(Ok(data)) => Some(data.repositories.digest.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl Component for PveNodeRepositoriesPanel {
+    type Message = Msg;
+    type Properties = NodeRepositoriesPanel;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link().send_message(Msg::Load);
+        Self {
+            data: None,
+            load_guard: None,
+            cmd_guard: None,
+            confirm_add: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let props = ctx.props();
+        let link = ctx.link().clone();
+        match msg {
+            Msg::Load => {
+                let node = props.node.to_string();
+                self.load_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = load_repository_data(&node).await;
+                    link.send_message(Msg::LoadResult(result));
+                }));
+            }
+            Msg::LoadResult(result) => {
+                self.load_guard = None;
+                self.data = Some(result.map_err(|err| err.to_string()));
+            }
+            Msg::SetEnabled(path, index, enabled) => {
+                let url = self.repositories_url(ctx);
+                let param = json!({
+                    "path": path,
+                    "index": index,
+                    "enabled": enabled,
+                    "digest": self.digest(),
+                });
+                self.cmd_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_post(&url, Some(param)).await;
+                    link.send_message(Msg::CommandResult(result));
+                }));
+            }
+            Msg::ConfirmAdd(repo) => self.confirm_add = repo,
+            Msg::Add(handle) => {
+                self.confirm_add = None;
+                let url = self.repositories_url(ctx);
+                let param = json!({
+                    "handle": handle,
+                    "digest": self.digest(),
+                });
+                self.cmd_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_put(&url, Some(param)).await;
+                    link.send_message(Msg::CommandResult(result));
+                }));
+            }
+            Msg::CommandResult(result) => {
+                self.cmd_guard = None;
+                if let Err(err) = result {
+                    crate::show_failed_command_error(ctx.link(), err);
+                }
+                ctx.link().send_message(Msg::Load);
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let confirm_add = self.confirm_add.as_ref().map(|repo| {
+            let handle = handle_name(repo);
+            ConfirmDialog::default()
+                .confirm_message(tr!("Add repository '{0}'?", repo.name))
+                .on_close(ctx.link().callback(|_| Msg::ConfirmAdd(None)))
+                .on_confirm(ctx.link().callback(move |_| Msg::Add(handle.clone())))
+        });
+
+        let content = render_loaded_data(&self.data, |data| {
+            Column::new()
+                .gap(2)
+                .with_child(self.view_warnings(data))
+                .with_child(self.view_standard_repos(ctx, data))
+                .with_child(self.view_configured_repos(ctx, data))
+                .into()
+        });
+
+        Column::new()
+            .class(pwt::css::FlexFit)
+            .class("pwt-overflow-auto")
+            .padding(2)
+            .with_child(content)
+            .with_optional_child(confirm_add)
+            .into()
+    }
+}
+
+impl From<NodeRepositoriesPanel> for VNode {
+    fn from(props: NodeRepositoriesPanel) -> Self {
+        let comp = VComp::new::<PveNodeRepositoriesPanel>(Rc::new(props), None);
+        VNode::from(comp)
+    }
+}
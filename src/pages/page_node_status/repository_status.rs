@@ -0,0 +1,200 @@
+use std::collections::HashSet;
+
+use anyhow::Error;
+use serde_json::Value;
+
+use pwt::prelude::*;
+
+use proxmox_apt_api_types::{APTRepositoriesResult, APTRepository, APTStandardRepository};
+
+use proxmox_yew_comp::{http_get, percent_encoding::percent_encode_component};
+
+pub struct RepositoryData {
+    pub repositories: APTRepositoriesResult,
+    // true if the node has an active subscription
+    pub subscription: bool,
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum RepositoryWarningKind {
+    FileError,
+    NoPveRepository,
+    EnterpriseWithoutSubscription,
+    NoSubscriptionRepository,
+    TestRepository,
+    NoSecurityRepository,
+    MixedSuites,
+    Info,
+}
+
+impl RepositoryWarningKind {
+    /// Warnings which are also shown with the subscription status on the dashboard.
+    pub fn affects_subscription(&self) -> bool {
+        matches!(
+            self,
+            RepositoryWarningKind::NoPveRepository
+                | RepositoryWarningKind::EnterpriseWithoutSubscription
+        )
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct RepositoryWarning {
+    pub kind: RepositoryWarningKind,
+    pub error: bool,
+    pub message: String,
+}
+
+impl RepositoryWarning {
+    fn new(kind: RepositoryWarningKind, error: bool, message: String) -> Self {
+        Self {
+            kind,
+            error,
+            message,
+        }
+    }
+}
+
+pub fn handle_name(repo: &APTStandardRepository) -> String {
+    serde_plain::to_string(&repo.handle).unwrap_or_default()
+}
+
+/// Load the APT repositories and subscription status of a node.
+pub async fn load_repository_data(node: &str) -> Result<RepositoryData, Error> {
+    let node = percent_encode_component(node);
+    let url = format!("/nodes/{node}/apt/repositories");
+    let repositories: APTRepositoriesResult = http_get(&url, None).await?;
+
+    // missing subscription info means there is no active subscription
+    let subscription: Result<Value, Error> =
+        http_get(&format!("/nodes/{node}/subscription"), None).await;
+    let subscription = match subscription {
+        Ok(info) => info["status"]
+            .as_str()
+            .map(|status| status.eq_ignore_ascii_case("active"))
+            .unwrap_or(false),
+        Err(_) => false,
+    };
+
+    Ok(RepositoryData {
+        repositories,
+        subscription,
+    })
+}
+
+/// Collect the same warnings the web interface shows for the repository setup.
+pub fn repository_warnings(data: &RepositoryData) -> Vec<RepositoryWarning> {
+    use RepositoryWarningKind::*;
+
+    let repositories = &data.repositories;
+    let mut warnings: Vec<RepositoryWarning> = Vec::new();
+
+    for error in &repositories.errors {
+        warnings.push(RepositoryWarning::new(
+            FileError,
+            true,
+            format!("{}: {}", error.path, error.error),
+        ));
+    }
+
+    let enabled = |handle: &str| {
+        repositories
+            .standard_repos
+            .iter()
+            .any(|repo| repo.status == Some(true) && handle_name(repo) == handle)
+    };
+
+    let pve_enabled = ["enterprise", "no-subscription", "test"]
+        .iter()
+        .any(|handle| enabled(handle));
+    if !pve_enabled {
+        warnings.push(RepositoryWarning::new(
+            NoPveRepository,
+            true,
+            tr!("No Proxmox VE repository is enabled, you do not get any updates!"),
+        ));
+    }
+
+    let enterprise_enabled = repositories
+        .standard_repos
+        .iter()
+        .any(|repo| repo.status == Some(true) && handle_name(repo).ends_with("enterprise"));
+    if enterprise_enabled && !data.subscription {
+        warnings.push(RepositoryWarning::new(
+            EnterpriseWithoutSubscription,
+            false,
+            tr!("The enterprise repository is enabled, but there is no active subscription!"),
+        ));
+    }
+
+    if enabled("no-subscription") {
+        warnings.push(RepositoryWarning::new(
+            NoSubscriptionRepository,
+            false,
+            tr!("The no-subscription repository is not recommended for production use!"),
+        ));
+    }
+    if enabled("test") {
+        warnings.push(RepositoryWarning::new(
+            TestRepository,
+            false,
+            tr!("The test repository may pull in unstable updates and is not recommended for production use!"),
+        ));
+    }
+
+    let enabled_repos: Vec<&APTRepository> = repositories
+        .files
+        .iter()
+        .flat_map(|file| file.repositories.iter())
+        .filter(|repo| repo.enabled)
+        .collect();
+
+    let has_security = enabled_repos
+        .iter()
+        .any(|repo| repo.suites.iter().any(|suite| suite.ends_with("-security")));
+    if !has_security {
+        warnings.push(RepositoryWarning::new(
+            NoSecurityRepository,
+            false,
+            tr!("No Debian security repository is enabled!"),
+        ));
+    }
+
+    let mut suites = HashSet::new();
+    for repo in &enabled_repos {
+        let official = repo
+            .uris
+            .iter()
+            .any(|uri| uri.contains("debian.org") || uri.contains("proxmox.com"));
+        if !official {
+            continue;
+        }
+        for suite in &repo.suites {
+            let base = suite.split(['-', '/']).next().unwrap_or(suite);
+            if !base.is_empty() {
+                suites.insert(base.to_string());
+            }
+        }
+    }
+    if suites.len() > 1 {
+        let mut suites: Vec<String> = suites.into_iter().collect();
+        suites.sort();
+        warnings.push(RepositoryWarning::new(
+            MixedSuites,
+            false,
+            tr!("Mixed suites detected: {0}", suites.join(", ")),
+        ));
+    }
+
+    for info in &repositories.infos {
+        if info.kind == "warning" {
+            warnings.push(RepositoryWarning::new(
+                Info,
+                false,
+                format!("{}: {}", info.path, info.message),
+            ));
+        }
+    }
+
+    warnings
+}
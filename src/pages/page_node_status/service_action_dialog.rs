@@ -0,0 +1,279 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+use serde_json::{Value, json};
+
+use yew::html::IntoEventCallback;
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::AsyncAbortGuard;
+use pwt::prelude::*;
+use pwt::widget::{Button, Column, ConfirmDialog, Container, MiniScroll, MiniScrollMode, Row};
+
+use pwt_macros::builder;
+
+use proxmox_yew_comp::layout::list_tile::title_subtitle_column;
+use proxmox_yew_comp::layout::render_loaded_data;
+use proxmox_yew_comp::{http_get, http_post, percent_encoding::percent_encode_component};
+
+use crate::widgets::bottom_sheet;
+
+/// Services which break cluster communication or the web interface when stopped.
+const CRITICAL_SERVICES: &[&str] = &["pve-cluster", "corosync", "pveproxy"];
+
+// Number of journal lines shown
+const JOURNAL_LINES: usize = 50;
+
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct ServiceActionDialog {
+    node: AttrValue,
+    /// Service ID used in the API path (e.g. `syslog`)
+    service: AttrValue,
+    /// Systemd unit name (e.g. `rsyslog`), used to filter the journal
+    unit: AttrValue,
+    desc: AttrValue,
+    state: AttrValue,
+
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    #[prop_or_default]
+    /// Called when the dialog is closed
+    pub on_close: Option<Callback<()>>,
+
+    #[builder_cb(IntoEventCallback, into_event_callback, String)]
+    #[prop_or_default]
+    /// Called with the task UPID after a service command started
+    pub on_start_task: Option<Callback<String>>,
+}
+
+impl ServiceActionDialog {
+    pub fn new(
+        node: impl Into<AttrValue>,
+        service: impl Into<AttrValue>,
+        unit: impl Into<AttrValue>,
+        desc: impl Into<AttrValue>,
+        state: impl Into<AttrValue>,
+    ) -> Self {
+        yew::props!(Self {
+            node: node.into(),
+            service: service.into(),
+            unit: unit.into(),
+            desc: desc.into(),
+            state: state.into(),
+        })
+    }
+}
+
+pub enum Msg {
+    JournalResult(Result<Vec<Value>, Error>),
+    Command(&'static str),
+    Execute(&'static str),
+    Confirm(Option<&'static str>),
+    CommandResult(Result<String, Error>),
+}
+
+pub struct PveServiceActionDialog {
+    journal: Option<Result<Vec<String>, String>>,
+    journal_guard: Option<AsyncAbortGuard>,
+    cmd_guard: Option<AsyncAbortGuard>,
+    confirm: Option<&'static str>,
+}
+
+// Format an epoch (local time) as accepted by the syslog `since` parameter.
+fn format_since(epoch: f64) -> String {
+    let date = js_sys::Date::new(&(epoch * 1000.0).into());
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        date.get_full_year(),
+        date.get_month() + 1,
+        date.get_date(),
+        date.get_hours(),
+        date.get_minutes(),
+        date.get_seconds(),
+    )
+}
+
+impl PveServiceActionDialog {
+    fn view_actions(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let running = props.state.as_str() == "running";
+        let busy = self.cmd_guard.is_some();
+
+        let button = |label: String, icon: &str, cmd: &'static str, enabled: bool| {
+            Button::new(label)
+                .icon_class(format!("fa fa-{icon}"))
+                .disabled(busy || !enabled)
+                .on_activate(ctx.link().callback(move |_| Msg::Command(cmd)))
+        };
+
+        let row = Row::new()
+            .gap(2)
+            .with_child(button(tr!("Start"), "play", "start", !running))
+            .with_child(button(tr!("Stop"), "stop", "stop", running))
+            .with_child(button(tr!("Restart"), "refresh", "restart", running))
+            .with_child(button(tr!("Reload"), "retweet", "reload", running));
+
+        MiniScroll::new(row)
+            .scroll_mode(MiniScrollMode::Native)
+            .class(pwt::css::Flex::None)
+            .into()
+    }
+}
+
+impl Component for PveServiceActionDialog {
+    type Message = Msg;
+    type Properties = ServiceActionDialog;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let props = ctx.props();
+
+        let url = format!("/nodes/{}/syslog", percent_encode_component(&props.node));
+        let since = format_since(js_sys::Date::now() / 1000.0 - 24.0 * 3600.0);
+        let param = json!({
+            "service": props.unit.to_string(),
+            "since": since,
+            "limit": 5000,
+        });
+        let link = ctx.link().clone();
+        let journal_guard = AsyncAbortGuard::spawn(async move {
+            let result = http_get(&url, Some(param)).await;
+            link.send_message(Msg::JournalResult(result));
+        });
+
+        Self {
+            journal: None,
+            journal_guard: Some(journal_guard),
+            cmd_guard: None,
+            confirm: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let props = ctx.props();
+        match msg {
+            Msg::JournalResult(result) => {
+                self.journal_guard = None;
+                self.journal = Some(
+                    result
+                        .map(|list| {
+                            let lines: Vec<String> = list
+                                .iter()
+                                .filter_map(|item| item["t"].as_str().map(String::from))
+                                .collect();
+                            let skip = lines.len().saturating_sub(JOURNAL_LINES);
+                            lines.into_iter().skip(skip).collect()
+                        })
+                        .map_err(|err| err.to_string()),
+                );
+            }
+            Msg::Confirm(cmd) => self.confirm = cmd,
+            Msg::Command(cmd) => {
+                let critical = CRITICAL_SERVICES.contains(&props.service.as_str());
+                let disrupts = cmd == "stop" || cmd == "restart";
+                if critical && disrupts {
+                    self.confirm = Some(cmd);
+                } else {
+                    ctx.link().send_message(Msg::Execute(cmd));
+                }
+            }
+            Msg::Execute(cmd) => {
+                self.confirm = None;
+                let url = format!(
+                    "/nodes/{}/services/{}/{cmd}",
+                    percent_encode_component(&props.node),
+                    percent_encode_component(&props.service),
+                );
+                let link = ctx.link().clone();
+                self.cmd_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_post(&url, None).await;
+                    link.send_message(Msg::CommandResult(result));
+                }));
+            }
+            Msg::CommandResult(result) => {
+                self.cmd_guard = None;
+                match result {
+                    Ok(upid) => {
+                        if let Some(on_start_task) = &props.on_start_task {
+                            on_start_task.emit(upid);
+                        }
+                        if let Some(on_close) = &props.on_close {
+                            on_close.emit(());
+                        }
+                    }
+                    Err(err) => crate::show_failed_command_error(ctx.link(), err),
+                }
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+
+        let journal = render_loaded_data(&self.journal, |lines| {
+            if lines.is_empty() {
+                return Container::new()
+                    .padding(2)
+                    .with_child(tr!("No journal entries in the last 24 hours."))
+                    .into();
+            }
+            Container::from_tag("pre")
+                .padding(1)
+                .class("pwt-font-size-body-small")
+                .style("white-space", "pre-wrap")
+                .style("overflow-wrap", "anywhere")
+                .with_child(lines.join("\n"))
+                .into()
+        });
+
+        let confirm = self.confirm.map(|cmd| {
+            let message = if cmd == "stop" {
+                tr!(
+                    "Stopping '{0}' can make the node or cluster unreachable. Continue?",
+                    props.service
+                )
+            } else {
+                tr!(
+                    "Restarting '{0}' can interrupt the node or cluster. Continue?",
+                    props.service
+                )
+            };
+            ConfirmDialog::default()
+                .confirm_message(message)
+                .on_close(ctx.link().callback(|_| Msg::Confirm(None)))
+                .on_confirm(ctx.link().callback(move |_| Msg::Execute(cmd)))
+        });
+
+        let header = Column::new()
+            .padding(2)
+            .gap(2)
+            .with_child(
+                Row::new()
+                    .class(pwt::css::AlignItems::Center)
+                    .with_child(title_subtitle_column(
+                        props.unit.clone(),
+                        props.desc.clone(),
+                    ))
+                    .with_flex_spacer()
+                    .with_child(props.state.clone()),
+            )
+            .with_child(self.view_actions(ctx))
+            .with_child(
+                Container::new()
+                    .class("pwt-font-size-title-small")
+                    .with_child(tr!("Journal")),
+            )
+            .with_optional_child(confirm);
+
+        bottom_sheet(header, Container::new().padding_x(2).with_child(journal))
+            .on_close(props.on_close.clone())
+            .into()
+    }
+}
+
+impl From<ServiceActionDialog> for VNode {
+    fn from(props: ServiceActionDialog) -> Self {
+        let comp = VComp::new::<PveServiceActionDialog>(Rc::new(props), None);
+        VNode::from(comp)
+    }
+}
@@ -4,6 +4,7 @@ use anyhow::Error;
 use gloo_timers::callback::Timeout;
 
 use yew::virtual_dom::{VComp, VNode};
+use yew_router::scope_ext::RouterScopeExt;
 
 use pwt::widget::{Column, Container, List};
 use pwt::AsyncAbortGuard;
@@ -14,6 +15,10 @@ use proxmox_yew_comp::layout::render_loaded_data;
 use proxmox_yew_comp::pve_api_types::ServiceStatus;
 use proxmox_yew_comp::{http_get, percent_encoding::percent_encode_component};
 
+use crate::widgets::TasksListButton;
+
+use super::ServiceActionDialog;
+
 #[derive(Clone, PartialEq, Properties)]
 pub struct NodeServicesPanel {
     node: AttrValue,
@@ -32,13 +37,17 @@ impl NodeServicesPanel {
 pub enum Msg {
     Load,
     LoadResult(Result<Vec<ServiceStatus>, Error>),
+    ShowActions(Option<String>),
+    StartTask(String),
+    TaskFinished,
 }
 
 pub struct PveNodeServicesPanel {
     data: Option<Result<Vec<ServiceStatus>, String>>,
     reload_timeout: Option<Timeout>,
     load_guard: Option<AsyncAbortGuard>,
-    //cmd_guard: Option<AsyncAbortGuard>,
+    show_actions: Option<String>,
+    running_upid: Option<String>,
 }
 
 fn service_state_icon(s: &ServiceStatus) -> Container {
@@ -81,11 +90,18 @@ impl PveNodeServicesPanel {
         title_subtitle_column(msg, None::<&str>).padding(2).into()
     }
 
-    fn view_services(&self, _ctx: &Context<Self>, data: &[ServiceStatus]) -> Html {
+    fn view_services(&self, ctx: &Context<Self>, data: &[ServiceStatus]) -> Html {
         let list: Vec<ListTile> = data
             .iter()
             .map(|s| {
+                let service = s.service.clone();
                 ListTile::new()
+                    .key(s.service.clone())
+                    .interactive(true)
+                    .onclick(
+                        ctx.link()
+                            .callback(move |_| Msg::ShowActions(Some(service.clone()))),
+                    )
                     .with_child(title_subtitle_column(s.name.clone(), s.desc.clone()))
                     .with_child(service_state_icon(&s))
             })
@@ -108,7 +124,8 @@ impl Component for PveNodeServicesPanel {
             data: None,
             reload_timeout: None,
             load_guard: None,
-            // cmd_guard: None,
+            show_actions: None,
+            running_upid: None,
         }
     }
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -129,16 +146,51 @@ impl Component for PveNodeServicesPanel {
                     link.send_message(Msg::Load);
                 }));
             }
+            Msg::ShowActions(service) => self.show_actions = service,
+            Msg::StartTask(upid) => self.running_upid = Some(upid),
+            Msg::TaskFinished => self.running_upid = None,
         }
         true
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+
         render_loaded_data(&self.data, |data| {
+            let task_button = TasksListButton::new()
+                .running_upid(self.running_upid.clone())
+                .on_task_finished(ctx.link().callback(|_| Msg::TaskFinished))
+                .on_show_task_list({
+                    let navigator = ctx.link().navigator().clone().unwrap();
+                    let node = props.node.clone();
+                    move |_| {
+                        navigator.push(&crate::Route::NodeTasks {
+                            nodename: node.to_string(),
+                        });
+                    }
+                });
+
+            let dialog = self.show_actions.as_ref().and_then(|id| {
+                let service = data.iter().find(|s| &s.service == id)?;
+                Some(
+                    ServiceActionDialog::new(
+                        props.node.clone(),
+                        service.service.clone(),
+                        service.name.clone(),
+                        service.desc.clone(),
+                        service.state.clone(),
+                    )
+                    .on_close(ctx.link().callback(|_| Msg::ShowActions(None)))
+                    .on_start_task(ctx.link().callback(Msg::StartTask)),
+                )
+            });
+
             Column::new()
                 .class(pwt::css::FlexFit)
                 .with_child(self.view_service_summary(ctx, data))
+                .with_child(Container::new().padding_x(2).with_child(task_button))
                 .with_child(self.view_services(ctx, data))
+                .with_optional_child(dialog)
                 .into()
         })
     }
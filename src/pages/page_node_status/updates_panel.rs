@@ -3,17 +3,23 @@ use std::rc::Rc;
 use anyhow::Error;
 use gloo_timers::callback::Timeout;
 
+use serde_json::json;
 use yew::virtual_dom::{Key, VComp, VNode};
+use yew_router::scope_ext::RouterScopeExt;
 
 use pwt::AsyncAbortGuard;
-use pwt::widget::{Column, Container, Dialog, Fa, List};
+use pwt::widget::{Button, Column, Container, Dialog, Fa, List, Row};
 use pwt::{prelude::*, widget::ListTile};
 
 use proxmox_apt_api_types::APTUpdateInfo;
 
 use proxmox_yew_comp::layout::list_tile::title_subtitle_column;
 use proxmox_yew_comp::layout::render_loaded_data;
-use proxmox_yew_comp::{http_get, percent_encoding::percent_encode_component};
+use proxmox_yew_comp::{
+    ConsoleType, XTermJs, http_get, http_post, percent_encoding::percent_encode_component,
+};
+
+use crate::widgets::{TasksListButton, bottom_sheet, bottom_sheet_title};
 
 #[derive(Clone, PartialEq, Properties)]
 pub struct NodeUpdatesPanel {
@@ -30,6 +36,11 @@ pub enum Msg {
     Load,
     LoadResult(Result<Vec<APTUpdateInfo>, Error>),
     ShowInfo(Option<APTUpdateInfo>),
+    Refresh,
+    RefreshResult(Result<String, Error>),
+    TaskFinished((String, Option<String>)),
+    ShowChangelog(Option<String>),
+    ChangelogResult(Result<String, Error>),
 }
 
 pub struct PveNodeUpdatesPanel {
@@ -37,7 +48,11 @@ pub struct PveNodeUpdatesPanel {
     show_info: Option<APTUpdateInfo>,
     reload_timeout: Option<Timeout>,
     load_guard: Option<AsyncAbortGuard>,
-    //cmd_guard: Option<AsyncAbortGuard>,
+    cmd_guard: Option<AsyncAbortGuard>,
+    running_upid: Option<String>,
+    // package name and loaded changelog
+    changelog: Option<(String, Option<Result<String, String>>)>,
+    changelog_guard: Option<AsyncAbortGuard>,
 }
 
 impl PveNodeUpdatesPanel {
@@ -70,6 +85,74 @@ impl PveNodeUpdatesPanel {
             .min_row_height(50)
             .into()
     }
+
+    fn view_actions(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let node = props.node.clone();
+        let has_updates = matches!(&self.data, Some(Ok(list)) if !list.is_empty());
+
+        let task_button = TasksListButton::new()
+            .running_upid(self.running_upid.clone())
+            .on_task_finished(ctx.link().callback(Msg::TaskFinished))
+            .on_show_task_list({
+                let navigator = ctx.link().navigator().clone().unwrap();
+                let node = node.clone();
+                move |_| {
+                    navigator.push(&crate::Route::NodeTasks {
+                        nodename: node.to_string(),
+                    });
+                }
+            });
+
+        Column::new()
+            .padding(2)
+            .gap(2)
+            .with_child(
+                Row::new()
+                    .gap(2)
+                    .class(pwt::css::JustifyContent::SpaceBetween)
+                    .with_child(
+                        Button::new(tr!("Refresh"))
+                            .icon_class("fa fa-refresh")
+                            .disabled(self.running_upid.is_some())
+                            .on_activate(ctx.link().callback(|_| Msg::Refresh)),
+                    )
+                    .with_child(
+                        Button::new(tr!("Upgrade"))
+                            .icon_class("fa fa-arrow-circle-o-up")
+                            .disabled(!has_updates)
+                            .on_activate(move |_| {
+                                XTermJs::open_xterm_js_viewer(
+                                    ConsoleType::UpgradeShell,
+                                    &node,
+                                    true,
+                                );
+                            }),
+                    ),
+            )
+            .with_child(task_button)
+            .into()
+    }
+
+    fn view_changelog(&self, ctx: &Context<Self>) -> Option<Html> {
+        let (package, changelog) = self.changelog.as_ref()?;
+
+        let content = render_loaded_data(changelog, |text| {
+            Container::from_tag("pre")
+                .padding(2)
+                .style("white-space", "pre-wrap")
+                .style("overflow-wrap", "anywhere")
+                .with_child(text.clone())
+                .into()
+        });
+
+        let title = format!("{}: {}", tr!("Changelog"), package);
+        Some(
+            bottom_sheet(bottom_sheet_title(title), content)
+                .on_close(ctx.link().callback(|_| Msg::ShowChangelog(None)))
+                .into(),
+        )
+    }
 }
 
 impl Component for PveNodeUpdatesPanel {
@@ -83,7 +166,10 @@ impl Component for PveNodeUpdatesPanel {
             show_info: None,
             reload_timeout: None,
             load_guard: None,
-            // cmd_guard: None,
+            cmd_guard: None,
+            running_upid: None,
+            changelog: None,
+            changelog_guard: None,
         }
     }
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
@@ -110,12 +196,58 @@ impl Component for PveNodeUpdatesPanel {
             Msg::ShowInfo(info) => {
                 self.show_info = info;
             }
+            Msg::Refresh => {
+                let link = ctx.link().clone();
+                let url = format!(
+                    "/nodes/{}/apt/update",
+                    percent_encode_component(&props.node)
+                );
+                self.cmd_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_post(&url, None).await;
+                    link.send_message(Msg::RefreshResult(result));
+                }));
+            }
+            Msg::RefreshResult(result) => {
+                self.cmd_guard = None;
+                match result {
+                    Ok(upid) => self.running_upid = Some(upid),
+                    Err(err) => crate::show_failed_command_error(ctx.link(), err),
+                }
+            }
+            Msg::TaskFinished(_) => {
+                self.running_upid = None;
+                ctx.link().send_message(Msg::Load);
+            }
+            Msg::ShowChangelog(None) => {
+                self.changelog = None;
+                self.changelog_guard = None;
+            }
+            Msg::ShowChangelog(Some(package)) => {
+                let link = ctx.link().clone();
+                let url = format!(
+                    "/nodes/{}/apt/changelog",
+                    percent_encode_component(&props.node)
+                );
+                let param = json!({ "name": package });
+                self.show_info = None;
+                self.changelog = Some((package, None));
+                self.changelog_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_get(&url, Some(param)).await;
+                    link.send_message(Msg::ChangelogResult(result));
+                }));
+            }
+            Msg::ChangelogResult(result) => {
+                self.changelog_guard = None;
+                if let Some((_, changelog)) = &mut self.changelog {
+                    *changelog = Some(result.map_err(|err| err.to_string()));
+                }
+            }
         }
         true
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        render_loaded_data(&self.data, |data| {
+        let content = render_loaded_data(&self.data, |data| {
             if data.is_empty() {
                 Container::new()
                     .padding(2)
@@ -123,12 +255,22 @@ impl Component for PveNodeUpdatesPanel {
                     .into()
             } else {
                 let info = self.show_info.as_ref().map(|info| {
+                    let package = info.package.clone();
                     Dialog::new(info.package.clone())
                         .with_child(
                             title_subtitle_column(info.title.clone(), info.version.clone())
                                 .padding(2)
                                 .with_child(
                                     Container::from_tag("p").with_child(info.description.clone()),
+                                )
+                                .with_child(
+                                    Row::new().with_flex_spacer().with_child(
+                                        Button::new(tr!("Changelog"))
+                                            .icon_class("fa fa-list-alt")
+                                            .on_activate(ctx.link().callback(move |_| {
+                                                Msg::ShowChangelog(Some(package.clone()))
+                                            })),
+                                    ),
                                 ),
                         )
                         .on_close(ctx.link().callback(|_| Msg::ShowInfo(None)))
@@ -140,7 +282,14 @@ impl Component for PveNodeUpdatesPanel {
                     .with_optional_child(info)
                     .into()
             }
-        })
+        });
+
+        Column::new()
+            .class(pwt::css::FlexFit)
+            .with_child(self.view_actions(ctx))
+            .with_child(content)
+            .with_optional_child(self.view_changelog(ctx))
+            .into()
     }
 }
 
@@ -0,0 +1,318 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+use serde::Deserialize;
+use serde_json::Value;
+
+use yew::virtual_dom::{Key, VComp, VNode};
+
+use pwt::AsyncAbortGuard;
+use pwt::prelude::*;
+use pwt::widget::{Button, Column, Container, Fa, List, ListTile, Row};
+
+use proxmox_human_byte::HumanByte;
+use proxmox_yew_comp::layout::list_tile::title_subtitle_column;
+use proxmox_yew_comp::layout::render_loaded_data;
+use proxmox_yew_comp::{
+    ConsoleType, XTermJs, http_get, percent_encoding::percent_encode_component,
+};
+
+use crate::widgets::{HealthState, bottom_sheet, bottom_sheet_title, health_label, value_text};
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct NodeZfsPanel {
+    node: AttrValue,
+}
+
+impl NodeZfsPanel {
+    pub fn new(node: impl Into<AttrValue>) -> Self {
+        Self { node: node.into() }
+    }
+}
+
+// ZFS pool list entry (`GET /nodes/{node}/disks/zfs`)
+#[derive(Clone, PartialEq, Deserialize)]
+pub struct ZfsPoolInfo {
+    pub name: String,
+    #[serde(default)]
+    pub size: u64,
+    #[serde(default)]
+    pub alloc: u64,
+    #[serde(default)]
+    pub frag: u64,
+    #[serde(default)]
+    pub dedup: f64,
+    #[serde(default)]
+    pub health: String,
+}
+
+// vdev tree node (`GET /nodes/{node}/disks/zfs/{name}`)
+#[derive(Clone, PartialEq, Deserialize)]
+pub struct ZfsVdev {
+    name: String,
+    #[serde(default)]
+    state: Option<String>,
+    #[serde(default)]
+    read: Value,
+    #[serde(default)]
+    write: Value,
+    #[serde(default)]
+    cksum: Value,
+    #[serde(default)]
+    msg: Option<String>,
+    #[serde(default)]
+    children: Vec<ZfsVdev>,
+}
+
+#[derive(Clone, PartialEq, Deserialize)]
+pub struct ZfsPoolDetail {
+    #[serde(default)]
+    state: Option<String>,
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(default)]
+    action: Option<String>,
+    #[serde(default)]
+    scan: Option<String>,
+    #[serde(default)]
+    errors: Option<String>,
+    #[serde(default)]
+    children: Vec<ZfsVdev>,
+}
+
+pub enum Msg {
+    Load,
+    LoadResult(Result<Vec<ZfsPoolInfo>, Error>),
+    ShowDetail(Option<String>),
+    DetailResult(Result<ZfsPoolDetail, Error>),
+}
+
+pub struct PveNodeZfsPanel {
+    data: Option<Result<Vec<ZfsPoolInfo>, String>>,
+    load_guard: Option<AsyncAbortGuard>,
+    // pool name and loaded details
+    detail: Option<(String, Option<Result<ZfsPoolDetail, String>>)>,
+    detail_guard: Option<AsyncAbortGuard>,
+}
+
+fn zfs_health_label(health: &str) -> Container {
+    match health {
+        "ONLINE" => health_label(HealthState::Ok, health.to_string()),
+        "DEGRADED" => health_label(HealthState::Warning, health.to_string()),
+        "" => health_label(HealthState::Unknown, "-"),
+        _ => health_label(HealthState::Error, health.to_string()),
+    }
+}
+
+fn add_vdev_tiles(tiles: &mut Vec<ListTile>, vdev: &ZfsVdev, depth: usize) {
+    let counters = format!(
+        "R: {} W: {} C: {}",
+        value_text(&vdev.read),
+        value_text(&vdev.write),
+        value_text(&vdev.cksum)
+    );
+    let subtitle = match vdev.msg.as_deref() {
+        Some(msg) if !msg.is_empty() => format!("{counters} - {msg}"),
+        _ => counters,
+    };
+
+    tiles.push(
+        ListTile::new()
+            .with_child(
+                title_subtitle_column(vdev.name.clone(), subtitle)
+                    .style("padding-inline-start", format!("{}em", depth)),
+            )
+            .with_child(zfs_health_label(vdev.state.as_deref().unwrap_or(""))),
+    );
+
+    for child in &vdev.children {
+        add_vdev_tiles(tiles, child, depth + 1);
+    }
+}
+
+impl PveNodeZfsPanel {
+    fn view_pools(&self, ctx: &Context<Self>, pools: &[ZfsPoolInfo]) -> Html {
+        if pools.is_empty() {
+            return Container::new()
+                .padding(2)
+                .with_child(tr!("No ZFS pools configured."))
+                .into();
+        }
+
+        let tiles: Vec<ListTile> = pools
+            .iter()
+            .map(|pool| {
+                let usage = if pool.size > 0 {
+                    pool.alloc as f64 * 100.0 / pool.size as f64
+                } else {
+                    0.0
+                };
+                let subtitle = format!(
+                    "{} {} / {} ({usage:.1}%), {}: {}%, {}: {:.2}",
+                    tr!("Allocated"),
+                    HumanByte::new_binary(pool.alloc as f64),
+                    HumanByte::new_binary(pool.size as f64),
+                    tr!("Fragmentation"),
+                    pool.frag,
+                    tr!("Dedup"),
+                    pool.dedup,
+                );
+                let name = pool.name.clone();
+                ListTile::new()
+                    .key(Key::from(pool.name.clone()))
+                    .interactive(true)
+                    .onclick(
+                        ctx.link()
+                            .callback(move |_| Msg::ShowDetail(Some(name.clone()))),
+                    )
+                    .with_child(Fa::new("database").fixed_width().large().padding_end(1))
+                    .with_child(title_subtitle_column(pool.name.clone(), subtitle))
+                    .with_child(zfs_health_label(&pool.health))
+            })
+            .collect();
+
+        List::from_tiles(tiles)
+            .class(pwt::css::FlexFit)
+            .grid_template_columns("auto 1fr auto")
+            .min_row_height(50)
+            .into()
+    }
+
+    fn view_detail_data(&self, ctx: &Context<Self>, pool: &str, detail: &ZfsPoolDetail) -> Html {
+        let props = ctx.props();
+
+        let mut column = Column::new().gap(2);
+
+        let mut info = Column::new().padding_x(2).gap(1);
+        for (label, value) in [
+            (tr!("State"), &detail.state),
+            (tr!("Status"), &detail.status),
+            (tr!("Action"), &detail.action),
+            (tr!("Scan"), &detail.scan),
+            (tr!("Errors"), &detail.errors),
+        ] {
+            if let Some(value) = value.as_deref().filter(|v| !v.is_empty()) {
+                info.add_child(title_subtitle_column(label, value.to_string()));
+            }
+        }
+        column.add_child(info);
+
+        // There is no API call to start a scrub, so provide a shell to run it.
+        let node = props.node.clone();
+        column.add_child(
+            Row::new()
+                .padding_x(2)
+                .gap(2)
+                .class(pwt::css::AlignItems::Center)
+                .with_child(
+                    Container::new()
+                        .class("pwt-font-size-body-small")
+                        .with_child(tr!("Run 'zpool scrub {0}' in the node shell.", pool)),
+                )
+                .with_flex_spacer()
+                .with_child(
+                    Button::new(tr!("Shell"))
+                        .icon_class("fa fa-terminal")
+                        .on_activate(move |_| {
+                            XTermJs::open_xterm_js_viewer(ConsoleType::LoginShell, &node, true);
+                        }),
+                ),
+        );
+
+        let mut tiles = Vec::new();
+        for vdev in &detail.children {
+            add_vdev_tiles(&mut tiles, vdev, 0);
+        }
+        if !tiles.is_empty() {
+            column.add_child(List::from_tiles(tiles).grid_template_columns("1fr auto"));
+        }
+
+        column.into()
+    }
+
+    fn view_detail(&self, ctx: &Context<Self>) -> Option<Html> {
+        let (pool, detail) = self.detail.as_ref()?;
+
+        let content = render_loaded_data(detail, |detail| self.view_detail_data(ctx, pool, detail));
+
+        Some(
+            bottom_sheet(bottom_sheet_title(format!("ZFS Pool {pool}")), content)
+                .on_close(ctx.link().callback(|_| Msg::ShowDetail(None)))
+                .into(),
+        )
+    }
+}
+
+impl Component for PveNodeZfsPanel {
+    type Message = Msg;
+    type Properties = NodeZfsPanel;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link().send_message(Msg::Load);
+        Self {
+            data: None,
+            load_guard: None,
+            detail: None,
+            detail_guard: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let props = ctx.props();
+        match msg {
+            Msg::Load => {
+                let link = ctx.link().clone();
+                let url = format!("/nodes/{}/disks/zfs", percent_encode_component(&props.node));
+                self.load_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_get(&url, None).await;
+                    link.send_message(Msg::LoadResult(result));
+                }));
+            }
+            Msg::LoadResult(result) => {
+                self.load_guard = None;
+                self.data = Some(result.map_err(|err| err.to_string()));
+            }
+            Msg::ShowDetail(None) => {
+                self.detail = None;
+                self.detail_guard = None;
+            }
+            Msg::ShowDetail(Some(pool)) => {
+                let link = ctx.link().clone();
+                let url = format!(
+                    "/nodes/{}/disks/zfs/{}",
+                    percent_encode_component(&props.node),
+                    percent_encode_component(&pool)
+                );
+                self.detail = Some((pool, None));
+                self.detail_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_get(&url, None).await;
+                    link.send_message(Msg::DetailResult(result));
+                }));
+            }
+            Msg::DetailResult(result) => {
+                self.detail_guard = None;
+                if let Some((_, detail)) = &mut self.detail {
+                    *detail = Some(result.map_err(|err| err.to_string()));
+                }
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let content = render_loaded_data(&self.data, |pools| self.view_pools(ctx, pools));
+
+        Column::new()
+            .class(pwt::css::FlexFit)
+            .with_child(content)
+            .with_optional_child(self.view_detail(ctx))
+            .into()
+    }
+}
+
+impl From<NodeZfsPanel> for VNode {
+    fn from(props: NodeZfsPanel) -> Self {
+        let comp = VComp::new::<PveNodeZfsPanel>(Rc::new(props), None);
+        VNode::from(comp)
+    }
+}
@@ -46,16 +46,20 @@ pub fn PvePageNodeTasks(props: &PageNodeTasks) -> Html {
                     percent_encode_component(&props.nodename)
                 )),
         )
-        .with_child(TasksPanel::new(base_url.clone()).on_show_task({
-            let nodename = props.nodename.to_string();
-            move |(upid, endtime): (String, Option<i64>)| {
-                navigator.push(&crate::Route::NodeTaskStatus {
-                    nodename: nodename.clone(),
-                    upid,
-                    endtime: endtime.unwrap_or(0),
-                });
-            }
-        }))
+        .with_child(
+            TasksPanel::new(base_url.clone())
+                .filter_state_id("pve-node-task-filter")
+                .on_show_task({
+                    let nodename = props.nodename.to_string();
+                    move |(upid, endtime): (String, Option<i64>)| {
+                        navigator.push(&crate::Route::NodeTaskStatus {
+                            nodename: nodename.clone(),
+                            upid,
+                            endtime: endtime.unwrap_or(0),
+                        });
+                    }
+                }),
+        )
         .into()
 }
 
@@ -21,13 +21,15 @@ use proxmox_yew_comp::layout::card::standard_card;
 use proxmox_yew_comp::layout::list_tile::{icon_list_tile, list_tile_usage, standard_list_tile};
 use proxmox_yew_comp::layout::render_loaded_data;
 use proxmox_yew_comp::utils::lookup_task_description;
-use proxmox_yew_comp::{
-    ConsoleType, XTermJs, http_get, http_post, percent_encoding::percent_encode_component,
-};
+use proxmox_yew_comp::{http_get, http_post, percent_encoding::percent_encode_component};
 
 use pve_api_types::{IsRunning, QemuStatus};
 
-use crate::widgets::TasksListButton;
+use super::QemuGuestAgentCard;
+use crate::widgets::{
+    GuestBackupJobsCard, GuestCloneDialog, GuestHaCard, GuestMigrateDialog, GuestType,
+    QemuConsoleButton, RrdGraphPanel, TasksListButton, guest_base_url,
+};
 
 #[derive(Clone, PartialEq, Properties)]
 pub struct QemuDashboardPanel {
@@ -51,6 +53,12 @@ pub struct PveQemuDashboardPanel {
     cmd_guard: Option<AsyncAbortGuard>,
     running_upid: Option<String>,
     confirm_vm_command: Option<(String, String, Option<Value>)>,
+    show_migrate_dialog: bool,
+    show_clone_dialog: bool,
+    // running migration task (upid, target node)
+    migrate_task: Option<(String, String)>,
+    // requested HA state, `None` if not HA managed
+    ha_state: Option<String>,
 }
 
 #[derive(Copy, Clone, PartialEq)]
@@ -61,6 +69,7 @@ pub enum ConfirmableCommands {
     Suspend,
     Stop,
     Shutdown,
+    Template,
 }
 
 pub enum Msg {
@@ -69,6 +78,12 @@ pub enum Msg {
     CommandResult(Result<String, Error>),
     StartCommand(String),
     Confirm(ConfirmableCommands),
+    ShowMigrateDialog(bool),
+    ShowCloneDialog(bool),
+    TemplateResult(Result<Value, Error>),
+    MigrateStarted((String, String)),
+    TaskFinished((String, Option<String>)),
+    HaState(Option<String>),
     CloseDialog,
     VmCommand((String, Option<Value>)),
 }
@@ -100,6 +115,9 @@ fn format_guest_task_confirmation(
         ConfirmableCommands::Reboot => "qmreboot",
         ConfirmableCommands::Shutdown => "qmshutdown",
         ConfirmableCommands::Stop => "qmstop",
+        ConfirmableCommands::Template => {
+            return tr!("Convert VM {0} to a template?", vmid);
+        }
     };
     let vm_name_or_id = match guest_name {
         Some(name) => name.to_string(),
@@ -111,8 +129,20 @@ fn format_guest_task_confirmation(
 impl PveQemuDashboardPanel {
     fn vm_command(&mut self, ctx: &Context<Self>, cmd: &str, param: Option<Value>) {
         let props = ctx.props();
-        let url = get_status_url(&props.node, props.vmid, cmd);
         let link = ctx.link().clone();
+        if cmd == "template" {
+            // not a status command, and the result is not always a task
+            let url = format!(
+                "{}/template",
+                guest_base_url(&props.node, GuestType::Qemu, props.vmid)
+            );
+            self.cmd_guard = Some(AsyncAbortGuard::spawn(async move {
+                let result = http_post(&url, param.clone()).await;
+                link.send_message(Msg::TemplateResult(result));
+            }));
+            return;
+        }
+        let url = get_status_url(&props.node, props.vmid, cmd);
         self.cmd_guard = Some(AsyncAbortGuard::spawn(async move {
             let result = http_post(&url, param.clone()).await;
             link.send_message(Msg::CommandResult(result));
@@ -134,6 +164,7 @@ impl PveQemuDashboardPanel {
             ConfirmableCommands::Reboot => ("reboot", None),
             ConfirmableCommands::Shutdown => ("shutdown", None),
             ConfirmableCommands::Stop => ("stop", None),
+            ConfirmableCommands::Template => ("template", None),
         };
 
         self.confirm_vm_command = Some((command_str.into(), confirm_msg, param));
@@ -153,6 +184,15 @@ impl PveQemuDashboardPanel {
             data.qmpstatus.clone().unwrap_or(data.status.to_string()),
         ));
 
+        if let Some(ha_state) = &self.ha_state {
+            tiles.push(icon_list_tile(
+                Fa::new("heartbeat").class("pwt-color-primary"),
+                tr!("HA managed"),
+                (),
+                ha_state.clone(),
+            ));
+        }
+
         if let Some(Ok(data)) = &self.data {
             if data.status == IsRunning::Running {
                 if let (Some(cpu), Some(maxcpu)) = (data.cpu, data.cpus) {
@@ -201,11 +241,10 @@ impl PveQemuDashboardPanel {
 
     fn view_actions(&self, ctx: &Context<Self>, data: &QemuStatus) -> Html {
         let props = ctx.props();
-        let vmid = props.vmid;
-        let node_name = props.node.clone();
 
         let qmpstatus = data.qmpstatus.as_deref().unwrap_or("");
         let running = data.status == IsRunning::Running;
+        let template = data.template.unwrap_or(false);
 
         let menu = Menu::new()
             .with_item(
@@ -239,6 +278,18 @@ impl PveQemuDashboardPanel {
                     ctx.link()
                         .callback(|_| Msg::Confirm(ConfirmableCommands::Reset)),
                 ),
+            )
+            .with_item(
+                MenuItem::new(tr!("Clone"))
+                    .on_select(ctx.link().callback(|_| Msg::ShowCloneDialog(true))),
+            )
+            .with_item(
+                MenuItem::new(tr!("Convert to template"))
+                    .disabled(running || template)
+                    .on_select(
+                        ctx.link()
+                            .callback(|_| Msg::Confirm(ConfirmableCommands::Template)),
+                    ),
             );
 
         let shutdown = SplitButton::new(tr!("Shutdown"))
@@ -251,11 +302,9 @@ impl PveQemuDashboardPanel {
 
         let resume = ["prelaunch", "paused", "suspended"].contains(&qmpstatus);
 
-        let row = Row::new()
-            .padding_y(1)
-            .gap(2)
-            .class(pwt::css::JustifyContent::SpaceBetween)
-            .with_child(if resume {
+        // templates cannot be started
+        let start = (!template).then(|| {
+            if resume {
                 Button::new(tr!("Resume")).on_activate(
                     ctx.link()
                         .callback(|_| Msg::VmCommand(("resume".into(), None))),
@@ -265,18 +314,21 @@ impl PveQemuDashboardPanel {
                     ctx.link()
                         .callback(|_| Msg::VmCommand(("start".into(), None))),
                 )
-            })
+            }
+        });
+
+        let row = Row::new()
+            .padding_y(1)
+            .gap(2)
+            .class(pwt::css::JustifyContent::SpaceBetween)
+            .with_optional_child(start)
             .with_child(shutdown)
+            .with_child(QemuConsoleButton::new(props.node.clone(), props.vmid))
             .with_child(
-                Button::new(tr!("Console"))
-                    .icon_class("fa fa-terminal")
-                    .on_activate(move |_| {
-                        XTermJs::open_xterm_js_viewer(
-                            ConsoleType::KVM(vmid.into()),
-                            &node_name,
-                            true,
-                        );
-                    }),
+                Button::new(tr!("Migrate"))
+                    .icon_class("fa fa-paper-plane")
+                    .disabled(self.migrate_task.is_some())
+                    .on_activate(ctx.link().callback(|_| Msg::ShowMigrateDialog(true))),
             );
 
         MiniScroll::new(row)
@@ -288,6 +340,7 @@ impl PveQemuDashboardPanel {
     fn task_button(&self, ctx: &Context<Self>) -> Html {
         TasksListButton::new()
             .running_upid(self.running_upid.clone())
+            .on_task_finished(ctx.link().callback(Msg::TaskFinished))
             .on_show_task_list({
                 let navigator = ctx.link().navigator().clone().unwrap();
                 let props = ctx.props();
@@ -317,6 +370,10 @@ impl Component for PveQemuDashboardPanel {
             cmd_guard: None,
             running_upid: None,
             confirm_vm_command: None,
+            show_migrate_dialog: false,
+            show_clone_dialog: false,
+            migrate_task: None,
+            ha_state: None,
         }
     }
 
@@ -368,6 +425,39 @@ impl Component for PveQemuDashboardPanel {
                 }
             },
             Msg::Confirm(command) => self.confirmed_vm_command(ctx, command),
+            Msg::ShowMigrateDialog(show) => self.show_migrate_dialog = show,
+            Msg::ShowCloneDialog(show) => self.show_clone_dialog = show,
+            Msg::TemplateResult(result) => match result {
+                // newer versions run the conversion as task
+                Ok(Value::String(upid)) => self.running_upid = Some(upid),
+                Ok(_) => ctx.link().send_message(Msg::Load),
+                Err(err) => crate::show_failed_command_error(ctx.link(), err),
+            },
+            Msg::MigrateStarted((target, upid)) => {
+                self.running_upid = Some(upid.clone());
+                self.migrate_task = Some((upid, target));
+            }
+            Msg::HaState(state) => self.ha_state = state,
+            Msg::TaskFinished((upid, exit_status)) => {
+                let is_migration =
+                    matches!(&self.migrate_task, Some((migrate_upid, _)) if *migrate_upid == upid);
+                if !is_migration {
+                    return true;
+                }
+                let success = matches!(
+                    exit_status.as_deref(),
+                    Some(status) if status == "OK" || status.starts_with("WARNINGS")
+                );
+                if let (Some((_, target)), true) = (self.migrate_task.take(), success) {
+                    // the guest now lives on the target node, so do not go back to the old one
+                    if let Some(navigator) = ctx.link().navigator() {
+                        navigator.replace(&crate::Route::Qemu {
+                            vmid: props.vmid,
+                            nodename: target,
+                        });
+                    }
+                }
+            }
         }
         true
     }
@@ -399,13 +489,44 @@ impl Component for PveQemuDashboardPanel {
                 .with_child(self.view_status(ctx, data))
                 .with_child(self.view_actions(ctx, data))
                 .with_child(self.task_button(ctx))
+                .with_child(QemuGuestAgentCard::new(
+                    props.node.clone(),
+                    props.vmid,
+                    data.status == IsRunning::Running,
+                ))
                 .with_child(
                     QemuHardwarePanel::new(props.node.clone(), props.vmid)
                         .mobile(true)
                         .readonly(true)
                         .on_start_command(ctx.link().callback(Msg::StartCommand)),
                 )
+                .with_child(
+                    GuestHaCard::new(GuestType::Qemu, props.vmid)
+                        .on_load(ctx.link().callback(Msg::HaState)),
+                )
+                .with_child(GuestBackupJobsCard::new(
+                    props.node.clone(),
+                    GuestType::Qemu,
+                    props.vmid,
+                ))
+                .with_child(RrdGraphPanel::guest(
+                    props.node.clone(),
+                    GuestType::Qemu,
+                    props.vmid,
+                ))
                 .with_optional_child(confirm_dialog)
+                .with_optional_child(self.show_migrate_dialog.then(|| {
+                    GuestMigrateDialog::new(props.node.clone(), GuestType::Qemu, props.vmid)
+                        .running(data.status == IsRunning::Running)
+                        .on_close(ctx.link().callback(|_| Msg::ShowMigrateDialog(false)))
+                        .on_migrate(ctx.link().callback(Msg::MigrateStarted))
+                }))
+                .with_optional_child(self.show_clone_dialog.then(|| {
+                    GuestCloneDialog::new(props.node.clone(), GuestType::Qemu, props.vmid)
+                        .template(data.template.unwrap_or(false))
+                        .on_close(ctx.link().callback(|_| Msg::ShowCloneDialog(false)))
+                        .on_clone(ctx.link().callback(Msg::StartCommand))
+                }))
                 .into()
         })
     }
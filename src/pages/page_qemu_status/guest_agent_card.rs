@@ -0,0 +1,349 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+use gloo_timers::callback::Timeout;
+use serde_json::Value;
+
+use yew::prelude::*;
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::AsyncAbortGuard;
+use pwt::prelude::*;
+use pwt::widget::{Container, Fa, List, ListTile};
+
+use proxmox_human_byte::HumanByte;
+use proxmox_yew_comp::http_get;
+use proxmox_yew_comp::layout::card::standard_card;
+use proxmox_yew_comp::layout::list_tile::{icon_list_tile, list_tile_usage};
+use proxmox_yew_comp::layout::render_loaded_data;
+
+use crate::widgets::{GuestType, guest_base_url, parse_property_string};
+
+/// Shows information reported by the QEMU guest agent.
+///
+/// Network interfaces are required, all other information is optional
+/// because older agents (or Windows guests) do not implement all commands.
+#[derive(Clone, PartialEq, Properties)]
+pub struct QemuGuestAgentCard {
+    vmid: u32,
+    node: AttrValue,
+    running: bool,
+}
+
+impl QemuGuestAgentCard {
+    pub fn new(node: impl Into<AttrValue>, vmid: u32, running: bool) -> Self {
+        Self {
+            node: node.into(),
+            vmid,
+            running,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct AgentInterface {
+    name: String,
+    addresses: Vec<String>,
+}
+
+#[derive(Clone, PartialEq)]
+struct AgentFilesystem {
+    mountpoint: String,
+    fs_type: String,
+    used: Option<u64>,
+    total: Option<u64>,
+}
+
+#[derive(Clone, PartialEq)]
+pub struct AgentInfo {
+    hostname: Option<String>,
+    os_name: Option<String>,
+    kernel: Option<String>,
+    interfaces: Vec<AgentInterface>,
+    filesystems: Vec<AgentFilesystem>,
+}
+
+pub enum Msg {
+    Load,
+    /// `None` if the agent is disabled in the VM configuration
+    LoadResult(Result<Option<AgentInfo>, Error>),
+}
+
+pub struct PveQemuGuestAgentCard {
+    data: Option<Result<Option<AgentInfo>, String>>,
+    load_guard: Option<AsyncAbortGuard>,
+    retry_timeout: Option<Timeout>,
+}
+
+// Delay (in ms) before querying the agent again if it is not running
+const RETRY_DELAY: u32 = 5000;
+
+// Errors returned while the agent is not (yet) available in the guest.
+fn agent_not_running(err: &str) -> bool {
+    err.contains("guest agent is not running") || err.contains("got timeout")
+}
+
+fn agent_enabled(config: &Value) -> bool {
+    let agent = match &config["agent"] {
+        Value::String(text) => text.clone(),
+        Value::Number(number) => number.to_string(),
+        _ => return false,
+    };
+    parse_property_string(&agent, "enabled")
+        .iter()
+        .any(|(key, value)| {
+            key == "enabled" && matches!(value.as_str(), "1" | "on" | "yes" | "true")
+        })
+}
+
+async fn agent_command(base_url: &str, command: &str) -> Result<Value, Error> {
+    let url = format!("{base_url}/agent/{command}");
+    let mut data: Value = http_get(&url, None).await?;
+    Ok(data["result"].take())
+}
+
+async fn load_agent_info(base_url: &str) -> Result<Option<AgentInfo>, Error> {
+    let config: Value = http_get(&format!("{base_url}/config"), None).await?;
+    if !agent_enabled(&config) {
+        return Ok(None);
+    }
+
+    let interfaces = agent_command(base_url, "network-get-interfaces").await?;
+
+    let hostname = agent_command(base_url, "get-host-name")
+        .await
+        .ok()
+        .and_then(|data| data["host-name"].as_str().map(String::from));
+
+    let osinfo = agent_command(base_url, "get-osinfo")
+        .await
+        .unwrap_or_default();
+    let os_name = osinfo["pretty-name"]
+        .as_str()
+        .or(osinfo["name"].as_str())
+        .map(String::from);
+    let kernel = osinfo["kernel-release"].as_str().map(String::from);
+
+    let filesystems = agent_command(base_url, "get-fsinfo")
+        .await
+        .unwrap_or_default();
+
+    Ok(Some(AgentInfo {
+        hostname,
+        os_name,
+        kernel,
+        interfaces: parse_interfaces(&interfaces),
+        filesystems: parse_filesystems(&filesystems),
+    }))
+}
+
+fn parse_interfaces(data: &Value) -> Vec<AgentInterface> {
+    let Some(list) = data.as_array() else {
+        return Vec::new();
+    };
+
+    list.iter()
+        .filter_map(|item| {
+            let name = item["name"].as_str()?;
+            if name == "lo" || name.starts_with("Loopback") {
+                return None;
+            }
+            let addresses: Vec<String> = item["ip-addresses"]
+                .as_array()
+                .map(|list| {
+                    list.iter()
+                        .filter_map(|ip| {
+                            let address = ip["ip-address"].as_str()?;
+                            Some(match ip["prefix"].as_u64() {
+                                Some(prefix) => format!("{address}/{prefix}"),
+                                None => address.to_string(),
+                            })
+                        })
+                        .collect()
+                })
+                .unwrap_or_default();
+            Some(AgentInterface {
+                name: name.to_string(),
+                addresses,
+            })
+        })
+        .collect()
+}
+
+fn parse_filesystems(data: &Value) -> Vec<AgentFilesystem> {
+    let Some(list) = data.as_array() else {
+        return Vec::new();
+    };
+
+    let mut filesystems: Vec<AgentFilesystem> = list
+        .iter()
+        .filter_map(|item| {
+            let mountpoint = item["mountpoint"].as_str()?;
+            let fs_type = item["type"].as_str().unwrap_or("").to_string();
+            // skip pseudo file systems like snaps or bind mounts of the same disk
+            if fs_type == "squashfs" || fs_type == "autofs" {
+                return None;
+            }
+            Some(AgentFilesystem {
+                mountpoint: mountpoint.to_string(),
+                fs_type,
+                used: item["used-bytes"].as_u64(),
+                total: item["total-bytes"].as_u64(),
+            })
+        })
+        .collect();
+
+    filesystems.sort_by(|a, b| a.mountpoint.cmp(&b.mountpoint));
+    filesystems.dedup_by(|a, b| a.mountpoint == b.mountpoint);
+    filesystems
+}
+
+impl PveQemuGuestAgentCard {
+    fn view_info(&self, info: &AgentInfo) -> Html {
+        let mut tiles: Vec<ListTile> = Vec::new();
+
+        if info.hostname.is_some() || info.os_name.is_some() {
+            let os = match (&info.os_name, &info.kernel) {
+                (Some(name), Some(kernel)) => format!("{name} ({kernel})"),
+                (Some(name), None) => name.clone(),
+                (None, _) => String::from("-"),
+            };
+            tiles.push(icon_list_tile(
+                Fa::new("info"),
+                info.hostname.clone().unwrap_or_else(|| tr!("Hostname")),
+                os,
+                (),
+            ));
+        }
+
+        for interface in &info.interfaces {
+            let addresses = if interface.addresses.is_empty() {
+                tr!("No IP address")
+            } else {
+                interface.addresses.join(", ")
+            };
+            tiles.push(icon_list_tile(
+                Fa::new("exchange"),
+                interface.name.clone(),
+                addresses,
+                (),
+            ));
+        }
+
+        for fs in &info.filesystems {
+            let mut tile = icon_list_tile(
+                Fa::new("hdd-o"),
+                fs.mountpoint.clone(),
+                fs.fs_type.clone(),
+                (),
+            );
+            if let (Some(used), Some(total)) = (fs.used, fs.total) {
+                let percentage = if total == 0 {
+                    0.0
+                } else {
+                    (used as f32) / (total as f32)
+                };
+                tile.add_child(list_tile_usage(
+                    HumanByte::new_binary(used as f64).to_string(),
+                    HumanByte::new_binary(total as f64).to_string(),
+                    percentage,
+                ));
+            }
+            tiles.push(tile);
+        }
+
+        List::from_tiles(tiles)
+            .grid_template_columns("auto 1fr auto")
+            .into()
+    }
+}
+
+impl Component for PveQemuGuestAgentCard {
+    type Message = Msg;
+    type Properties = QemuGuestAgentCard;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link().send_message(Msg::Load);
+        Self {
+            data: None,
+            load_guard: None,
+            retry_timeout: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let props = ctx.props();
+        match msg {
+            Msg::Load => {
+                self.retry_timeout = None;
+                if !props.running {
+                    self.load_guard = None;
+                    self.data = None;
+                    return true;
+                }
+                let base_url = guest_base_url(&props.node, GuestType::Qemu, props.vmid);
+                let link = ctx.link().clone();
+                self.load_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = load_agent_info(&base_url).await;
+                    link.send_message(Msg::LoadResult(result));
+                }));
+            }
+            Msg::LoadResult(result) => {
+                self.load_guard = None;
+                self.data = Some(result.map_err(|err| err.to_string()));
+                // the agent needs some time to come up after the guest started
+                if matches!(&self.data, Some(Err(err)) if agent_not_running(err)) {
+                    let link = ctx.link().clone();
+                    self.retry_timeout = Some(Timeout::new(RETRY_DELAY, move || {
+                        link.send_message(Msg::Load);
+                    }));
+                }
+            }
+        }
+        true
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        if ctx.props().running != old_props.running {
+            ctx.link().send_message(Msg::Load);
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+
+        let content: Html = if !props.running {
+            Container::new()
+                .padding(2)
+                .with_child(tr!("Guest not running"))
+                .into()
+        } else {
+            match &self.data {
+                Some(Err(err)) if agent_not_running(err) => Container::new()
+                    .padding(2)
+                    .with_child(tr!("Guest Agent not running"))
+                    .into(),
+                Some(Ok(None)) => Container::new()
+                    .padding(2)
+                    .with_child(tr!("Guest Agent not enabled"))
+                    .into(),
+                _ => render_loaded_data(&self.data, |info| match info {
+                    Some(info) => self.view_info(info),
+                    None => html! {},
+                }),
+            }
+        };
+
+        standard_card(tr!("Guest Agent"), (), ())
+            .with_child(content)
+            .into()
+    }
+}
+
+impl From<QemuGuestAgentCard> for VNode {
+    fn from(props: QemuGuestAgentCard) -> Self {
+        let comp = VComp::new::<PveQemuGuestAgentCard>(Rc::new(props), None);
+        VNode::from(comp)
+    }
+}
@@ -0,0 +1,529 @@
+use std::collections::HashSet;
+use std::rc::Rc;
+
+use anyhow::Error;
+use serde_json::Value;
+
+use yew::virtual_dom::{VComp, VNode};
+use yew_router::scope_ext::RouterScopeExt;
+
+use pwt::AsyncAbortGuard;
+use pwt::prelude::*;
+use pwt::props::SubmitCallback;
+use pwt::widget::form::{Checkbox, Combobox, Number};
+use pwt::widget::{Column, Container};
+
+use proxmox_human_byte::HumanByte;
+use proxmox_yew_comp::http_put;
+use proxmox_yew_comp::layout::mobile_form::label_field;
+
+use crate::widgets::{
+    EditableProperty, GuestDiskActionDialog, GuestType, PendingPropertyList, PropertyEditorState,
+    TasksListButton, field_bool, field_string, format_property_string, guest_base_url,
+    indexed_keys, load_bridge_list, load_iso_list, parse_property_string, set_property_value,
+};
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct QemuHardwarePendingPanel {
+    vmid: u32,
+    node: AttrValue,
+}
+
+impl QemuHardwarePendingPanel {
+    pub fn new(node: impl Into<AttrValue>, vmid: u32) -> Self {
+        Self {
+            node: node.into(),
+            vmid,
+        }
+    }
+}
+
+pub enum Msg {
+    LoadResult(Result<(Vec<String>, Vec<String>), Error>),
+    ConfigKeys(HashSet<String>),
+    ShowDiskAction(Option<String>),
+    StartTask(String),
+}
+
+pub struct PveQemuHardwarePendingPanel {
+    properties: Rc<Vec<EditableProperty>>,
+    // keys of the loaded configuration, only those get a property
+    keys: HashSet<String>,
+    iso_list: Rc<Vec<String>>,
+    bridges: Rc<Vec<String>>,
+    load_guard: Option<AsyncAbortGuard>,
+    disk_action: Option<String>,
+    running_upid: Option<String>,
+}
+
+const DRIVE_BUSSES: &[(&str, usize)] = &[("ide", 4), ("sata", 6), ("scsi", 31), ("virtio", 16)];
+
+fn value_as_u64(value: &Value) -> Option<u64> {
+    match value {
+        Value::Number(n) => n.as_u64(),
+        Value::String(text) => text.parse().ok(),
+        _ => None,
+    }
+}
+
+// newer versions use a property string: `[current=]<integer>`
+fn memory_mib(value: &Value) -> u64 {
+    match value {
+        Value::String(text) => text
+            .split(',')
+            .next()
+            .map(|current| current.trim_start_matches("current="))
+            .and_then(|current| current.parse().ok()),
+        value => value_as_u64(value),
+    }
+    .unwrap_or(512)
+}
+
+fn render_mib(mib: u64) -> String {
+    HumanByte::new_binary((mib * 1024 * 1024) as f64).to_string()
+}
+
+fn is_cdrom(drive: &str) -> bool {
+    parse_property_string(drive, "file")
+        .iter()
+        .any(|(key, value)| key == "media" && value == "cdrom")
+}
+
+fn memory_property() -> EditableProperty {
+    EditableProperty::new("memory", tr!("Memory"))
+        .required(true)
+        .placeholder(render_mib(512))
+        .revert_keys(Rc::new(vec!["memory".into(), "balloon".into()]))
+        .renderer(|_name, value, record| {
+            let memory = memory_mib(value);
+            let text = render_mib(memory);
+            match value_as_u64(&record["balloon"]) {
+                Some(0) => format!("{text} [{}]", tr!("Ballooning disabled")),
+                Some(balloon) if balloon != memory => {
+                    format!("{}/{text} [{}]", render_mib(balloon), tr!("Ballooning"))
+                }
+                _ => text,
+            }
+            .into()
+        })
+        .load_hook(Callback::from(|mut record: Value| {
+            record["memory"] = memory_mib(&record["memory"]).into();
+            Ok(record)
+        }))
+        .render_input_panel(|_| {
+            Column::new()
+                .gap(2)
+                .with_child(label_field(
+                    tr!("Memory") + " (MiB)",
+                    Number::<u64>::new().name("memory").required(true).min(16),
+                    true,
+                ))
+                .with_child(label_field(
+                    tr!("Minimum memory") + " (MiB)",
+                    Number::<u64>::new().name("balloon").min(0),
+                    true,
+                ))
+                .into()
+        })
+}
+
+fn processor_property() -> EditableProperty {
+    EditableProperty::new("cores", tr!("Processors"))
+        .required(true)
+        .placeholder(tr!("1 (1 sockets, 1 cores)"))
+        .revert_keys(Rc::new(vec!["cores".into(), "sockets".into()]))
+        .renderer(|_name, value, record| {
+            let cores = value_as_u64(value).unwrap_or(1);
+            let sockets = value_as_u64(&record["sockets"]).unwrap_or(1);
+            let mut text = tr!(
+                "{0} ({1} sockets, {2} cores)",
+                cores * sockets,
+                sockets,
+                cores
+            );
+            if let Some(cpu) = record["cpu"].as_str() {
+                text += &format!(" [{cpu}]");
+            }
+            text.into()
+        })
+        .render_input_panel(|_| {
+            Column::new()
+                .gap(2)
+                .with_child(label_field(
+                    tr!("Sockets"),
+                    Number::<u64>::new()
+                        .name("sockets")
+                        .required(true)
+                        .min(1)
+                        .max(4)
+                        .default(1),
+                    true,
+                ))
+                .with_child(label_field(
+                    tr!("Cores"),
+                    Number::<u64>::new()
+                        .name("cores")
+                        .required(true)
+                        .min(1)
+                        .default(1),
+                    true,
+                ))
+                .into()
+        })
+}
+
+fn drive_property(
+    name: &str,
+    iso_list: Rc<Vec<String>>,
+    on_disk_action: Callback<String>,
+) -> EditableProperty {
+    let property = EditableProperty::new(
+        AttrValue::from(name.to_string()),
+        format!("{} ({name})", tr!("Drive")),
+    )
+    .renderer(|_name, value, _record| {
+        let text = value.as_str().unwrap_or("");
+        if is_cdrom(text) {
+            format!("{}: {text}", tr!("CD/DVD Drive")).into()
+        } else {
+            text.into()
+        }
+    })
+    .action_icon("fa fa-ellipsis-v")
+    .on_action({
+        let name = name.to_string();
+        move |_| on_disk_action.emit(name.clone())
+    });
+
+    // VirtIO block devices cannot be CD/DVD drives
+    if name.starts_with("virtio") {
+        return property;
+    }
+
+    let name = name.to_string();
+    property
+        .load_hook({
+            let name = name.clone();
+            Callback::from(move |mut record: Value| {
+                let drive = record[&name].as_str().unwrap_or("").to_string();
+                if is_cdrom(&drive) {
+                    let file = parse_property_string(&drive, "file")
+                        .into_iter()
+                        .find(|(key, _)| key == "file")
+                        .map(|(_, file)| file)
+                        .unwrap_or_default();
+                    record["_no_media"] = (file == "none").into();
+                    if file != "none" {
+                        record["_iso"] = file.into();
+                    }
+                }
+                Ok(record)
+            })
+        })
+        .submit_hook({
+            let name = name.clone();
+            Callback::from(move |state: PropertyEditorState| {
+                let drive = state.record[&name].as_str().unwrap_or("");
+                let mut list = parse_property_string(drive, "file");
+                let file = if field_bool(&state.form_ctx, "_no_media") {
+                    Some(String::from("none"))
+                } else {
+                    field_string(&state.form_ctx, "_iso")
+                };
+                set_property_value(&mut list, "file", file);
+
+                let mut data = state.get_submit_data();
+                if let Value::Object(map) = &mut data {
+                    map.remove("_iso");
+                    map.remove("_no_media");
+                }
+                data[&name] = format_property_string(&list, Some("file")).into();
+                Ok(data)
+            })
+        })
+        .render_input_panel(move |state: PropertyEditorState| {
+            if !is_cdrom(state.record[&name].as_str().unwrap_or("")) {
+                return Container::new()
+                    .with_child(tr!("Only the media of CD/DVD drives can be changed here."))
+                    .into();
+            }
+            let no_media = field_bool(&state.form_ctx, "_no_media");
+            let iso_selector = iso_list
+                .iter()
+                .fold(Combobox::new(), |combo, iso| combo.with_item(iso.clone()))
+                .name("_iso")
+                .disabled(no_media)
+                .required(!no_media)
+                .force_selection(true);
+
+            Column::new()
+                .gap(2)
+                .with_child(
+                    Checkbox::new()
+                        .name("_no_media")
+                        .box_label(tr!("Do not use any media")),
+                )
+                .with_child(label_field(tr!("ISO Image"), iso_selector, true))
+                .into()
+        })
+}
+
+fn network_property(name: &str, bridges: Rc<Vec<String>>) -> EditableProperty {
+    let name = name.to_string();
+    EditableProperty::new(
+        AttrValue::from(name.clone()),
+        format!("{} ({name})", tr!("Network Device")),
+    )
+    .load_hook({
+        let name = name.clone();
+        Callback::from(move |mut record: Value| {
+            let list = parse_property_string(record[&name].as_str().unwrap_or(""), "model");
+            for (key, value) in list {
+                match key.as_str() {
+                    "bridge" => record["_bridge"] = value.into(),
+                    "tag" => record["_tag"] = value.parse::<u16>().ok().into(),
+                    "firewall" => record["_firewall"] = (value == "1").into(),
+                    _ => {}
+                }
+            }
+            Ok(record)
+        })
+    })
+    .submit_hook({
+        let name = name.clone();
+        Callback::from(move |state: PropertyEditorState| {
+            let net = state.record[&name].as_str().unwrap_or("");
+            let mut list = parse_property_string(net, "model");
+
+            let tag = state
+                .form_ctx
+                .read()
+                .get_field_value("_tag")
+                .as_ref()
+                .and_then(value_as_u64);
+            let firewall = field_bool(&state.form_ctx, "_firewall");
+
+            set_property_value(
+                &mut list,
+                "bridge",
+                field_string(&state.form_ctx, "_bridge"),
+            );
+            set_property_value(&mut list, "tag", tag.map(|tag| tag.to_string()));
+            set_property_value(&mut list, "firewall", firewall.then(|| String::from("1")));
+
+            let mut data = state.get_submit_data();
+            if let Value::Object(map) = &mut data {
+                map.remove("_bridge");
+                map.remove("_tag");
+                map.remove("_firewall");
+            }
+            data[&name] = format_property_string(&list, Some("model")).into();
+            Ok(data)
+        })
+    })
+    .render_input_panel(move |_| {
+        let bridge_selector = bridges
+            .iter()
+            .fold(Combobox::new(), |combo, bridge| {
+                combo.with_item(bridge.clone())
+            })
+            .name("_bridge")
+            .required(true)
+            .force_selection(true);
+
+        Column::new()
+            .gap(2)
+            .with_child(label_field(tr!("Bridge"), bridge_selector, true))
+            .with_child(label_field(
+                tr!("VLAN Tag"),
+                Number::<u16>::new()
+                    .name("_tag")
+                    .min(1)
+                    .max(4094)
+                    .placeholder(tr!("no VLAN")),
+                true,
+            ))
+            .with_child(Checkbox::new().name("_firewall").box_label(tr!("Firewall")))
+            .into()
+    })
+}
+
+fn hardware_properties(
+    keys: &HashSet<String>,
+    iso_list: Rc<Vec<String>>,
+    bridges: Rc<Vec<String>>,
+    on_disk_action: Callback<String>,
+) -> Vec<EditableProperty> {
+    let mut properties = vec![
+        memory_property(),
+        processor_property(),
+        EditableProperty::new("bios", tr!("BIOS"))
+            .required(true)
+            .placeholder(tr!("Default") + " (SeaBIOS)")
+            .renderer(|_name, value, _record| match value.as_str() {
+                Some("ovmf") => "OVMF (UEFI)".into(),
+                Some("seabios") => "SeaBIOS".into(),
+                _ => value.to_string().into(),
+            }),
+        EditableProperty::new("machine", tr!("Machine"))
+            .required(true)
+            .placeholder(tr!("Default") + " (i440fx)"),
+    ];
+
+    for (bus, count) in DRIVE_BUSSES {
+        for name in indexed_keys(keys, bus, *count) {
+            properties.push(drive_property(
+                &name,
+                iso_list.clone(),
+                on_disk_action.clone(),
+            ));
+        }
+    }
+
+    properties.push(EditableProperty::new("efidisk0", tr!("EFI Disk")));
+    properties.push(EditableProperty::new("tpmstate0", tr!("TPM State")));
+
+    for name in indexed_keys(keys, "net", 32) {
+        properties.push(network_property(&name, bridges.clone()));
+    }
+
+    for name in indexed_keys(keys, "usb", 14) {
+        let title = format!("{} ({name})", tr!("USB Device"));
+        properties.push(EditableProperty::new(AttrValue::from(name), title));
+    }
+
+    for name in indexed_keys(keys, "hostpci", 16) {
+        let title = format!("{} ({name})", tr!("PCI Device"));
+        properties.push(EditableProperty::new(AttrValue::from(name), title));
+    }
+
+    properties
+}
+
+impl PveQemuHardwarePendingPanel {
+    fn update_properties(&mut self, ctx: &Context<Self>) {
+        self.properties = Rc::new(hardware_properties(
+            &self.keys,
+            self.iso_list.clone(),
+            self.bridges.clone(),
+            ctx.link().callback(|disk| Msg::ShowDiskAction(Some(disk))),
+        ));
+    }
+}
+
+impl Component for PveQemuHardwarePendingPanel {
+    type Message = Msg;
+    type Properties = QemuHardwarePendingPanel;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let props = ctx.props();
+
+        // ISO images and bridges are only needed by the editors
+        let node = props.node.clone();
+        let link = ctx.link().clone();
+        let load_guard = AsyncAbortGuard::spawn(async move {
+            let result = async {
+                let iso_list = load_iso_list(&node).await?;
+                let bridges = load_bridge_list(&node).await?;
+                Ok((iso_list, bridges))
+            }
+            .await;
+            link.send_message(Msg::LoadResult(result));
+        });
+
+        let mut me = Self {
+            properties: Rc::new(Vec::new()),
+            keys: HashSet::new(),
+            iso_list: Rc::new(Vec::new()),
+            bridges: Rc::new(Vec::new()),
+            load_guard: Some(load_guard),
+            disk_action: None,
+            running_upid: None,
+        };
+        me.update_properties(ctx);
+        me
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::LoadResult(result) => {
+                self.load_guard = None;
+                match result {
+                    Ok((iso_list, bridges)) => {
+                        self.iso_list = Rc::new(iso_list);
+                        self.bridges = Rc::new(bridges);
+                        self.update_properties(ctx);
+                    }
+                    Err(err) => crate::show_failed_command_error(ctx.link(), err),
+                }
+            }
+            Msg::ConfigKeys(keys) => {
+                if keys == self.keys {
+                    return false;
+                }
+                self.keys = keys;
+                self.update_properties(ctx);
+            }
+            Msg::ShowDiskAction(disk) => self.disk_action = disk,
+            Msg::StartTask(upid) => self.running_upid = Some(upid),
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let base_url = guest_base_url(&props.node, GuestType::Qemu, props.vmid);
+        let config_url = format!("{base_url}/config");
+
+        let list = PendingPropertyList::new(self.properties.clone())
+            .pending_loader(format!("{base_url}/pending"))
+            .editor_loader(config_url.clone())
+            .on_load(ctx.link().callback(Msg::ConfigKeys))
+            .on_submit(SubmitCallback::new(move |data: Value| {
+                let url = config_url.clone();
+                async move {
+                    let _: Value = http_put(&url, Some(data)).await?;
+                    Ok(())
+                }
+            }));
+
+        let task_button = TasksListButton::new()
+            .running_upid(self.running_upid.clone())
+            .on_show_task_list({
+                let navigator = ctx.link().navigator().clone().unwrap();
+                let node = props.node.clone();
+                let vmid = props.vmid;
+                move |_| {
+                    navigator.push(&crate::Route::QemuTasks {
+                        vmid,
+                        nodename: node.to_string(),
+                    });
+                }
+            });
+
+        let disk_dialog = self.disk_action.as_ref().map(|disk| {
+            GuestDiskActionDialog::new(
+                props.node.clone(),
+                GuestType::Qemu,
+                props.vmid,
+                disk.clone(),
+            )
+            .on_close(ctx.link().callback(|_| Msg::ShowDiskAction(None)))
+            .on_start_task(ctx.link().callback(Msg::StartTask))
+        });
+
+        Column::new()
+            .class(pwt::css::FlexFit)
+            .with_child(Container::new().padding(2).with_child(task_button))
+            .with_child(list)
+            .with_optional_child(disk_dialog)
+            .into()
+    }
+}
+
+impl From<QemuHardwarePendingPanel> for VNode {
+    fn from(props: QemuHardwarePendingPanel) -> Self {
+        let comp = VComp::new::<PveQemuHardwarePendingPanel>(Rc::new(props), None);
+        VNode::from(comp)
+    }
+}
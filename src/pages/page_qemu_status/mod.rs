@@ -1,6 +1,12 @@
 mod dashboard_panel;
 pub use dashboard_panel::QemuDashboardPanel;
 
+mod hardware_panel;
+pub use hardware_panel::QemuHardwarePendingPanel;
+
+mod guest_agent_card;
+pub use guest_agent_card::QemuGuestAgentCard;
+
 use std::rc::Rc;
 
 use serde::{Deserialize, Serialize};
@@ -13,7 +19,7 @@ use yew::virtual_dom::{VComp, VNode};
 use pwt::prelude::*;
 use pwt::widget::{Column, TabBar, TabBarItem};
 
-use crate::widgets::{GuestBackupPanel, TopNavBar};
+use crate::widgets::{GuestBackupPanel, GuestSnapshotPanel, GuestType, TopNavBar};
 
 use proxmox_yew_comp::configuration::pve::QemuOptionsPanel;
 
@@ -36,7 +42,9 @@ impl PageQemuStatus {
 pub enum ViewState {
     #[default]
     Dashboard,
+    Hardware,
     Options,
+    Snapshots,
     Backup,
 }
 
@@ -80,6 +88,14 @@ impl Component for PvePageQemuStatus {
                 "dashboard",
                 QemuDashboardPanel::new(props.node.clone(), props.vmid).into(),
             ),
+            ViewState::Hardware => (
+                "hardware",
+                QemuHardwarePendingPanel::new(props.node.clone(), props.vmid).into(),
+            ),
+            ViewState::Snapshots => (
+                "snapshots",
+                GuestSnapshotPanel::new(props.node.clone(), GuestType::Qemu, props.vmid).into(),
+            ),
             ViewState::Backup => (
                 "backup",
                 GuestBackupPanel::new(props.node.clone(), props.vmid).into(),
@@ -104,6 +120,15 @@ impl Component for PvePageQemuStatus {
                             .callback(|_| Msg::SetViewState(ViewState::Dashboard)),
                     ),
             )
+            .with_item(
+                TabBarItem::new()
+                    .label(tr!("Hardware"))
+                    .key("hardware")
+                    .on_activate(
+                        ctx.link()
+                            .callback(|_| Msg::SetViewState(ViewState::Hardware)),
+                    ),
+            )
             .with_item(
                 TabBarItem::new()
                     .label(tr!("Options"))
@@ -113,6 +138,15 @@ impl Component for PvePageQemuStatus {
                             .callback(|_| Msg::SetViewState(ViewState::Options)),
                     ),
             )
+            .with_item(
+                TabBarItem::new()
+                    .label(tr!("Snapshots"))
+                    .key("snapshots")
+                    .on_activate(
+                        ctx.link()
+                            .callback(|_| Msg::SetViewState(ViewState::Snapshots)),
+                    ),
+            )
             .with_item(
                 TabBarItem::new()
                     .label(tr!("Backup"))
@@ -53,18 +53,22 @@ pub fn PvePageQemuTasks(props: &PageQemuTasks) -> Html {
                     props.vmid,
                 )),
         )
-        .with_child(TasksPanel::new(base_url.clone()).on_show_task({
-            let vmid = props.vmid;
-            let nodename = props.nodename.to_string();
-            move |(upid, endtime): (String, Option<i64>)| {
-                navigator.push(&crate::Route::QemuTaskStatus {
-                    vmid,
-                    nodename: nodename.clone(),
-                    upid,
-                    endtime: endtime.unwrap_or(0),
-                });
-            }
-        }))
+        .with_child(
+            TasksPanel::new(base_url.clone())
+                .filter_state_id("pve-qemu-task-filter")
+                .on_show_task({
+                    let vmid = props.vmid;
+                    let nodename = props.nodename.to_string();
+                    move |(upid, endtime): (String, Option<i64>)| {
+                        navigator.push(&crate::Route::QemuTaskStatus {
+                            vmid,
+                            nodename: nodename.clone(),
+                            upid,
+                            endtime: endtime.unwrap_or(0),
+                        });
+                    }
+                }),
+        )
         .into()
 }
 
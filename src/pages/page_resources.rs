@@ -13,7 +13,7 @@ use yew_router::scope_ext::RouterScopeExt;
 
 use pwt::AsyncAbortGuard;
 use pwt::prelude::*;
-use pwt::touch::SideDialog;
+use pwt::touch::{Fab, FabSize, SideDialog};
 use pwt::widget::form::{Checkbox, Field};
 use pwt::widget::{ActionIcon, Card, Column, Fa, List, ListTile, Panel, Row, Trigger};
 
@@ -91,6 +91,7 @@ pub struct PvePageResources {
     load_guard: Option<AsyncAbortGuard>,
     filter: PersistentState<ResourceFilter>,
     show_filter_dialog: bool,
+    show_create_dialog: bool,
 }
 
 pub enum Msg {
@@ -104,6 +105,7 @@ pub enum Msg {
     FilterQemu(bool),
     FilterStorage(bool),
     FilterNodes(bool),
+    ShowCreateDialog(bool),
 }
 
 impl PvePageResources {
@@ -133,6 +135,12 @@ impl PvePageResources {
     }
 
     fn create_vm_list_item(&self, icon: &str, item: &ClusterResource) -> ListTile {
+        let template = item.template.unwrap_or(false);
+        let (icon, status) = if template {
+            ("file-o", Some(tr!("Template")))
+        } else {
+            (icon, item.status.clone())
+        };
         icon_list_tile(
             Fa::new(icon)
                 .class((item.status.as_deref() == Some("running")).then(|| "pwt-color-primary")),
@@ -142,7 +150,7 @@ impl PvePageResources {
                 item.name.as_deref().unwrap_or("")
             ),
             item.node.clone(),
-            item.status.clone(),
+            status,
         )
         .interactive(true)
     }
@@ -294,6 +302,26 @@ impl PvePageResources {
             .into()
     }
 
+    fn create_guest_panel(&self, ctx: &Context<Self>) -> Html {
+        let navigator = ctx.link().navigator().unwrap();
+
+        let tiles = vec![
+            icon_list_tile(Fa::new("desktop"), tr!("Create VM"), (), ())
+                .interactive(true)
+                .onclick({
+                    let navigator = navigator.clone();
+                    move |_| navigator.push(&crate::Route::CreateQemu)
+                }),
+            icon_list_tile(Fa::new("cube"), tr!("Create CT"), (), ())
+                .interactive(true)
+                .onclick(move |_| navigator.push(&crate::Route::CreateLxc)),
+        ];
+
+        List::from_tiles(tiles)
+            .grid_template_columns("auto 1fr")
+            .into()
+    }
+
     fn create_top_bar(&self, ctx: &Context<Self>) -> Html {
         let mut search = Field::new()
             .value(self.filter.name.clone())
@@ -369,6 +397,7 @@ impl Component for PvePageResources {
         Self {
             filter,
             show_filter_dialog: false,
+            show_create_dialog: false,
             reload_timeout: None,
             load_guard: None,
         }
@@ -432,6 +461,9 @@ impl Component for PvePageResources {
                 filter.nodes = value;
                 self.filter.update(filter);
             }
+            Msg::ShowCreateDialog(show) => {
+                self.show_create_dialog = show;
+            }
         }
         true
     }
@@ -440,10 +472,28 @@ impl Component for PvePageResources {
         let content = RESOURCES
             .with_borrow(|r| render_loaded_data(r, |data| self.create_resource_list(ctx, data)));
 
+        let fab = Fab::new("fa fa-plus")
+            .size(FabSize::Small)
+            .text(tr!("Create"))
+            .class("pwt-position-absolute")
+            .style("right", "var(--pwt-spacer-2)")
+            .style("bottom", "var(--pwt-spacer-2)")
+            .on_activate(ctx.link().callback(|_| Msg::ShowCreateDialog(true)));
+
+        let create_dialog = self.show_create_dialog.then(|| {
+            SideDialog::new()
+                .location(pwt::touch::SideDialogLocation::Bottom)
+                .on_close(ctx.link().callback(|_| Msg::ShowCreateDialog(false)))
+                .with_child(self.create_guest_panel(ctx))
+        });
+
         Column::new()
             .class("pwt-fit")
+            .class("pwt-position-relative")
             .with_child(self.create_top_bar(ctx))
             .with_child(content)
+            .with_child(fab)
+            .with_optional_child(create_dialog)
             .into()
     }
 }
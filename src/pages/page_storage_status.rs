@@ -8,7 +8,7 @@ use yew::virtual_dom::{VComp, VNode};
 use pwt::widget::{Column, Container};
 use pwt::{AsyncAbortGuard, prelude::*};
 
-use crate::widgets::{StorageContentPanel, TopNavBar, storage_card};
+use crate::widgets::{RrdGraphPanel, StorageContentPanel, TopNavBar, storage_card};
 
 use proxmox_yew_comp::layout::render_loaded_data;
 use proxmox_yew_comp::{http_get, percent_encoding::percent_encode_component};
@@ -54,6 +54,11 @@ impl PvePageStorageStatus {
                 status["total"].as_i64(),
                 status["used"].as_i64(),
             )))
+            .with_child(
+                Container::new()
+                    .padding_x(2)
+                    .with_child(RrdGraphPanel::storage(props.node.clone(), props.name.clone())),
+            )
             .with_child(content)
             .into()
     }
@@ -0,0 +1,400 @@
+use std::marker::PhantomData;
+use std::rc::Rc;
+
+use anyhow::Error;
+use serde_json::Value;
+
+use yew::virtual_dom::{VComp, VNode};
+use yew_router::scope_ext::RouterScopeExt;
+
+use pwt::AsyncAbortGuard;
+use pwt::prelude::*;
+use pwt::touch::{SnackBar, SnackBarContextExt};
+use pwt::widget::form::{Checkbox, Form, FormContext, SubmitButton};
+use pwt::widget::{Button, Column, Container, Progress, Row};
+
+use proxmox_yew_comp::http_post;
+use proxmox_yew_comp::layout::render_loaded_data;
+use proxmox_yew_comp::percent_encoding::percent_encode_component;
+
+use crate::widgets::{GuestType, TopNavBar, field_string, load_nodes_and_next_id};
+
+/// Guest specific part of a [PveCreateWizard].
+///
+/// The wizard takes care of loading the node list and next free VMID, reloading
+/// the node specific data when the target node changes, the step navigation,
+/// the final confirm step and submitting the create request.
+pub trait CreateWizardSpec: Properties + 'static {
+    /// Data depending on the selected target node.
+    type NodeData: Default + 'static;
+    type Step: Copy + PartialEq + 'static;
+
+    const GUEST_TYPE: GuestType;
+    /// All steps, except the final confirm step.
+    const STEPS: &'static [Self::Step];
+
+    fn title(&self) -> String;
+
+    fn step_title(&self, step: Self::Step) -> String;
+
+    /// Names of the fields on the step, which must be valid before going to the next step.
+    fn step_fields(&self, step: Self::Step) -> &'static [&'static str];
+
+    /// Returns true if the step depends on other form fields and needs a redraw on change.
+    fn redraw_on_change(&self, _step: Self::Step) -> bool {
+        false
+    }
+
+    fn load_node_data(node: &str) -> impl Future<Output = Result<Self::NodeData, Error>>;
+
+    fn view_step(&self, step: Self::Step, state: &WizardState<Self::NodeData>) -> Html;
+
+    /// Assemble the parameters for `POST /nodes/{node}/{qemu|lxc}`.
+    fn create_params(&self, form_ctx: &FormContext) -> Value;
+
+    /// Parameters shown on the confirm step.
+    fn confirm_params(&self, form_ctx: &FormContext) -> Value {
+        self.create_params(form_ctx)
+    }
+}
+
+/// State passed to [CreateWizardSpec::view_step].
+pub struct WizardState<'a, D> {
+    pub nodes: &'a [String],
+    pub next_id: u32,
+    pub form_ctx: &'a FormContext,
+    pub node_data: &'a D,
+    /// Node specific data is currently loading.
+    pub loading: bool,
+}
+
+pub enum Msg<D> {
+    LoadResult(Result<(Vec<String>, u32), Error>),
+    LoadNodeData(String),
+    NodeDataResult(String, Result<D, Error>),
+    FormChange,
+    SetStep(usize),
+    Submit(FormContext),
+    SubmitResult(String, Result<String, Error>),
+}
+
+pub struct PveCreateWizard<W: CreateWizardSpec> {
+    // index into `W::STEPS`, `W::STEPS.len()` is the confirm step
+    step: usize,
+    data: Option<Result<(Vec<String>, u32), String>>,
+    load_guard: Option<AsyncAbortGuard>,
+    node: Option<String>,
+    node_data: W::NodeData,
+    node_data_guard: Option<AsyncAbortGuard>,
+    submit_guard: Option<AsyncAbortGuard>,
+    form_ctx: FormContext,
+    _phantom: PhantomData<W>,
+}
+
+impl<W: CreateWizardSpec> PveCreateWizard<W> {
+    fn is_confirm_step(&self) -> bool {
+        self.step >= W::STEPS.len()
+    }
+
+    fn step_title(&self, ctx: &Context<Self>) -> String {
+        match W::STEPS.get(self.step) {
+            Some(step) => ctx.props().step_title(*step),
+            None => tr!("Confirm"),
+        }
+    }
+
+    fn confirm_panel(&self, ctx: &Context<Self>) -> Html {
+        let param = ctx.props().confirm_params(&self.form_ctx);
+        let node = field_string(&self.form_ctx, "node").unwrap_or_default();
+
+        let mut list = Column::new().gap(1).with_child(
+            Row::new()
+                .gap(2)
+                .with_child(
+                    Container::new()
+                        .class("pwt-font-weight-bold")
+                        .with_child("node"),
+                )
+                .with_flex_spacer()
+                .with_child(node),
+        );
+
+        if let Value::Object(map) = &param {
+            for (key, value) in map {
+                let value = match value {
+                    Value::String(text) => text.clone(),
+                    other => other.to_string(),
+                };
+                list.add_child(
+                    Row::new()
+                        .gap(2)
+                        .with_child(
+                            Container::new()
+                                .class("pwt-font-weight-bold")
+                                .with_child(key.clone()),
+                        )
+                        .with_flex_spacer()
+                        .with_child(
+                            Container::new()
+                                .class("pwt-text-truncate")
+                                .with_child(value),
+                        ),
+                );
+            }
+        }
+
+        Column::new()
+            .gap(2)
+            .with_child(list)
+            .with_child(
+                Checkbox::new()
+                    .name("start")
+                    .box_label(tr!("Start after created")),
+            )
+            .into()
+    }
+
+    // Hidden steps cannot show their invalid fields, so check them before moving on.
+    fn step_valid(&self, ctx: &Context<Self>) -> bool {
+        let Some(step) = W::STEPS.get(self.step) else {
+            return true;
+        };
+        let form_ctx = self.form_ctx.read();
+        ctx.props()
+            .step_fields(*step)
+            .iter()
+            .all(|name| !matches!(form_ctx.get_field_valid(*name), Some(Err(_))))
+    }
+
+    fn step_panel(&self, index: usize, content: Html) -> Html {
+        Column::new()
+            .class((index != self.step).then_some("pwt-d-none"))
+            .with_child(content)
+            .into()
+    }
+
+    fn view_wizard(&self, ctx: &Context<Self>, (nodes, next_id): &(Vec<String>, u32)) -> Html {
+        let props = ctx.props();
+        let index = self.step;
+        let count = W::STEPS.len() + 1;
+
+        let state = WizardState {
+            nodes,
+            next_id: *next_id,
+            form_ctx: &self.form_ctx,
+            node_data: &self.node_data,
+            loading: self.node_data_guard.is_some(),
+        };
+
+        // Keep all steps mounted, so that the form context knows all fields
+        let mut steps = Column::new().class(pwt::css::FlexFit).padding(2).gap(2);
+        for (i, step) in W::STEPS.iter().enumerate() {
+            steps.add_child(self.step_panel(i, props.view_step(*step, &state)));
+        }
+        steps.add_optional_child(self.is_confirm_step().then(|| self.confirm_panel(ctx)));
+
+        let back_button = Button::new(tr!("Back"))
+            .icon_class("fa fa-chevron-left")
+            .disabled(index == 0)
+            .onclick({
+                let link = ctx.link().clone();
+                move |_| {
+                    if index > 0 {
+                        link.send_message(Msg::SetStep(index - 1));
+                    }
+                }
+            });
+
+        let next_button: Html = if self.is_confirm_step() {
+            SubmitButton::new()
+                .check_dirty(false)
+                .disabled(self.submit_guard.is_some())
+                .text(tr!("Finish"))
+                .icon_class("fa fa-check")
+                .on_submit(ctx.link().callback(Msg::Submit))
+                .into()
+        } else {
+            Button::new(tr!("Next"))
+                .class("pwt-button-outline")
+                .icon_class("fa fa-chevron-right")
+                .onclick({
+                    let link = ctx.link().clone();
+                    move |_| link.send_message(Msg::SetStep(index + 1))
+                })
+                .into()
+        };
+
+        Column::new()
+            .class(pwt::css::FlexFit)
+            .with_child(
+                Column::new()
+                    .padding(2)
+                    .gap(1)
+                    .with_child(
+                        Container::new()
+                            .class("pwt-font-size-title-medium")
+                            .with_child(format!(
+                                "{} ({}/{})",
+                                self.step_title(ctx),
+                                index + 1,
+                                count
+                            )),
+                    )
+                    .with_child(Progress::new().value((index + 1) as f32 / count as f32)),
+            )
+            .with_child(
+                Form::new()
+                    .form_context(self.form_ctx.clone())
+                    .class(pwt::css::FlexFit)
+                    .with_child(
+                        Column::new()
+                            .class(pwt::css::FlexFit)
+                            .with_child(steps)
+                            .with_child(
+                                Row::new()
+                                    .padding(2)
+                                    .gap(2)
+                                    .class("pwt-border-top")
+                                    .with_child(back_button)
+                                    .with_flex_spacer()
+                                    .with_child(next_button),
+                            ),
+                    ),
+            )
+            .into()
+    }
+}
+
+impl<W: CreateWizardSpec> Component for PveCreateWizard<W> {
+    type Message = Msg<W::NodeData>;
+    type Properties = W;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let link = ctx.link().clone();
+        let load_guard = AsyncAbortGuard::spawn(async move {
+            let result = load_nodes_and_next_id().await;
+            link.send_message(Msg::LoadResult(result));
+        });
+
+        Self {
+            step: 0,
+            data: None,
+            load_guard: Some(load_guard),
+            node: None,
+            node_data: W::NodeData::default(),
+            node_data_guard: None,
+            submit_guard: None,
+            form_ctx: FormContext::new().on_change(ctx.link().callback(|_| Msg::FormChange)),
+            _phantom: PhantomData,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::LoadResult(result) => {
+                self.load_guard = None;
+                if let Ok((nodes, _)) = &result {
+                    if let Some(node) = nodes.first() {
+                        ctx.link().send_message(Msg::LoadNodeData(node.clone()));
+                    }
+                }
+                self.data = Some(result.map_err(|err| err.to_string()));
+            }
+            Msg::FormChange => {
+                if let Some(node) = field_string(&self.form_ctx, "node") {
+                    if Some(&node) != self.node.as_ref() {
+                        ctx.link().send_message(Msg::LoadNodeData(node));
+                    }
+                }
+                return match W::STEPS.get(self.step) {
+                    Some(step) => ctx.props().redraw_on_change(*step),
+                    None => false,
+                };
+            }
+            Msg::LoadNodeData(node) => {
+                self.node = Some(node.clone());
+                let link = ctx.link().clone();
+                self.node_data_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = W::load_node_data(&node).await;
+                    link.send_message(Msg::NodeDataResult(node, result));
+                }));
+            }
+            Msg::NodeDataResult(node, result) => {
+                if Some(&node) != self.node.as_ref() {
+                    return false;
+                }
+                self.node_data_guard = None;
+                match result {
+                    Ok(data) => self.node_data = data,
+                    Err(err) => {
+                        self.node_data = W::NodeData::default();
+                        crate::show_failed_command_error(ctx.link(), err);
+                    }
+                }
+            }
+            Msg::SetStep(step) => {
+                if step > self.step && !self.step_valid(ctx) {
+                    ctx.link().show_snackbar(
+                        SnackBar::new()
+                            .message(tr!("Please fill in all required fields correctly.")),
+                    );
+                    return false;
+                }
+                self.step = step.min(W::STEPS.len());
+            }
+            Msg::Submit(form_ctx) => {
+                let node = match field_string(&form_ctx, "node") {
+                    Some(node) => node,
+                    None => return false,
+                };
+                let param = ctx.props().create_params(&form_ctx);
+                let url = format!(
+                    "/nodes/{}/{}",
+                    percent_encode_component(&node),
+                    W::GUEST_TYPE
+                );
+                let link = ctx.link().clone();
+                self.submit_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_post(&url, Some(param)).await;
+                    link.send_message(Msg::SubmitResult(node, result));
+                }));
+            }
+            Msg::SubmitResult(nodename, result) => {
+                self.submit_guard = None;
+                match result {
+                    Ok(upid) => {
+                        if let Some(navigator) = ctx.link().navigator() {
+                            navigator.push(&crate::Route::NodeTaskStatus {
+                                nodename,
+                                upid,
+                                endtime: 0,
+                            });
+                        }
+                    }
+                    Err(err) => crate::show_failed_command_error(ctx.link(), err),
+                }
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let content = render_loaded_data(&self.data, |data| self.view_wizard(ctx, data));
+
+        Column::new()
+            .class("pwt-fit")
+            .with_child(
+                TopNavBar::new()
+                    .title(ctx.props().title())
+                    .back("/resources"),
+            )
+            .with_child(content)
+            .into()
+    }
+}
+
+/// Create the [PveCreateWizard] component for a guest specific wizard.
+pub fn create_wizard<W: CreateWizardSpec>(props: W) -> VNode {
+    let comp = VComp::new::<PveCreateWizard<W>>(Rc::new(props), None);
+    VNode::from(comp)
+}
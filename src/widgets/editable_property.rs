@@ -120,6 +120,14 @@ pub struct EditableProperty {
 
     /// Edit input panel builder
     pub render_input_panel: Option<RenderPropertyInputPanelFn>,
+
+    /// Icon class for an additional action, shown next to the value.
+    #[builder(IntoPropValue, into_prop_value)]
+    pub action_icon: Option<AttrValue>,
+
+    /// Called when the action icon is activated.
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    pub on_action: Option<Callback<()>>,
 }
 
 impl EditableProperty {
@@ -139,6 +147,8 @@ impl EditableProperty {
             on_change: None,
             render_input_panel: None,
             advanced_checkbox: false,
+            action_icon: None,
+            on_action: None,
         }
     }
 
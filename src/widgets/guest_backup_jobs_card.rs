@@ -0,0 +1,206 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+use serde_json::{Value, json};
+
+use yew::virtual_dom::{VComp, VNode};
+use yew_router::scope_ext::RouterScopeExt;
+
+use pwt::AsyncAbortGuard;
+use pwt::prelude::*;
+use pwt::widget::{Fa, List, ListTile};
+
+use proxmox_yew_comp::http_get;
+use proxmox_yew_comp::layout::card::standard_card;
+use proxmox_yew_comp::layout::list_tile::icon_list_tile;
+use proxmox_yew_comp::layout::render_loaded_data;
+use proxmox_yew_comp::utils::render_epoch_short;
+
+use crate::widgets::GuestType;
+
+fn vmid_list(value: &Value) -> Vec<u32> {
+    value
+        .as_str()
+        .unwrap_or("")
+        .split(',')
+        .filter_map(|vmid| vmid.trim().parse().ok())
+        .collect()
+}
+
+/// Describe which guests a backup job (`GET /cluster/backup`) includes.
+pub fn backup_job_selection(job: &Value) -> String {
+    let node = job["node"].as_str();
+    let selection = if job["all"].as_u64() == Some(1) || job["all"].as_bool() == Some(true) {
+        match job["exclude"].as_str() {
+            Some(exclude) if !exclude.is_empty() => tr!("All except {0}", exclude),
+            _ => tr!("All"),
+        }
+    } else if let Some(pool) = job["pool"].as_str() {
+        tr!("Pool {0}", pool)
+    } else {
+        job["vmid"].as_str().unwrap_or("-").to_string()
+    };
+    match node {
+        Some(node) => format!("{selection} ({node})"),
+        None => selection,
+    }
+}
+
+/// Text for the next run of a backup job.
+pub fn backup_job_next_run(job: &Value) -> String {
+    if job["enabled"].as_u64() == Some(0) || job["enabled"].as_bool() == Some(false) {
+        return tr!("disabled");
+    }
+    match job["next-run"].as_i64() {
+        Some(next_run) => render_epoch_short(next_run),
+        None => String::from("-"),
+    }
+}
+
+// Same selection logic as vzdump: node restriction, all (with excludes), pool or vmid list.
+fn job_covers_guest(job: &Value, node: &str, vmid: u32, pool: Option<&str>) -> bool {
+    if let Some(job_node) = job["node"].as_str() {
+        if job_node != node {
+            return false;
+        }
+    }
+    if job["all"].as_u64() == Some(1) || job["all"].as_bool() == Some(true) {
+        return !vmid_list(&job["exclude"]).contains(&vmid);
+    }
+    if let Some(job_pool) = job["pool"].as_str() {
+        return pool == Some(job_pool);
+    }
+    vmid_list(&job["vmid"]).contains(&vmid)
+}
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct GuestBackupJobsCard {
+    node: AttrValue,
+    guest_type: GuestType,
+    vmid: u32,
+}
+
+impl GuestBackupJobsCard {
+    pub fn new(node: impl Into<AttrValue>, guest_type: GuestType, vmid: u32) -> Self {
+        Self {
+            node: node.into(),
+            guest_type,
+            vmid,
+        }
+    }
+}
+
+pub enum Msg {
+    LoadResult(Result<Vec<Value>, Error>),
+}
+
+pub struct PveGuestBackupJobsCard {
+    data: Option<Result<Vec<Value>, String>>,
+    _load_guard: AsyncAbortGuard,
+}
+
+async fn load_covering_jobs(node: String, vmid: u32) -> Result<Vec<Value>, Error> {
+    let jobs: Vec<Value> = http_get("/cluster/backup", None).await?;
+
+    // pool membership is only needed for pool based jobs
+    let pool = if jobs.iter().any(|job| job["pool"].is_string()) {
+        let resources: Vec<Value> =
+            http_get("/cluster/resources", Some(json!({ "type": "vm" }))).await?;
+        resources
+            .into_iter()
+            .find(|item| item["vmid"].as_u64() == Some(vmid as u64))
+            .and_then(|item| item["pool"].as_str().map(String::from))
+    } else {
+        None
+    };
+
+    Ok(jobs
+        .into_iter()
+        .filter(|job| job_covers_guest(job, &node, vmid, pool.as_deref()))
+        .collect())
+}
+
+impl Component for PveGuestBackupJobsCard {
+    type Message = Msg;
+    type Properties = GuestBackupJobsCard;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let props = ctx.props();
+        let link = ctx.link().clone();
+        let node = props.node.to_string();
+        let vmid = props.vmid;
+        let load_guard = AsyncAbortGuard::spawn(async move {
+            let result = load_covering_jobs(node, vmid).await;
+            link.send_message(Msg::LoadResult(result));
+        });
+
+        Self {
+            data: None,
+            _load_guard: load_guard,
+        }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::LoadResult(result) => {
+                self.data = Some(result.map_err(|err| err.to_string()));
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+
+        let content = render_loaded_data(&self.data, |jobs| {
+            let tiles: Vec<ListTile> = if jobs.is_empty() {
+                let kind = match props.guest_type {
+                    GuestType::Qemu => tr!("VM"),
+                    GuestType::Lxc => tr!("CT"),
+                };
+                vec![icon_list_tile(
+                    Fa::new("exclamation-triangle").class("pwt-color-warning"),
+                    tr!(
+                        "{0} {1} is not covered by any backup job.",
+                        kind,
+                        props.vmid
+                    ),
+                    (),
+                    (),
+                )]
+            } else {
+                jobs.iter()
+                    .map(|job| {
+                        let title = match job["comment"].as_str() {
+                            Some(comment) if !comment.is_empty() => comment.to_string(),
+                            _ => job["schedule"].as_str().unwrap_or("").to_string(),
+                        };
+                        icon_list_tile(
+                            Fa::new("floppy-o"),
+                            title,
+                            job["storage"].as_str().unwrap_or("").to_string(),
+                            backup_job_next_run(job),
+                        )
+                    })
+                    .collect()
+            };
+            List::from_tiles(tiles)
+                .grid_template_columns("auto 1fr auto")
+                .into()
+        });
+
+        let navigator = ctx.link().navigator().unwrap();
+        standard_card(tr!("Backup Jobs"), (), ())
+            .class("pwt-interactive")
+            .onclick(move |_| navigator.push(&crate::Route::BackupJobs))
+            .with_child(content)
+            .into()
+    }
+}
+
+impl From<GuestBackupJobsCard> for VNode {
+    fn from(props: GuestBackupJobsCard) -> Self {
+        let comp = VComp::new::<PveGuestBackupJobsCard>(Rc::new(props), None);
+        VNode::from(comp)
+    }
+}
@@ -0,0 +1,340 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+use serde_json::{Value, json};
+
+use yew::html::IntoEventCallback;
+use yew::prelude::*;
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::AsyncAbortGuard;
+use pwt::prelude::*;
+use pwt::touch::SideDialog;
+use pwt::widget::form::{Checkbox, Combobox, Field, Form, FormContext, Number, SubmitButton};
+use pwt::widget::{Column, Container, Row};
+
+use pwt_macros::builder;
+
+use proxmox_yew_comp::layout::mobile_form::label_field;
+use proxmox_yew_comp::layout::render_loaded_data;
+use proxmox_yew_comp::{http_get, http_post, percent_encoding::percent_encode_component};
+
+use pve_api_types::StorageInfo;
+
+use crate::widgets::{GuestType, guest_base_url, load_nodes_and_next_id};
+
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct GuestCloneDialog {
+    node: AttrValue,
+    guest_type: GuestType,
+    vmid: u32,
+
+    /// The source guest is a template (allows linked clones).
+    #[builder]
+    #[prop_or_default]
+    pub template: bool,
+
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    #[prop_or_default]
+    /// Called when the dialog is closed
+    pub on_close: Option<Callback<()>>,
+
+    #[builder_cb(IntoEventCallback, into_event_callback, String)]
+    #[prop_or_default]
+    /// Called with the task UPID after the clone task started
+    pub on_clone: Option<Callback<String>>,
+}
+
+impl GuestCloneDialog {
+    pub fn new(node: impl Into<AttrValue>, guest_type: GuestType, vmid: u32) -> Self {
+        yew::props!(Self {
+            node: node.into(),
+            guest_type,
+            vmid,
+        })
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct CloneInfo {
+    nodes: Vec<String>,
+    pools: Vec<String>,
+    next_id: u32,
+}
+
+pub enum Msg {
+    LoadResult(Result<CloneInfo, Error>),
+    LoadStorage(String),
+    LoadStorageResult(String, Result<Vec<StorageInfo>, Error>),
+    FormChange,
+    Clone(FormContext),
+    CloneResult(Result<String, Error>),
+}
+
+pub struct PveGuestCloneDialog {
+    data: Option<Result<CloneInfo, String>>,
+    load_guard: Option<AsyncAbortGuard>,
+    target_node: Option<String>,
+    storage_list: Vec<String>,
+    load_storage_guard: Option<AsyncAbortGuard>,
+    clone_guard: Option<AsyncAbortGuard>,
+    form_ctx: FormContext,
+}
+
+impl PveGuestCloneDialog {
+    fn create_clone_panel(&self, ctx: &Context<Self>, info: &CloneInfo) -> Html {
+        let props = ctx.props();
+
+        let full_clone = !props.template
+            || self
+                .form_ctx
+                .read()
+                .get_field_value("full")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+
+        let node_selector = info
+            .nodes
+            .iter()
+            .fold(Combobox::new(), |combo, node| combo.with_item(node.clone()))
+            .name("target")
+            .required(true)
+            .force_selection(true)
+            .default(props.node.clone());
+
+        let storage_selector = self
+            .storage_list
+            .iter()
+            .fold(Combobox::new(), |combo, storage| {
+                combo.with_item(storage.clone())
+            })
+            .name("storage")
+            .disabled(!full_clone)
+            .force_selection(true)
+            .placeholder(tr!("Same as source"));
+
+        let pool_selector = info
+            .pools
+            .iter()
+            .fold(Combobox::new(), |combo, pool| combo.with_item(pool.clone()))
+            .name("pool")
+            .force_selection(true)
+            .placeholder(tr!("None"));
+
+        let (title, name_label, name_field) = match props.guest_type {
+            GuestType::Qemu => (tr!("Clone VM {0}", props.vmid), tr!("Name"), "name"),
+            GuestType::Lxc => (tr!("Clone CT {0}", props.vmid), tr!("Hostname"), "hostname"),
+        };
+
+        Form::new()
+            .form_context(self.form_ctx.clone())
+            .class(pwt::css::FlexFit)
+            .with_child(
+                Column::new()
+                    .class(pwt::css::FlexFit)
+                    .padding(2)
+                    .gap(2)
+                    .with_child(
+                        Container::new()
+                            .class("pwt-font-size-title-large")
+                            .with_child(title),
+                    )
+                    .with_child(label_field(tr!("Target Node"), node_selector, true))
+                    .with_child(label_field(
+                        tr!("VM ID"),
+                        Number::<u32>::new()
+                            .name("newid")
+                            .required(true)
+                            .min(100)
+                            .default(info.next_id),
+                        true,
+                    ))
+                    .with_child(label_field(name_label, Field::new().name(name_field), true))
+                    .with_child(
+                        Checkbox::new()
+                            .name("full")
+                            .default(true)
+                            // linked clones are only possible from templates
+                            .disabled(!props.template)
+                            .box_label(tr!("Full Clone")),
+                    )
+                    .with_child(label_field(tr!("Target Storage"), storage_selector, true))
+                    .with_child(label_field(tr!("Resource Pool"), pool_selector, true))
+                    .with_child(
+                        Row::new()
+                            .class(pwt::css::JustifyContent::Center)
+                            .with_child(
+                                SubmitButton::new()
+                                    .check_dirty(false)
+                                    .disabled(self.clone_guard.is_some())
+                                    .text(tr!("Clone"))
+                                    .icon_class("fa fa-clone")
+                                    .class("pwt-button-outline")
+                                    .on_submit(ctx.link().callback(Msg::Clone)),
+                            ),
+                    ),
+            )
+            .into()
+    }
+}
+
+impl Component for PveGuestCloneDialog {
+    type Message = Msg;
+    type Properties = GuestCloneDialog;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let props = ctx.props();
+
+        let link = ctx.link().clone();
+        let load_guard = AsyncAbortGuard::spawn(async move {
+            let result = async {
+                let (nodes, next_id) = load_nodes_and_next_id().await?;
+
+                let pools: Vec<Value> = http_get("/pools", None).await?;
+                let mut pools: Vec<String> = pools
+                    .iter()
+                    .filter_map(|pool| pool["poolid"].as_str().map(String::from))
+                    .collect();
+                pools.sort();
+
+                Ok::<_, Error>(CloneInfo {
+                    nodes,
+                    pools,
+                    next_id,
+                })
+            }
+            .await;
+            link.send_message(Msg::LoadResult(result));
+        });
+
+        ctx.link()
+            .send_message(Msg::LoadStorage(props.node.to_string()));
+
+        Self {
+            data: None,
+            load_guard: Some(load_guard),
+            target_node: None,
+            storage_list: Vec::new(),
+            load_storage_guard: None,
+            clone_guard: None,
+            form_ctx: FormContext::new().on_change(ctx.link().callback(|_| Msg::FormChange)),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let props = ctx.props();
+        match msg {
+            Msg::LoadResult(result) => {
+                self.load_guard = None;
+                self.data = Some(result.map_err(|err| err.to_string()));
+            }
+            Msg::FormChange => {
+                let node = self
+                    .form_ctx
+                    .read()
+                    .get_field_value("target")
+                    .and_then(|v| v.as_str().map(String::from));
+                if let Some(node) = node {
+                    if Some(&node) != self.target_node.as_ref() {
+                        ctx.link().send_message(Msg::LoadStorage(node));
+                    }
+                }
+                // the storage selector depends on the 'full' checkbox
+                return props.template;
+            }
+            Msg::LoadStorage(node) => {
+                let content = match props.guest_type {
+                    GuestType::Qemu => "images",
+                    GuestType::Lxc => "rootdir",
+                };
+                self.target_node = Some(node.clone());
+                let url = format!("/nodes/{}/storage", percent_encode_component(&node));
+                let link = ctx.link().clone();
+                self.load_storage_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_get(&url, Some(json!({ "content": content }))).await;
+                    link.send_message(Msg::LoadStorageResult(node, result));
+                }));
+            }
+            Msg::LoadStorageResult(node, result) => {
+                if Some(&node) != self.target_node.as_ref() {
+                    return false;
+                }
+                match result {
+                    Ok(list) => {
+                        let mut list: Vec<String> = list
+                            .into_iter()
+                            .filter(|info| info.enabled.unwrap_or(true))
+                            .map(|info| info.storage)
+                            .collect();
+                        list.sort();
+                        self.storage_list = list;
+                    }
+                    Err(err) => crate::show_failed_command_error(ctx.link(), err),
+                }
+            }
+            Msg::Clone(form_ctx) => {
+                let data = form_ctx.get_submit_data();
+
+                let mut param = json!({ "newid": data["newid"] });
+                for name in ["target", "name", "hostname", "storage", "pool"] {
+                    if let Some(value) = data[name].as_str().filter(|v| !v.is_empty()) {
+                        param[name] = value.into();
+                    }
+                }
+                let full = !props.template || data["full"].as_bool().unwrap_or(false);
+                param["full"] = full.into();
+                if !full {
+                    // linked clones always use the storage of the template
+                    if let Value::Object(map) = &mut param {
+                        map.remove("storage");
+                    }
+                }
+
+                let url = format!(
+                    "{}/clone",
+                    guest_base_url(&props.node, props.guest_type, props.vmid)
+                );
+                let link = ctx.link().clone();
+                self.clone_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_post(&url, Some(param)).await;
+                    link.send_message(Msg::CloneResult(result));
+                }));
+            }
+            Msg::CloneResult(result) => {
+                self.clone_guard = None;
+                match result {
+                    Ok(upid) => {
+                        if let Some(on_clone) = &props.on_clone {
+                            on_clone.emit(upid);
+                        }
+                        if let Some(on_close) = &props.on_close {
+                            on_close.emit(());
+                        }
+                    }
+                    Err(err) => crate::show_failed_command_error(ctx.link(), err),
+                }
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+
+        let content = render_loaded_data(&self.data, |info| self.create_clone_panel(ctx, info));
+
+        SideDialog::new()
+            .location(pwt::touch::SideDialogLocation::Bottom)
+            .on_close(props.on_close.clone())
+            .with_child(content)
+            .into()
+    }
+}
+
+impl From<GuestCloneDialog> for VNode {
+    fn from(props: GuestCloneDialog) -> Self {
+        let comp = VComp::new::<PveGuestCloneDialog>(Rc::new(props), None);
+        VNode::from(comp)
+    }
+}
@@ -0,0 +1,352 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+use serde_json::{Value, json};
+
+use yew::html::IntoEventCallback;
+use yew::prelude::*;
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::AsyncAbortGuard;
+use pwt::prelude::*;
+use pwt::touch::SideDialog;
+use pwt::widget::form::{Checkbox, Combobox, Field, Form, FormContext, SubmitButton};
+use pwt::widget::{Column, Container, Row};
+
+use pwt_macros::builder;
+
+use proxmox_human_byte::HumanByte;
+use proxmox_yew_comp::layout::mobile_form::label_field;
+use proxmox_yew_comp::layout::render_loaded_data;
+use proxmox_yew_comp::{http_get, http_post, http_put, percent_encoding::percent_encode_component};
+
+use pve_api_types::StorageInfo;
+
+use crate::widgets::{GuestType, guest_base_url, parse_property_string};
+
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct GuestDiskActionDialog {
+    node: AttrValue,
+    guest_type: GuestType,
+    vmid: u32,
+    /// The disk (`scsi0`) or volume (`rootfs`, `mp0`) name.
+    disk: AttrValue,
+
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    #[prop_or_default]
+    /// Called when the dialog is closed
+    pub on_close: Option<Callback<()>>,
+
+    #[builder_cb(IntoEventCallback, into_event_callback, String)]
+    #[prop_or_default]
+    /// Called with the task UPID after a resize or move task started
+    pub on_start_task: Option<Callback<String>>,
+}
+
+impl GuestDiskActionDialog {
+    pub fn new(
+        node: impl Into<AttrValue>,
+        guest_type: GuestType,
+        vmid: u32,
+        disk: impl Into<AttrValue>,
+    ) -> Self {
+        yew::props!(Self {
+            node: node.into(),
+            guest_type,
+            vmid,
+            disk: disk.into(),
+        })
+    }
+}
+
+#[derive(Clone, PartialEq)]
+struct DiskInfo {
+    volume: String,
+    size: Option<String>,
+    cdrom: bool,
+    storages: Vec<String>,
+}
+
+pub enum Msg {
+    LoadResult(Result<DiskInfo, Error>),
+    Resize(FormContext),
+    Move(FormContext),
+    CommandResult(Result<Value, Error>),
+}
+
+pub struct PveGuestDiskActionDialog {
+    data: Option<Result<DiskInfo, String>>,
+    load_guard: Option<AsyncAbortGuard>,
+    command_guard: Option<AsyncAbortGuard>,
+    resize_form: FormContext,
+    move_form: FormContext,
+}
+
+// Values without unit are interpreted as GiB, like in the web interface.
+fn parse_size_increment(value: &str) -> Result<HumanByte, Error> {
+    let value = value.trim().trim_start_matches('+');
+    let size = match value.parse::<f64>() {
+        Ok(gib) => HumanByte::new_binary(gib * 1024.0 * 1024.0 * 1024.0),
+        Err(_) => value.parse()?,
+    };
+    if size.as_u64() == 0 {
+        return Err(Error::msg(tr!("Size increment must be greater than zero")));
+    }
+    Ok(size)
+}
+
+impl PveGuestDiskActionDialog {
+    fn section_title(title: String) -> Container {
+        Container::new()
+            .class("pwt-font-size-title-medium")
+            .with_child(title)
+    }
+
+    fn view_dialog(&self, ctx: &Context<Self>, info: &DiskInfo) -> Html {
+        let props = ctx.props();
+        let busy = self.command_guard.is_some();
+
+        let mut panel = Column::new()
+            .class(pwt::css::FlexFit)
+            .padding(2)
+            .gap(2)
+            .with_child(
+                Container::new()
+                    .class("pwt-font-size-title-large")
+                    .with_child(format!("{} ({})", tr!("Disk Action"), props.disk)),
+            )
+            .with_child(
+                Container::new()
+                    .class("pwt-text-truncate")
+                    .with_child(format!(
+                        "{}: {}",
+                        info.volume,
+                        info.size.as_deref().unwrap_or("-")
+                    )),
+            );
+
+        if info.cdrom {
+            return panel
+                .with_child(tr!("CD/DVD drives cannot be resized or moved."))
+                .into();
+        }
+
+        let resize = Form::new()
+            .form_context(self.resize_form.clone())
+            .with_child(
+                Column::new()
+                    .gap(2)
+                    .with_child(Self::section_title(tr!("Resize")))
+                    .with_child(label_field(
+                        tr!("Size Increment"),
+                        Field::new()
+                            .name("size")
+                            .required(true)
+                            .placeholder("10 GiB")
+                            .validate(|value: &String| parse_size_increment(value).map(|_| ())),
+                        true,
+                    ))
+                    .with_child(
+                        Row::new().with_flex_spacer().with_child(
+                            SubmitButton::new()
+                                .check_dirty(false)
+                                .disabled(busy)
+                                .text(tr!("Resize disk"))
+                                .icon_class("fa fa-arrows-v")
+                                .class("pwt-button-outline")
+                                .on_submit(ctx.link().callback(Msg::Resize)),
+                        ),
+                    ),
+            );
+
+        let storage_selector = info
+            .storages
+            .iter()
+            .fold(Combobox::new(), |combo, storage| {
+                combo.with_item(storage.clone())
+            })
+            .name("storage")
+            .required(true)
+            .force_selection(true);
+
+        let move_disk = Form::new().form_context(self.move_form.clone()).with_child(
+            Column::new()
+                .gap(2)
+                .with_child(Self::section_title(tr!("Move")))
+                .with_child(label_field(tr!("Target Storage"), storage_selector, true))
+                .with_child(
+                    Checkbox::new()
+                        .name("delete")
+                        .box_label(tr!("Delete source")),
+                )
+                .with_child(
+                    Row::new().with_flex_spacer().with_child(
+                        SubmitButton::new()
+                            .check_dirty(false)
+                            .disabled(busy)
+                            .text(tr!("Move disk"))
+                            .icon_class("fa fa-truck")
+                            .class("pwt-button-outline")
+                            .on_submit(ctx.link().callback(Msg::Move)),
+                    ),
+                ),
+        );
+
+        panel.add_child(resize);
+        panel.add_child(html! {<hr/>});
+        panel.add_child(move_disk);
+        panel.into()
+    }
+}
+
+impl Component for PveGuestDiskActionDialog {
+    type Message = Msg;
+    type Properties = GuestDiskActionDialog;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let props = ctx.props();
+
+        let config_url = format!(
+            "{}/config",
+            guest_base_url(&props.node, props.guest_type, props.vmid)
+        );
+        let storage_url = format!("/nodes/{}/storage", percent_encode_component(&props.node));
+        let content = match props.guest_type {
+            GuestType::Qemu => "images",
+            GuestType::Lxc => "rootdir",
+        };
+        let disk = props.disk.to_string();
+
+        let link = ctx.link().clone();
+        let load_guard = AsyncAbortGuard::spawn(async move {
+            let result = async {
+                let config: Value = http_get(&config_url, None).await?;
+                let list = parse_property_string(config[&disk].as_str().unwrap_or(""), "volume");
+                let get = |key: &str| {
+                    list.iter()
+                        .find(|(k, _)| k == key)
+                        .map(|(_, value)| value.clone())
+                };
+
+                let storage_list: Vec<StorageInfo> =
+                    http_get(&storage_url, Some(json!({ "content": content }))).await?;
+                let volume = get("volume").unwrap_or_default();
+                // moving to the current storage is not possible
+                let current_storage = volume.split_once(':').map(|(storage, _)| storage);
+                let mut storages: Vec<String> = storage_list
+                    .into_iter()
+                    .filter(|info| info.enabled.unwrap_or(true))
+                    .filter(|info| Some(info.storage.as_str()) != current_storage)
+                    .map(|info| info.storage)
+                    .collect();
+                storages.sort();
+
+                Ok(DiskInfo {
+                    volume,
+                    size: get("size"),
+                    cdrom: get("media").as_deref() == Some("cdrom"),
+                    storages,
+                })
+            }
+            .await;
+            link.send_message(Msg::LoadResult(result));
+        });
+
+        Self {
+            data: None,
+            load_guard: Some(load_guard),
+            command_guard: None,
+            resize_form: FormContext::new(),
+            move_form: FormContext::new(),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let props = ctx.props();
+        let base_url = guest_base_url(&props.node, props.guest_type, props.vmid);
+        let link = ctx.link().clone();
+
+        match msg {
+            Msg::LoadResult(result) => {
+                self.load_guard = None;
+                self.data = Some(result.map_err(|err| err.to_string()));
+            }
+            Msg::Resize(form_ctx) => {
+                let size = form_ctx.get_submit_data()["size"]
+                    .as_str()
+                    .unwrap_or("")
+                    .to_string();
+                let size = match parse_size_increment(&size) {
+                    Ok(size) => size,
+                    Err(err) => {
+                        crate::show_failed_command_error(ctx.link(), err);
+                        return false;
+                    }
+                };
+                let param = json!({
+                    "disk": props.disk.to_string(),
+                    "size": format!("+{}", size.as_u64()),
+                });
+                let url = format!("{base_url}/resize");
+                self.command_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_put(&url, Some(param)).await;
+                    link.send_message(Msg::CommandResult(result));
+                }));
+            }
+            Msg::Move(form_ctx) => {
+                let data = form_ctx.get_submit_data();
+                let (url, disk_key) = match props.guest_type {
+                    GuestType::Qemu => (format!("{base_url}/move_disk"), "disk"),
+                    GuestType::Lxc => (format!("{base_url}/move_volume"), "volume"),
+                };
+                let mut param = json!({
+                    "storage": data["storage"],
+                    "delete": data["delete"].as_bool().unwrap_or(false),
+                });
+                param[disk_key] = props.disk.to_string().into();
+                self.command_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_post(&url, Some(param)).await;
+                    link.send_message(Msg::CommandResult(result));
+                }));
+            }
+            Msg::CommandResult(result) => {
+                self.command_guard = None;
+                match result {
+                    Ok(result) => {
+                        // older versions resize synchronously and return null
+                        if let (Value::String(upid), Some(on_start_task)) =
+                            (result, &props.on_start_task)
+                        {
+                            on_start_task.emit(upid);
+                        }
+                        if let Some(on_close) = &props.on_close {
+                            on_close.emit(());
+                        }
+                    }
+                    Err(err) => crate::show_failed_command_error(ctx.link(), err),
+                }
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+
+        let content = render_loaded_data(&self.data, |info| self.view_dialog(ctx, info));
+
+        SideDialog::new()
+            .location(pwt::touch::SideDialogLocation::Bottom)
+            .on_close(props.on_close.clone())
+            .with_child(content)
+            .into()
+    }
+}
+
+impl From<GuestDiskActionDialog> for VNode {
+    fn from(props: GuestDiskActionDialog) -> Self {
+        let comp = VComp::new::<PveGuestDiskActionDialog>(Rc::new(props), None);
+        VNode::from(comp)
+    }
+}
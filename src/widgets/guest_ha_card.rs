@@ -0,0 +1,282 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+use serde_json::{Value, json};
+
+use yew::html::IntoEventCallback;
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::AsyncAbortGuard;
+use pwt::prelude::*;
+use pwt::widget::{
+    Button, Column, ConfirmDialog, Container, Fa, List, MiniScroll, MiniScrollMode, Row,
+};
+
+use pwt_macros::builder;
+
+use proxmox_yew_comp::layout::card::standard_card;
+use proxmox_yew_comp::layout::list_tile::icon_list_tile;
+use proxmox_yew_comp::layout::render_loaded_data;
+use proxmox_yew_comp::{
+    http_delete_get, http_get, http_post, http_put, percent_encoding::percent_encode_component,
+};
+
+use crate::widgets::{GuestType, toggle_button_class};
+
+/// Requested states of an HA resource.
+const HA_STATES: &[&str] = &["started", "stopped", "ignored", "disabled"];
+
+/// Returns the HA resource ID of a guest (`vm:100` or `ct:100`).
+pub fn ha_sid(guest_type: GuestType, vmid: u32) -> String {
+    match guest_type {
+        GuestType::Qemu => format!("vm:{vmid}"),
+        GuestType::Lxc => format!("ct:{vmid}"),
+    }
+}
+
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct GuestHaCard {
+    guest_type: GuestType,
+    vmid: u32,
+
+    #[builder_cb(IntoEventCallback, into_event_callback, Option<String>)]
+    #[prop_or_default]
+    /// Called with the requested HA state after loading (`None` if not HA managed)
+    pub on_load: Option<Callback<Option<String>>>,
+}
+
+impl GuestHaCard {
+    pub fn new(guest_type: GuestType, vmid: u32) -> Self {
+        yew::props!(Self { guest_type, vmid })
+    }
+}
+
+pub struct HaResourceInfo {
+    // requested state, `None` if the guest is not HA managed
+    state: Option<String>,
+    group: Option<String>,
+    // status entry from `/cluster/ha/status/current`
+    status: Option<String>,
+    node: Option<String>,
+}
+
+pub enum Msg {
+    Load,
+    LoadResult(Result<HaResourceInfo, Error>),
+    Add,
+    SetState(&'static str),
+    ConfirmRemove(bool),
+    Remove,
+    CommandResult(Result<Value, Error>),
+}
+
+pub struct PveGuestHaCard {
+    data: Option<Result<HaResourceInfo, String>>,
+    load_guard: Option<AsyncAbortGuard>,
+    cmd_guard: Option<AsyncAbortGuard>,
+    confirm_remove: bool,
+}
+
+async fn load_ha_info(sid: String) -> Result<HaResourceInfo, Error> {
+    let resources: Vec<Value> = http_get("/cluster/ha/resources", None).await?;
+    let Some(resource) = resources.iter().find(|item| item["sid"] == sid.as_str()) else {
+        return Ok(HaResourceInfo {
+            state: None,
+            group: None,
+            status: None,
+            node: None,
+        });
+    };
+
+    let status: Vec<Value> = http_get("/cluster/ha/status/current", None).await?;
+    let entry = status.iter().find(|item| item["sid"] == sid.as_str());
+    let entry_str = |key: &str| entry.and_then(|e| e[key].as_str()).map(String::from);
+
+    Ok(HaResourceInfo {
+        // the state defaults to "started"
+        state: Some(resource["state"].as_str().unwrap_or("started").to_string()),
+        group: resource["group"].as_str().map(String::from),
+        status: entry_str("status").or_else(|| entry_str("crm_state")),
+        node: entry_str("node"),
+    })
+}
+
+impl PveGuestHaCard {
+    fn sid(&self, ctx: &Context<Self>) -> String {
+        let props = ctx.props();
+        ha_sid(props.guest_type, props.vmid)
+    }
+
+    fn resource_url(&self, ctx: &Context<Self>) -> String {
+        format!(
+            "/cluster/ha/resources/{}",
+            percent_encode_component(&self.sid(ctx))
+        )
+    }
+
+    fn command(
+        &mut self,
+        ctx: &Context<Self>,
+        future: impl Future<Output = Result<Value, Error>> + 'static,
+    ) {
+        let link = ctx.link().clone();
+        self.cmd_guard = Some(AsyncAbortGuard::spawn(async move {
+            let result = future.await;
+            link.send_message(Msg::CommandResult(result));
+        }));
+    }
+
+    fn view_info(&self, ctx: &Context<Self>, info: &HaResourceInfo) -> Html {
+        let busy = self.cmd_guard.is_some();
+
+        let Some(state) = &info.state else {
+            return Column::new()
+                .with_child(List::from_tiles(vec![icon_list_tile(
+                    Fa::new("heartbeat"),
+                    tr!("Not HA managed"),
+                    tr!("The guest is not restarted on another node on failure."),
+                    (),
+                )]))
+                .with_child(
+                    Row::new().padding(2).with_flex_spacer().with_child(
+                        Button::new(tr!("Add to HA"))
+                            .icon_class("fa fa-plus")
+                            .disabled(busy)
+                            .on_activate(ctx.link().callback(|_| Msg::Add)),
+                    ),
+                )
+                .into();
+        };
+
+        let mut subtitle = info.status.clone().unwrap_or_else(|| tr!("unknown"));
+        if let Some(node) = &info.node {
+            subtitle = format!("{subtitle} ({node})");
+        }
+        let group = info
+            .group
+            .as_ref()
+            .map(|group| tr!("Group: {0}", group))
+            .unwrap_or_default();
+
+        let mut row = Row::new().gap(1);
+        for &ha_state in HA_STATES {
+            row.add_child(
+                Button::new(ha_state)
+                    .class(toggle_button_class(ha_state == state.as_str()))
+                    .disabled(busy)
+                    .on_activate(ctx.link().callback(move |_| Msg::SetState(ha_state))),
+            );
+        }
+        row.add_child(
+            Button::new(tr!("Remove"))
+                .icon_class("fa fa-trash-o")
+                .disabled(busy)
+                .on_activate(ctx.link().callback(|_| Msg::ConfirmRemove(true))),
+        );
+
+        Column::new()
+            .with_child(
+                List::from_tiles(vec![icon_list_tile(
+                    Fa::new("heartbeat").class("pwt-color-primary"),
+                    tr!("Requested state: {0}", state),
+                    subtitle,
+                    group,
+                )])
+                .grid_template_columns("auto 1fr auto"),
+            )
+            .with_child(
+                Container::new().padding(2).with_child(
+                    MiniScroll::new(row)
+                        .scroll_mode(MiniScrollMode::Native)
+                        .class(pwt::css::Flex::None),
+                ),
+            )
+            .into()
+    }
+}
+
+impl Component for PveGuestHaCard {
+    type Message = Msg;
+    type Properties = GuestHaCard;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link().send_message(Msg::Load);
+        Self {
+            data: None,
+            load_guard: None,
+            cmd_guard: None,
+            confirm_remove: false,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let props = ctx.props();
+        match msg {
+            Msg::Load => {
+                let link = ctx.link().clone();
+                let sid = self.sid(ctx);
+                self.load_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = load_ha_info(sid).await;
+                    link.send_message(Msg::LoadResult(result));
+                }));
+            }
+            Msg::LoadResult(result) => {
+                self.load_guard = None;
+                if let (Ok(info), Some(on_load)) = (&result, &props.on_load) {
+                    on_load.emit(info.state.clone());
+                }
+                self.data = Some(result.map_err(|err| err.to_string()));
+            }
+            Msg::Add => {
+                let param = json!({ "sid": self.sid(ctx), "state": "started" });
+                self.command(ctx, async move {
+                    http_post("/cluster/ha/resources", Some(param)).await
+                });
+            }
+            Msg::SetState(state) => {
+                let url = self.resource_url(ctx);
+                self.command(ctx, async move {
+                    http_put(&url, Some(json!({ "state": state }))).await
+                });
+            }
+            Msg::ConfirmRemove(show) => self.confirm_remove = show,
+            Msg::Remove => {
+                self.confirm_remove = false;
+                let url = self.resource_url(ctx);
+                self.command(ctx, async move { http_delete_get(&url, None).await });
+            }
+            Msg::CommandResult(result) => {
+                self.cmd_guard = None;
+                if let Err(err) = result {
+                    crate::show_failed_command_error(ctx.link(), err);
+                }
+                ctx.link().send_message(Msg::Load);
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let content = render_loaded_data(&self.data, |info| self.view_info(ctx, info));
+
+        let confirm = self.confirm_remove.then(|| {
+            ConfirmDialog::default()
+                .confirm_message(tr!("Remove {0} from HA management?", self.sid(ctx)))
+                .on_close(ctx.link().callback(|_| Msg::ConfirmRemove(false)))
+                .on_confirm(ctx.link().callback(|_| Msg::Remove))
+        });
+
+        standard_card(tr!("High Availability"), (), ())
+            .with_child(content)
+            .with_optional_child(confirm)
+            .into()
+    }
+}
+
+impl From<GuestHaCard> for VNode {
+    fn from(props: GuestHaCard) -> Self {
+        let comp = VComp::new::<PveGuestHaCard>(Rc::new(props), None);
+        VNode::from(comp)
+    }
+}
@@ -0,0 +1,476 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+use serde_json::{Value, json};
+
+use yew::html::IntoEventCallback;
+use yew::prelude::*;
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::AsyncAbortGuard;
+use pwt::prelude::*;
+use pwt::touch::SideDialog;
+use pwt::widget::form::{Checkbox, Combobox, Form, FormContext, SubmitButton};
+use pwt::widget::{Column, Container, Fa, Row};
+
+use pwt_macros::builder;
+
+use proxmox_yew_comp::layout::mobile_form::label_field;
+use proxmox_yew_comp::layout::render_loaded_data;
+use proxmox_yew_comp::{http_get, http_post, percent_encoding::percent_encode_component};
+
+use pve_api_types::{ClusterNodeIndexResponse, ClusterNodeIndexResponseStatus, StorageInfo};
+
+use crate::widgets::{GuestType, guest_base_url};
+
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct GuestMigrateDialog {
+    node: AttrValue,
+    guest_type: GuestType,
+    vmid: u32,
+
+    /// Guest is running (enables online migration/restart mode).
+    #[builder]
+    #[prop_or_default]
+    pub running: bool,
+
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    #[prop_or_default]
+    /// Called when the dialog is closed
+    pub on_close: Option<Callback<()>>,
+
+    #[builder_cb(IntoEventCallback, into_event_callback, (String, String))]
+    #[prop_or_default]
+    /// Called with the target node and the task UPID after the migration started
+    pub on_migrate: Option<Callback<(String, String)>>,
+}
+
+impl GuestMigrateDialog {
+    pub fn new(node: impl Into<AttrValue>, guest_type: GuestType, vmid: u32) -> Self {
+        yew::props!(Self {
+            node: node.into(),
+            guest_type,
+            vmid,
+        })
+    }
+}
+
+// Parsed result of the migrate precondition check
+#[derive(Clone, PartialEq)]
+struct MigrateInfo {
+    target_nodes: Vec<String>,
+    // node => reason
+    not_allowed_nodes: Vec<(String, String)>,
+    local_resources: Vec<String>,
+    local_disks: Vec<String>,
+    mapped_resources: Vec<String>,
+}
+
+fn string_list(value: &Value) -> Vec<String> {
+    match value {
+        Value::Array(list) => list
+            .iter()
+            .map(|item| match item {
+                Value::String(text) => text.clone(),
+                // local_disks entries are objects with a volid
+                Value::Object(map) => map
+                    .get("volid")
+                    .and_then(|v| v.as_str())
+                    .map(String::from)
+                    .unwrap_or_else(|| item.to_string()),
+                other => other.to_string(),
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn parse_migrate_info(current_node: &str, data: &Value, online_nodes: Vec<String>) -> MigrateInfo {
+    let mut not_allowed_nodes = Vec::new();
+    if let Some(map) = data["not_allowed_nodes"].as_object() {
+        for (node, info) in map {
+            let mut reasons = Vec::new();
+            let storages = string_list(&info["unavailable_storages"]);
+            if !storages.is_empty() {
+                reasons.push(tr!("Storage not available: {0}", storages.join(", ")));
+            }
+            let resources = string_list(&info["unavailable-resources"]);
+            if !resources.is_empty() {
+                reasons.push(tr!("Resources not available: {0}", resources.join(", ")));
+            }
+            if reasons.is_empty() {
+                reasons.push(tr!("Not allowed"));
+            }
+            not_allowed_nodes.push((node.clone(), reasons.join("; ")));
+        }
+    }
+
+    // LXC (and older QEMU) preconditions may not list the allowed nodes
+    let mut target_nodes = match data["allowed_nodes"].as_array() {
+        Some(_) => string_list(&data["allowed_nodes"]),
+        None => online_nodes
+            .into_iter()
+            .filter(|node| !not_allowed_nodes.iter().any(|(n, _)| n == node))
+            .collect(),
+    };
+    target_nodes.retain(|node| node != current_node);
+    target_nodes.sort();
+
+    MigrateInfo {
+        target_nodes,
+        not_allowed_nodes,
+        local_resources: string_list(&data["local_resources"]),
+        local_disks: string_list(&data["local_disks"]),
+        mapped_resources: string_list(&data["mapped-resources"]),
+    }
+}
+
+pub enum Msg {
+    LoadResult(Result<MigrateInfo, Error>),
+    LoadStorage(String),
+    LoadStorageResult(String, Result<Vec<StorageInfo>, Error>),
+    FormChange,
+    Migrate(FormContext),
+    MigrateResult(String, Result<String, Error>),
+}
+
+pub struct PveGuestMigrateDialog {
+    data: Option<Result<MigrateInfo, String>>,
+    load_guard: Option<AsyncAbortGuard>,
+    target_node: Option<String>,
+    storage_list: Vec<String>,
+    load_storage_guard: Option<AsyncAbortGuard>,
+    migrate_guard: Option<AsyncAbortGuard>,
+    form_ctx: FormContext,
+}
+
+fn warning_row(icon: &str, text: impl Into<Html>) -> Row {
+    Row::new()
+        .gap(1)
+        .class("pwt-align-items-center")
+        .with_child(Fa::new(icon).class("pwt-color-warning"))
+        .with_child(Container::new().with_child(text))
+}
+
+impl PveGuestMigrateDialog {
+    fn view_blockers(&self, ctx: &Context<Self>, info: &MigrateInfo) -> Option<Html> {
+        let props = ctx.props();
+        let mut list = Column::new().gap(1);
+        let mut empty = true;
+
+        if !info.local_resources.is_empty() {
+            empty = false;
+            list.add_child(
+                warning_row(
+                    "exclamation-triangle",
+                    tr!(
+                        "Can't migrate guest with local resources: {0}",
+                        info.local_resources.join(", ")
+                    ),
+                )
+                .class(pwt::css::FontColor::Error),
+            );
+        }
+        if !info.mapped_resources.is_empty() {
+            empty = false;
+            list.add_child(warning_row(
+                "info-circle",
+                tr!("Mapped resources: {0}", info.mapped_resources.join(", ")),
+            ));
+        }
+        if !info.local_disks.is_empty() {
+            empty = false;
+            let msg = if props.running {
+                tr!(
+                    "Local disks will be migrated (this can take a long time): {0}",
+                    info.local_disks.join(", ")
+                )
+            } else {
+                tr!("Local disks: {0}", info.local_disks.join(", "))
+            };
+            list.add_child(warning_row("hdd-o", msg));
+        }
+        for (node, reason) in &info.not_allowed_nodes {
+            empty = false;
+            list.add_child(warning_row("ban", format!("{node}: {reason}")));
+        }
+
+        (!empty).then(|| list.into())
+    }
+
+    fn create_migrate_panel(&self, ctx: &Context<Self>, info: &MigrateInfo) -> Html {
+        let props = ctx.props();
+
+        let blocked = !info.local_resources.is_empty();
+
+        let node_selector = info
+            .target_nodes
+            .iter()
+            .fold(Combobox::new(), |combo, node| combo.with_item(node.clone()))
+            .name("target")
+            .required(true)
+            .force_selection(true)
+            .default(info.target_nodes.first().cloned().unwrap_or_default());
+
+        let storage_selector = self
+            .storage_list
+            .iter()
+            .fold(Combobox::new(), |combo, storage| {
+                combo.with_item(storage.clone())
+            })
+            .name("targetstorage")
+            .force_selection(true)
+            .placeholder(tr!("Current layout"));
+
+        let mode_checkbox = match props.guest_type {
+            GuestType::Qemu => Checkbox::new()
+                .name("online")
+                .default(props.running)
+                .disabled(!props.running)
+                .box_label(tr!("Online (live migration)")),
+            GuestType::Lxc => Checkbox::new()
+                .name("restart")
+                .default(props.running)
+                .disabled(!props.running)
+                .box_label(tr!("Restart mode")),
+        };
+
+        let title = match props.guest_type {
+            GuestType::Qemu => tr!("Migrate VM {0}", props.vmid),
+            GuestType::Lxc => tr!("Migrate CT {0}", props.vmid),
+        };
+
+        let mut panel = Column::new()
+            .class(pwt::css::FlexFit)
+            .padding(2)
+            .gap(2)
+            .with_child(
+                Column::new()
+                    .gap(1)
+                    .with_child(
+                        Container::new()
+                            .class("pwt-font-size-title-large")
+                            .with_child(title),
+                    )
+                    .with_child(
+                        Container::new()
+                            .class("pwt-font-size-title-small")
+                            .with_child(tr!("Source node: {0}", props.node)),
+                    ),
+            )
+            .with_optional_child(self.view_blockers(ctx, info));
+
+        if info.target_nodes.is_empty() {
+            panel.add_child(pwt::widget::error_message(&tr!(
+                "No target node available."
+            )));
+        }
+
+        panel = panel
+            .with_child(label_field(tr!("Target Node"), node_selector, true))
+            .with_child(label_field(tr!("Target Storage"), storage_selector, true))
+            .with_child(mode_checkbox)
+            .with_child(
+                Row::new()
+                    .class(pwt::css::JustifyContent::Center)
+                    .with_child(
+                        SubmitButton::new()
+                            .check_dirty(false)
+                            .disabled(
+                                blocked
+                                    || info.target_nodes.is_empty()
+                                    || self.migrate_guard.is_some(),
+                            )
+                            .text(tr!("Migrate"))
+                            .icon_class("fa fa-paper-plane")
+                            .class("pwt-button-outline")
+                            .on_submit(ctx.link().callback(Msg::Migrate)),
+                    ),
+            );
+
+        Form::new()
+            .form_context(self.form_ctx.clone())
+            .class(pwt::css::FlexFit)
+            .with_child(panel)
+            .into()
+    }
+}
+
+impl Component for PveGuestMigrateDialog {
+    type Message = Msg;
+    type Properties = GuestMigrateDialog;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let props = ctx.props();
+        let url = format!(
+            "{}/migrate",
+            guest_base_url(&props.node, props.guest_type, props.vmid)
+        );
+        let current_node = props.node.to_string();
+
+        let link = ctx.link().clone();
+        let load_guard = AsyncAbortGuard::spawn(async move {
+            let result = async {
+                let data: Value = http_get(&url, None).await?;
+
+                let nodes: Vec<ClusterNodeIndexResponse> = http_get("/nodes", None).await?;
+                let online_nodes: Vec<String> = nodes
+                    .into_iter()
+                    .filter(|n| n.status == ClusterNodeIndexResponseStatus::Online)
+                    .map(|n| n.node)
+                    .collect();
+
+                Ok::<_, Error>(parse_migrate_info(&current_node, &data, online_nodes))
+            }
+            .await;
+            link.send_message(Msg::LoadResult(result));
+        });
+
+        Self {
+            data: None,
+            load_guard: Some(load_guard),
+            target_node: None,
+            storage_list: Vec::new(),
+            load_storage_guard: None,
+            migrate_guard: None,
+            form_ctx: FormContext::new().on_change(ctx.link().callback(|_| Msg::FormChange)),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let props = ctx.props();
+        match msg {
+            Msg::LoadResult(result) => {
+                self.load_guard = None;
+                if let Ok(info) = &result {
+                    if let Some(node) = info.target_nodes.first() {
+                        ctx.link().send_message(Msg::LoadStorage(node.clone()));
+                    }
+                }
+                self.data = Some(result.map_err(|err| err.to_string()));
+            }
+            Msg::FormChange => {
+                let node = self
+                    .form_ctx
+                    .read()
+                    .get_field_value("target")
+                    .and_then(|v| v.as_str().map(String::from));
+                if let Some(node) = node {
+                    if Some(&node) != self.target_node.as_ref() {
+                        ctx.link().send_message(Msg::LoadStorage(node));
+                    }
+                }
+                return false;
+            }
+            Msg::LoadStorage(node) => {
+                let content = match props.guest_type {
+                    GuestType::Qemu => "images",
+                    GuestType::Lxc => "rootdir",
+                };
+                self.target_node = Some(node.clone());
+                let url = format!("/nodes/{}/storage", percent_encode_component(&node));
+                let link = ctx.link().clone();
+                self.load_storage_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_get(&url, Some(json!({ "content": content }))).await;
+                    link.send_message(Msg::LoadStorageResult(node, result));
+                }));
+            }
+            Msg::LoadStorageResult(node, result) => {
+                if Some(&node) != self.target_node.as_ref() {
+                    return false;
+                }
+                match result {
+                    Ok(list) => {
+                        let mut list: Vec<String> = list
+                            .into_iter()
+                            .filter(|info| info.enabled.unwrap_or(true))
+                            .map(|info| info.storage)
+                            .collect();
+                        list.sort();
+                        self.storage_list = list;
+                    }
+                    Err(err) => crate::show_failed_command_error(ctx.link(), err),
+                }
+            }
+            Msg::Migrate(form_ctx) => {
+                let data = form_ctx.get_submit_data();
+                let target = match data["target"].as_str() {
+                    Some(target) => target.to_string(),
+                    None => return false,
+                };
+
+                let mut param = json!({ "target": target });
+                let target_storage = data["targetstorage"].as_str().filter(|s| !s.is_empty());
+
+                match props.guest_type {
+                    GuestType::Qemu => {
+                        if props.running && data["online"].as_bool().unwrap_or(false) {
+                            param["online"] = true.into();
+                        }
+                        if let Some(Ok(info)) = &self.data {
+                            if !info.local_disks.is_empty() {
+                                param["with-local-disks"] = true.into();
+                            }
+                        }
+                        if let Some(storage) = target_storage {
+                            param["targetstorage"] = storage.into();
+                        }
+                    }
+                    GuestType::Lxc => {
+                        if props.running && data["restart"].as_bool().unwrap_or(false) {
+                            param["restart"] = true.into();
+                        }
+                        if let Some(storage) = target_storage {
+                            param["target-storage"] = storage.into();
+                        }
+                    }
+                }
+
+                let url = format!(
+                    "{}/migrate",
+                    guest_base_url(&props.node, props.guest_type, props.vmid)
+                );
+                let link = ctx.link().clone();
+                self.migrate_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_post(&url, Some(param)).await;
+                    link.send_message(Msg::MigrateResult(target, result));
+                }));
+            }
+            Msg::MigrateResult(target, result) => {
+                self.migrate_guard = None;
+                match result {
+                    Ok(upid) => {
+                        if let Some(on_migrate) = &props.on_migrate {
+                            on_migrate.emit((target, upid));
+                        }
+                        if let Some(on_close) = &props.on_close {
+                            on_close.emit(());
+                        }
+                    }
+                    Err(err) => crate::show_failed_command_error(ctx.link(), err),
+                }
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+
+        let content = render_loaded_data(&self.data, |info| self.create_migrate_panel(ctx, info));
+
+        SideDialog::new()
+            .location(pwt::touch::SideDialogLocation::Bottom)
+            .on_close(props.on_close.clone())
+            .with_child(content)
+            .into()
+    }
+}
+
+impl From<GuestMigrateDialog> for VNode {
+    fn from(props: GuestMigrateDialog) -> Self {
+        let comp = VComp::new::<PveGuestMigrateDialog>(Rc::new(props), None);
+        VNode::from(comp)
+    }
+}
@@ -0,0 +1,375 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+use serde_json::{Value, json};
+
+use yew::html::IntoEventCallback;
+use yew::prelude::*;
+use yew::virtual_dom::{VComp, VNode};
+use yew_router::scope_ext::RouterScopeExt;
+
+use pwt::AsyncAbortGuard;
+use pwt::prelude::*;
+use pwt::touch::SideDialog;
+use pwt::widget::form::{Checkbox, Combobox, Form, FormContext, Number, SubmitButton};
+use pwt::widget::{Column, Container, Row};
+
+use pwt_macros::builder;
+
+use proxmox_yew_comp::layout::mobile_form::label_field;
+use proxmox_yew_comp::layout::render_loaded_data;
+use proxmox_yew_comp::{http_get, http_post, percent_encoding::percent_encode_component};
+
+use pve_api_types::StorageInfo;
+
+use crate::widgets::{GuestType, load_nodes_and_next_id};
+
+/// Detect the guest type and original VMID of a backup volume.
+///
+/// Handles both vzdump archive names (`backup/vzdump-qemu-100-...`) and
+/// Proxmox Backup Server snapshots (`backup/vm/100/...`).
+pub fn parse_backup_volid(volid: &str) -> Option<(GuestType, Option<u32>)> {
+    let path = volid.split_once(':').map(|(_, path)| path).unwrap_or(volid);
+    let name = path.rsplit('/').next().unwrap_or(path);
+
+    let parse_vmid = |text: &str| text.split(['-', '/']).next()?.parse::<u32>().ok();
+
+    if let Some(rest) = name.strip_prefix("vzdump-qemu-") {
+        return Some((GuestType::Qemu, parse_vmid(rest)));
+    }
+    if let Some(rest) = name.strip_prefix("vzdump-lxc-") {
+        return Some((GuestType::Lxc, parse_vmid(rest)));
+    }
+    if let Some(rest) = path.strip_prefix("backup/vm/") {
+        return Some((GuestType::Qemu, parse_vmid(rest)));
+    }
+    if let Some(rest) = path.strip_prefix("backup/ct/") {
+        return Some((GuestType::Lxc, parse_vmid(rest)));
+    }
+    None
+}
+
+#[derive(Clone, PartialEq, Properties)]
+#[builder]
+pub struct GuestRestoreDialog {
+    node: AttrValue,
+    volid: AttrValue,
+
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    #[prop_or_default]
+    /// Called when the dialog is closed
+    pub on_close: Option<Callback<()>>,
+}
+
+impl GuestRestoreDialog {
+    pub fn new(node: impl Into<AttrValue>, volid: impl Into<AttrValue>) -> Self {
+        yew::props!(Self {
+            node: node.into(),
+            volid: volid.into(),
+        })
+    }
+}
+
+pub enum Msg {
+    LoadResult(Result<(Vec<String>, u32), Error>),
+    LoadStorage(String),
+    LoadStorageResult(String, Result<Vec<StorageInfo>, Error>),
+    FormChange,
+    Restore(FormContext),
+    RestoreResult(String, Result<String, Error>),
+}
+
+pub struct PveGuestRestoreDialog {
+    guest_type: Option<GuestType>,
+    original_vmid: Option<u32>,
+    data: Option<Result<(Vec<String>, u32), String>>,
+    load_guard: Option<AsyncAbortGuard>,
+    // last state of the 'force' checkbox
+    force: bool,
+    storage_node: String,
+    storage_list: Vec<String>,
+    load_storage_guard: Option<AsyncAbortGuard>,
+    restore_guard: Option<AsyncAbortGuard>,
+    form_ctx: FormContext,
+}
+
+impl PveGuestRestoreDialog {
+    fn create_restore_panel(
+        &self,
+        ctx: &Context<Self>,
+        guest_type: GuestType,
+        (nodes, next_id): &(Vec<String>, u32),
+    ) -> Html {
+        let props = ctx.props();
+
+        let node_selector = nodes
+            .iter()
+            .fold(Combobox::new(), |combo, node| combo.with_item(node.clone()))
+            .name("node")
+            .required(true)
+            .force_selection(true)
+            .default(props.node.clone());
+
+        let storage_selector = self
+            .storage_list
+            .iter()
+            .fold(Combobox::new(), |combo, storage| {
+                combo.with_item(storage.clone())
+            })
+            .name("storage")
+            .force_selection(true)
+            .placeholder(tr!("From backup configuration"));
+
+        let mut title = match guest_type {
+            GuestType::Qemu => tr!("Restore VM"),
+            GuestType::Lxc => tr!("Restore CT"),
+        };
+        if let Some(vmid) = self.original_vmid {
+            title = format!("{title} ({})", tr!("Backup of {0}", vmid));
+        }
+
+        Form::new()
+            .form_context(self.form_ctx.clone())
+            .class(pwt::css::FlexFit)
+            .with_child(
+                Column::new()
+                    .class(pwt::css::FlexFit)
+                    .padding(2)
+                    .gap(2)
+                    .with_child(
+                        Column::new()
+                            .gap(1)
+                            .with_child(
+                                Container::new()
+                                    .class("pwt-font-size-title-large")
+                                    .with_child(title),
+                            )
+                            .with_child(
+                                Container::new()
+                                    .class("pwt-font-size-title-small")
+                                    .with_child(props.volid.clone()),
+                            ),
+                    )
+                    .with_child(label_field(tr!("Target Node"), node_selector, true))
+                    .with_child(label_field(tr!("Storage"), storage_selector, true))
+                    .with_child(label_field(
+                        tr!("VM/CT ID"),
+                        Number::<u32>::new()
+                            .name("vmid")
+                            .required(true)
+                            .min(100)
+                            .default(*next_id),
+                        true,
+                    ))
+                    .with_child(
+                        Checkbox::new()
+                            .name("force")
+                            .box_label(tr!("Overwrite existing guest")),
+                    )
+                    .with_child(
+                        Checkbox::new()
+                            .name("unique")
+                            .box_label(tr!("Unique (regenerate MAC addresses)")),
+                    )
+                    .with_child(
+                        Checkbox::new()
+                            .name("start")
+                            .box_label(tr!("Start after restore")),
+                    )
+                    .with_child(
+                        Row::new()
+                            .class(pwt::css::JustifyContent::Center)
+                            .with_child(
+                                SubmitButton::new()
+                                    .check_dirty(false)
+                                    .text(tr!("Restore"))
+                                    .icon_class("fa fa-undo")
+                                    .class("pwt-button-outline")
+                                    .on_submit(ctx.link().callback(Msg::Restore)),
+                            ),
+                    ),
+            )
+            .into()
+    }
+}
+
+impl Component for PveGuestRestoreDialog {
+    type Message = Msg;
+    type Properties = GuestRestoreDialog;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let props = ctx.props();
+        let (guest_type, original_vmid) = match parse_backup_volid(&props.volid) {
+            Some((guest_type, vmid)) => (Some(guest_type), vmid),
+            None => (None, None),
+        };
+
+        let link = ctx.link().clone();
+        let load_guard = AsyncAbortGuard::spawn(async move {
+            let result = load_nodes_and_next_id().await;
+            link.send_message(Msg::LoadResult(result));
+        });
+
+        ctx.link()
+            .send_message(Msg::LoadStorage(props.node.to_string()));
+
+        Self {
+            guest_type,
+            original_vmid,
+            data: None,
+            load_guard: Some(load_guard),
+            force: false,
+            storage_node: props.node.to_string(),
+            storage_list: Vec::new(),
+            load_storage_guard: None,
+            restore_guard: None,
+            form_ctx: FormContext::new().on_change(ctx.link().callback(|_| Msg::FormChange)),
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let props = ctx.props();
+        match msg {
+            Msg::LoadResult(result) => {
+                self.load_guard = None;
+                self.data = Some(result.map_err(|err| err.to_string()));
+            }
+            Msg::FormChange => {
+                let (node, force) = {
+                    let form = self.form_ctx.read();
+                    let node = form
+                        .get_field_value("node")
+                        .and_then(|v| v.as_str().map(String::from));
+                    let force = form.get_field_value("force").and_then(|v| v.as_bool());
+                    (node, force.unwrap_or(false))
+                };
+                if let Some(node) = node {
+                    if node != self.storage_node {
+                        // storages differ between nodes
+                        self.form_ctx
+                            .write()
+                            .set_field_value("storage", Value::Null);
+                        ctx.link().send_message(Msg::LoadStorage(node));
+                    }
+                }
+                if force != self.force {
+                    self.force = force;
+                    // overwriting usually means restoring the original guest
+                    let next_id = match &self.data {
+                        Some(Ok((_, next_id))) => Some(*next_id),
+                        _ => None,
+                    };
+                    let vmid = if force {
+                        self.original_vmid.or(next_id)
+                    } else {
+                        next_id
+                    };
+                    if let Some(vmid) = vmid {
+                        self.form_ctx.write().set_field_value("vmid", vmid.into());
+                    }
+                }
+                return false;
+            }
+            Msg::LoadStorage(node) => {
+                let content = match self.guest_type {
+                    Some(GuestType::Lxc) => "rootdir",
+                    _ => "images",
+                };
+                self.storage_node = node.clone();
+                let url = format!("/nodes/{}/storage", percent_encode_component(&node));
+                let link = ctx.link().clone();
+                self.load_storage_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_get(&url, Some(json!({ "content": content }))).await;
+                    link.send_message(Msg::LoadStorageResult(node, result));
+                }));
+            }
+            Msg::LoadStorageResult(node, result) => {
+                if node != self.storage_node {
+                    return false;
+                }
+                match result {
+                    Ok(list) => {
+                        let mut list: Vec<String> = list
+                            .into_iter()
+                            .filter(|info| info.enabled.unwrap_or(true))
+                            .map(|info| info.storage)
+                            .collect();
+                        list.sort();
+                        self.storage_list = list;
+                    }
+                    Err(err) => crate::show_failed_command_error(ctx.link(), err),
+                }
+            }
+            Msg::Restore(form_ctx) => {
+                let guest_type = match self.guest_type {
+                    Some(guest_type) => guest_type,
+                    None => return false,
+                };
+                let mut data = form_ctx.get_submit_data();
+                let node = match data["node"].as_str() {
+                    Some(node) => node.to_string(),
+                    None => return false,
+                };
+                if let Value::Object(map) = &mut data {
+                    map.remove("node");
+                }
+                match guest_type {
+                    GuestType::Qemu => {
+                        data["archive"] = props.volid.to_string().into();
+                    }
+                    GuestType::Lxc => {
+                        data["ostemplate"] = props.volid.to_string().into();
+                        data["restore"] = true.into();
+                    }
+                }
+
+                let url = format!("/nodes/{}/{}", percent_encode_component(&node), guest_type);
+                let link = ctx.link().clone();
+                self.restore_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_post(&url, Some(data)).await;
+                    link.send_message(Msg::RestoreResult(node, result));
+                }));
+            }
+            Msg::RestoreResult(nodename, result) => match result {
+                Ok(upid) => {
+                    if let Some(on_close) = &props.on_close {
+                        on_close.emit(());
+                    }
+                    if let Some(navigator) = ctx.link().navigator() {
+                        navigator.push(&crate::Route::NodeTaskStatus {
+                            nodename,
+                            upid,
+                            endtime: 0,
+                        });
+                    }
+                }
+                Err(err) => crate::show_failed_command_error(ctx.link(), err),
+            },
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+
+        let content: Html = match self.guest_type {
+            Some(guest_type) => render_loaded_data(&self.data, |data| {
+                self.create_restore_panel(ctx, guest_type, data)
+            }),
+            None => pwt::widget::error_message(&tr!("Unable to detect the backup type.")).into(),
+        };
+
+        SideDialog::new()
+            .location(pwt::touch::SideDialogLocation::Bottom)
+            .on_close(props.on_close.clone())
+            .with_child(content)
+            .into()
+    }
+}
+
+impl From<GuestRestoreDialog> for VNode {
+    fn from(props: GuestRestoreDialog) -> Self {
+        let comp = VComp::new::<PveGuestRestoreDialog>(Rc::new(props), None);
+        VNode::from(comp)
+    }
+}
@@ -0,0 +1,537 @@
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use anyhow::Error;
+use gloo_timers::callback::Timeout;
+use serde::Deserialize;
+use serde_json::json;
+
+use yew::prelude::*;
+use yew::virtual_dom::{VComp, VNode};
+use yew_router::scope_ext::RouterScopeExt;
+
+use pwt::AsyncAbortGuard;
+use pwt::prelude::*;
+use pwt::touch::{Fab, FabSize, SideDialog};
+use pwt::widget::form::{Checkbox, Field, Form, FormContext, SubmitButton, TextArea};
+use pwt::widget::{Button, Column, Container, Fa, List, ListTile, Row};
+
+use proxmox_yew_comp::layout::list_tile::title_subtitle_column;
+use proxmox_yew_comp::layout::mobile_form::label_field;
+use proxmox_yew_comp::layout::render_loaded_data;
+use proxmox_yew_comp::utils::render_epoch_short;
+use proxmox_yew_comp::{
+    ConfirmButton, http_delete_get, http_get, http_post, http_put,
+    percent_encoding::percent_encode_component,
+};
+
+use crate::widgets::{GuestType, TasksListButton, guest_base_url};
+
+#[derive(Clone, PartialEq, Properties)]
+pub struct GuestSnapshotPanel {
+    vmid: u32,
+    node: AttrValue,
+    guest_type: GuestType,
+}
+
+impl GuestSnapshotPanel {
+    pub fn new(node: impl Into<AttrValue>, guest_type: GuestType, vmid: u32) -> Self {
+        Self {
+            node: node.into(),
+            guest_type,
+            vmid,
+        }
+    }
+}
+
+// Snapshot list entry (`GET /nodes/{node}/{qemu|lxc}/{vmid}/snapshot`)
+#[derive(Clone, PartialEq, Deserialize)]
+struct SnapshotInfo {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    parent: Option<String>,
+    #[serde(default)]
+    snaptime: Option<i64>,
+    #[serde(default, deserialize_with = "proxmox_serde::perl::deserialize_bool")]
+    vmstate: bool,
+}
+
+// The API always returns a pseudo entry for the current state.
+const CURRENT: &str = "current";
+
+pub enum Msg {
+    Load,
+    LoadResult(Result<Vec<SnapshotInfo>, Error>),
+    ShowTakeSnapshotDialog(bool),
+    TakeSnapshot(FormContext),
+    Select(Option<SnapshotInfo>),
+    EditDescription(Option<SnapshotInfo>),
+    UpdateDescription(String, FormContext),
+    UpdateResult(Result<(), Error>),
+    Rollback(String),
+    Remove(String),
+    CommandResult(Result<String, Error>),
+}
+
+pub struct PveGuestSnapshotPanel {
+    data: Option<Result<Vec<(usize, SnapshotInfo)>, String>>,
+    reload_timeout: Option<Timeout>,
+    load_guard: Option<AsyncAbortGuard>,
+    cmd_guard: Option<AsyncAbortGuard>,
+    running_upid: Option<String>,
+    show_take_snapshot_dialog: bool,
+    form_context: FormContext,
+    selected: Option<SnapshotInfo>,
+    edit_description: Option<SnapshotInfo>,
+}
+
+/// Sort snapshots into tree order, returning the depth of each entry.
+fn snapshot_tree(list: Vec<SnapshotInfo>) -> Vec<(usize, SnapshotInfo)> {
+    let names: Vec<String> = list.iter().map(|s| s.name.clone()).collect();
+
+    let mut children: HashMap<Option<String>, Vec<SnapshotInfo>> = HashMap::new();
+    for item in list {
+        // entries with unknown parents are shown as roots
+        let parent = item.parent.clone().filter(|p| names.contains(p));
+        children.entry(parent).or_default().push(item);
+    }
+
+    for list in children.values_mut() {
+        list.sort_by(|a, b| {
+            // the current state is always the last child
+            (a.name == CURRENT)
+                .cmp(&(b.name == CURRENT))
+                .then(a.snaptime.cmp(&b.snaptime))
+                .then(a.name.cmp(&b.name))
+        });
+    }
+
+    fn add_children(
+        result: &mut Vec<(usize, SnapshotInfo)>,
+        children: &HashMap<Option<String>, Vec<SnapshotInfo>>,
+        parent: Option<String>,
+        depth: usize,
+    ) {
+        if let Some(list) = children.get(&parent) {
+            for item in list {
+                result.push((depth, item.clone()));
+                add_children(result, children, Some(item.name.clone()), depth + 1);
+            }
+        }
+    }
+
+    let mut result = Vec::new();
+    add_children(&mut result, &children, None, 0);
+    result
+}
+
+impl PveGuestSnapshotPanel {
+    fn snapshot_url(&self, ctx: &Context<Self>, name: Option<&str>) -> String {
+        let props = ctx.props();
+        let base = guest_base_url(&props.node, props.guest_type, props.vmid);
+        match name {
+            Some(name) => format!("{base}/snapshot/{}", percent_encode_component(name)),
+            None => format!("{base}/snapshot"),
+        }
+    }
+
+    fn guest_command(
+        &mut self,
+        ctx: &Context<Self>,
+        future: impl Future<Output = Result<String, Error>> + 'static,
+    ) {
+        let link = ctx.link().clone();
+        self.cmd_guard = Some(AsyncAbortGuard::spawn(async move {
+            let result = future.await;
+            link.send_message(Msg::CommandResult(result));
+        }));
+    }
+
+    fn create_take_snapshot_panel(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+
+        let mut column = Column::new()
+            .class(pwt::css::FlexFit)
+            .padding(2)
+            .gap(2)
+            .with_child(label_field(
+                tr!("Name"),
+                Field::new().name("snapname").required(true),
+                true,
+            ))
+            .with_child(label_field(
+                tr!("Description"),
+                TextArea::new().name("description"),
+                true,
+            ));
+
+        if props.guest_type == GuestType::Qemu {
+            column.add_child(
+                Checkbox::new()
+                    .name("vmstate")
+                    .box_label(tr!("Include RAM")),
+            );
+        }
+
+        column.add_child(
+            Row::new()
+                .class(pwt::css::JustifyContent::Center)
+                .with_child(
+                    SubmitButton::new()
+                        .check_dirty(false)
+                        .text(tr!("Take Snapshot"))
+                        .icon_class("fa fa-camera")
+                        .class("pwt-button-outline")
+                        .on_submit(ctx.link().callback(Msg::TakeSnapshot)),
+                ),
+        );
+
+        Form::new()
+            .form_context(self.form_context.clone())
+            .class(pwt::css::FlexFit)
+            .with_child(column)
+            .into()
+    }
+
+    fn create_description_panel(&self, ctx: &Context<Self>, snapshot: &SnapshotInfo) -> Html {
+        let name = snapshot.name.clone();
+        Form::new()
+            .class(pwt::css::FlexFit)
+            .with_child(
+                Column::new()
+                    .class(pwt::css::FlexFit)
+                    .padding(2)
+                    .gap(2)
+                    .with_child(
+                        Container::new()
+                            .class("pwt-font-size-title-large")
+                            .with_child(snapshot.name.clone()),
+                    )
+                    .with_child(label_field(
+                        tr!("Description"),
+                        TextArea::new()
+                            .name("description")
+                            .submit_empty(true)
+                            .default(snapshot.description.clone().unwrap_or_default()),
+                        true,
+                    ))
+                    .with_child(
+                        Row::new()
+                            .class(pwt::css::JustifyContent::Center)
+                            .with_child(
+                                SubmitButton::new()
+                                    .text(tr!("Update"))
+                                    .class("pwt-button-outline")
+                                    .on_submit(ctx.link().callback(move |form_ctx| {
+                                        Msg::UpdateDescription(name.clone(), form_ctx)
+                                    })),
+                            ),
+                    ),
+            )
+            .into()
+    }
+
+    fn create_action_panel(&self, ctx: &Context<Self>, snapshot: &SnapshotInfo) -> Html {
+        let name = snapshot.name.clone();
+
+        let subtitle = snapshot
+            .snaptime
+            .map(render_epoch_short)
+            .unwrap_or_default();
+
+        let mut column = Column::new()
+            .padding(2)
+            .gap(2)
+            .class(pwt::css::FlexFit)
+            .with_child(
+                Column::new()
+                    .gap(1)
+                    .with_child(
+                        Container::new()
+                            .class("pwt-font-size-title-large")
+                            .with_child(snapshot.name.clone()),
+                    )
+                    .with_child(
+                        Container::new()
+                            .class("pwt-font-size-title-small")
+                            .with_child(subtitle),
+                    ),
+            );
+
+        if let Some(description) = snapshot.description.as_deref().filter(|d| !d.is_empty()) {
+            column.add_child(
+                Container::new()
+                    .class("pwt-white-space-pre")
+                    .with_child(description.to_string()),
+            );
+        }
+
+        column.add_child(
+            ConfirmButton::new(tr!("Rollback"))
+                .confirm_message(tr!("Rollback to snapshot '{0}'?", snapshot.name))
+                .icon_class("fa fa-history")
+                .class("pwt-button-outline")
+                .on_activate(ctx.link().callback({
+                    let name = name.clone();
+                    move |_| Msg::Rollback(name.clone())
+                })),
+        );
+
+        column.add_child(
+            Button::new(tr!("Edit Description"))
+                .icon_class("fa fa-pencil")
+                .class("pwt-button-outline")
+                .on_activate(ctx.link().callback({
+                    let snapshot = snapshot.clone();
+                    move |_| Msg::EditDescription(Some(snapshot.clone()))
+                })),
+        );
+
+        column.add_child(
+            ConfirmButton::remove_entry(name.clone())
+                .icon_class("fa fa-trash-o")
+                .class("pwt-button-outline")
+                .on_activate(ctx.link().callback(move |_| Msg::Remove(name.clone()))),
+        );
+
+        column.into()
+    }
+
+    fn snapshot_tile(&self, ctx: &Context<Self>, depth: usize, item: &SnapshotInfo) -> ListTile {
+        let current = item.name == CURRENT;
+
+        let (icon, title) = if current {
+            (Fa::new("play").class("pwt-color-primary"), tr!("NOW"))
+        } else {
+            (Fa::new("history"), item.name.clone())
+        };
+
+        let subtitle = if current {
+            tr!("You are here!")
+        } else {
+            match (item.snaptime, item.description.as_deref()) {
+                (Some(time), Some(descr)) if !descr.is_empty() => {
+                    format!(
+                        "{} - {}",
+                        render_epoch_short(time),
+                        descr.lines().next().unwrap_or("")
+                    )
+                }
+                (Some(time), _) => render_epoch_short(time),
+                (None, Some(descr)) => descr.lines().next().unwrap_or("").to_string(),
+                (None, None) => String::new(),
+            }
+        };
+
+        let tile = ListTile::new()
+            .class(pwt::css::AlignItems::Center)
+            .with_child(icon.fixed_width().large().margin_start(depth * 2))
+            .with_child(title_subtitle_column(title, subtitle))
+            .with_child(if item.vmstate {
+                Fa::new("microchip").into()
+            } else {
+                html! {}
+            });
+
+        if current {
+            tile
+        } else {
+            tile.interactive(true).onclick(ctx.link().callback({
+                let item = item.clone();
+                move |_| Msg::Select(Some(item.clone()))
+            }))
+        }
+    }
+
+    fn task_button(&self, ctx: &Context<Self>) -> Html {
+        TasksListButton::new()
+            .running_upid(self.running_upid.clone())
+            .on_show_task_list({
+                let navigator = ctx.link().navigator().clone().unwrap();
+                let props = ctx.props();
+                let node = props.node.clone();
+                let vmid = props.vmid;
+                let guest_type = props.guest_type;
+                move |_| {
+                    let nodename = node.to_string();
+                    match guest_type {
+                        GuestType::Qemu => {
+                            navigator.push(&crate::Route::QemuTasks { vmid, nodename })
+                        }
+                        GuestType::Lxc => {
+                            navigator.push(&crate::Route::LxcTasks { vmid, nodename })
+                        }
+                    }
+                }
+            })
+            .into()
+    }
+
+    fn view_snapshots(&self, ctx: &Context<Self>, data: &[(usize, SnapshotInfo)]) -> Html {
+        let tiles: Vec<ListTile> = data
+            .iter()
+            .map(|(depth, item)| self.snapshot_tile(ctx, *depth, item))
+            .collect();
+
+        let fab = Fab::new("fa fa-camera")
+            .size(FabSize::Small)
+            .text(tr!("Take Snapshot"))
+            .class("pwt-position-absolute")
+            .style("right", "var(--pwt-spacer-2)")
+            .style("bottom", "var(--pwt-spacer-2)")
+            .on_activate(ctx.link().callback(|_| Msg::ShowTakeSnapshotDialog(true)));
+
+        let take_snapshot_dialog = self.show_take_snapshot_dialog.then(|| {
+            SideDialog::new()
+                .location(pwt::touch::SideDialogLocation::Bottom)
+                .on_close(ctx.link().callback(|_| Msg::ShowTakeSnapshotDialog(false)))
+                .with_child(self.create_take_snapshot_panel(ctx))
+        });
+
+        let action_dialog = self.selected.as_ref().map(|snapshot| {
+            SideDialog::new()
+                .location(pwt::touch::SideDialogLocation::Bottom)
+                .on_close(ctx.link().callback(|_| Msg::Select(None)))
+                .with_child(self.create_action_panel(ctx, snapshot))
+        });
+
+        let description_dialog = self.edit_description.as_ref().map(|snapshot| {
+            SideDialog::new()
+                .location(pwt::touch::SideDialogLocation::Bottom)
+                .on_close(ctx.link().callback(|_| Msg::EditDescription(None)))
+                .with_child(self.create_description_panel(ctx, snapshot))
+        });
+
+        Column::new()
+            .class(pwt::css::FlexFit)
+            .with_child(
+                Container::new()
+                    .padding(2)
+                    .with_child(self.task_button(ctx)),
+            )
+            .with_child(
+                List::from_tiles(tiles)
+                    .class(pwt::css::FlexFit)
+                    .grid_template_columns("auto 1fr auto")
+                    .border_top(true),
+            )
+            .with_child(fab)
+            .with_optional_child(take_snapshot_dialog)
+            .with_optional_child(action_dialog)
+            .with_optional_child(description_dialog)
+            .into()
+    }
+}
+
+impl Component for PveGuestSnapshotPanel {
+    type Message = Msg;
+    type Properties = GuestSnapshotPanel;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link().send_message(Msg::Load);
+        Self {
+            data: None,
+            reload_timeout: None,
+            load_guard: None,
+            cmd_guard: None,
+            running_upid: None,
+            show_take_snapshot_dialog: false,
+            form_context: FormContext::new(),
+            selected: None,
+            edit_description: None,
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        self.data = None;
+        ctx.link().send_message(Msg::Load);
+        true
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::Load => {
+                let link = ctx.link().clone();
+                let url = self.snapshot_url(ctx, None);
+                self.load_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_get(&url, None).await;
+                    link.send_message(Msg::LoadResult(result));
+                }));
+            }
+            Msg::LoadResult(result) => {
+                self.data = Some(result.map(snapshot_tree).map_err(|err| err.to_string()));
+                let link = ctx.link().clone();
+                self.reload_timeout = Some(Timeout::new(3000, move || {
+                    link.send_message(Msg::Load);
+                }));
+            }
+            Msg::ShowTakeSnapshotDialog(show) => {
+                self.show_take_snapshot_dialog = show;
+                if show {
+                    self.form_context = FormContext::new();
+                }
+            }
+            Msg::TakeSnapshot(form_context) => {
+                self.show_take_snapshot_dialog = false;
+                let url = self.snapshot_url(ctx, None);
+                let data = form_context.get_submit_data();
+                self.guest_command(ctx, async move { http_post(&url, Some(data)).await });
+            }
+            Msg::Select(selected) => {
+                self.selected = selected;
+            }
+            Msg::EditDescription(snapshot) => {
+                self.selected = None;
+                self.edit_description = snapshot;
+            }
+            Msg::UpdateDescription(name, form_context) => {
+                self.edit_description = None;
+                let url = format!("{}/config", self.snapshot_url(ctx, Some(&name)));
+                let data = form_context.get_submit_data();
+                let param = json!({ "description": data["description"].as_str().unwrap_or("") });
+                let link = ctx.link().clone();
+                self.cmd_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_put(&url, Some(param)).await;
+                    link.send_message(Msg::UpdateResult(result));
+                }));
+            }
+            Msg::UpdateResult(result) => match result {
+                Ok(()) => ctx.link().send_message(Msg::Load),
+                Err(err) => crate::show_failed_command_error(ctx.link(), err),
+            },
+            Msg::Rollback(name) => {
+                self.selected = None;
+                let url = format!("{}/rollback", self.snapshot_url(ctx, Some(&name)));
+                self.guest_command(ctx, async move { http_post(&url, None).await });
+            }
+            Msg::Remove(name) => {
+                self.selected = None;
+                let url = self.snapshot_url(ctx, Some(&name));
+                self.guest_command(ctx, async move { http_delete_get(&url, None).await });
+            }
+            Msg::CommandResult(result) => match result {
+                Ok(upid) => {
+                    self.running_upid = Some(upid);
+                }
+                Err(err) => {
+                    self.running_upid = None;
+                    crate::show_failed_command_error(ctx.link(), err);
+                }
+            },
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        render_loaded_data(&self.data, |data| self.view_snapshots(ctx, data))
+    }
+}
+
+impl From<GuestSnapshotPanel> for VNode {
+    fn from(props: GuestSnapshotPanel) -> Self {
+        let comp = VComp::new::<PveGuestSnapshotPanel>(Rc::new(props), None);
+        VNode::from(comp)
+    }
+}
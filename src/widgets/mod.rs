@@ -19,10 +19,297 @@ pub use volume_action_dialog::VolumeActionDialog;
 mod guest_backup_panel;
 pub use guest_backup_panel::GuestBackupPanel;
 
+mod guest_restore_dialog;
+pub use guest_restore_dialog::{GuestRestoreDialog, parse_backup_volid};
+
+mod guest_snapshot_panel;
+pub use guest_snapshot_panel::GuestSnapshotPanel;
+
+mod guest_migrate_dialog;
+pub use guest_migrate_dialog::GuestMigrateDialog;
+
+mod guest_clone_dialog;
+pub use guest_clone_dialog::GuestCloneDialog;
+
+mod guest_disk_action_dialog;
+pub use guest_disk_action_dialog::GuestDiskActionDialog;
+
+mod qemu_console_button;
+pub use qemu_console_button::QemuConsoleButton;
+
+mod rrd_graph_panel;
+pub use rrd_graph_panel::RrdGraphPanel;
+
+mod guest_ha_card;
+pub use guest_ha_card::GuestHaCard;
+
+mod guest_backup_jobs_card;
+pub use guest_backup_jobs_card::{GuestBackupJobsCard, backup_job_next_run, backup_job_selection};
+
+mod create_wizard;
+pub use create_wizard::{CreateWizardSpec, WizardState, create_wizard};
+
+mod edit_dialog;
+pub use edit_dialog::EditDialog;
+
+mod editable_property;
+pub use editable_property::{
+    EditableProperty, PropertyEditorState, RenderPropertyFn, RenderPropertyInputPanelFn,
+};
+
+mod property_list;
+pub use property_list::PropertyList;
+
+mod pending_property_list;
+pub use pending_property_list::{PendingPropertyList, pve_pending_config_array_to_objects};
+
+use std::collections::HashSet;
+
+use anyhow::Error;
+use serde_json::{Value, json};
+
 use pwt::prelude::*;
-use pwt::widget::{Card, Column, Fa, Progress, Row};
+use pwt::touch::{SideDialog, SideDialogLocation};
+use pwt::widget::form::FormContext;
+use pwt::widget::{Card, Column, Container, Fa, Progress, Row};
 
 use proxmox_human_byte::HumanByte;
+use proxmox_yew_comp::http_get;
+use proxmox_yew_comp::percent_encoding::percent_encode_component;
+use proxmox_yew_comp::pve_api_types::StorageEntry;
+use pve_api_types::{ClusterNodeIndexResponse, ClusterNodeIndexResponseStatus, StorageInfo};
+
+/// Guest type, used to build the guest specific API paths.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GuestType {
+    Qemu,
+    Lxc,
+}
+
+impl GuestType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GuestType::Qemu => "qemu",
+            GuestType::Lxc => "lxc",
+        }
+    }
+}
+
+impl std::fmt::Display for GuestType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Returns the API base path for a guest, i.e. `/nodes/{node}/{qemu|lxc}/{vmid}`.
+pub fn guest_base_url(node: &str, guest_type: GuestType, vmid: u32) -> String {
+    format!(
+        "/nodes/{}/{}/{}",
+        percent_encode_component(node),
+        guest_type,
+        vmid
+    )
+}
+
+/// Parse a PVE property string (`key=value,...`) into an ordered list of key/value pairs.
+///
+/// Values without key are stored with `default_key`.
+pub fn parse_property_string(text: &str, default_key: &str) -> Vec<(String, String)> {
+    text.split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(|part| match part.split_once('=') {
+            Some((key, value)) => (key.to_string(), value.to_string()),
+            None => (default_key.to_string(), part.to_string()),
+        })
+        .collect()
+}
+
+/// Create a PVE property string from a list of key/value pairs.
+///
+/// The `default_key` entry is written first and without key. Pass `None` for
+/// schemas without default key, so that all entries are written with key.
+pub fn format_property_string(list: &[(String, String)], default_key: Option<&str>) -> String {
+    let is_default = |key: &String| Some(key.as_str()) == default_key;
+    let mut parts: Vec<String> = list
+        .iter()
+        .filter(|(key, _)| is_default(key))
+        .map(|(_, value)| value.clone())
+        .collect();
+    parts.extend(
+        list.iter()
+            .filter(|(key, _)| !is_default(key))
+            .map(|(key, value)| format!("{key}={value}")),
+    );
+    parts.join(",")
+}
+
+/// Set (or remove, if `value` is `None`) a key in a parsed property string.
+pub fn set_property_value(list: &mut Vec<(String, String)>, key: &str, value: Option<String>) {
+    match (list.iter().position(|(k, _)| k == key), value) {
+        (Some(pos), Some(value)) => list[pos].1 = value,
+        (Some(pos), None) => {
+            list.remove(pos);
+        }
+        (None, Some(value)) => list.push((key.to_string(), value)),
+        (None, None) => {}
+    }
+}
+
+/// Returns the keys of the form `{prefix}{N}` (with `N < max`), sorted by `N`.
+pub fn indexed_keys(keys: &HashSet<String>, prefix: &str, max: usize) -> Vec<String> {
+    let mut list: Vec<(usize, &String)> = keys
+        .iter()
+        .filter_map(|key| {
+            let index: usize = key.strip_prefix(prefix)?.parse().ok()?;
+            (index < max).then_some((index, key))
+        })
+        .collect();
+    list.sort();
+    list.into_iter().map(|(_, key)| key.clone()).collect()
+}
+
+/// Returns the text of a form field, or `None` if the field is empty.
+pub fn field_string(form_ctx: &FormContext, name: &str) -> Option<String> {
+    match form_ctx.read().get_field_value(name) {
+        Some(Value::String(text)) if !text.is_empty() => Some(text),
+        Some(Value::Number(n)) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Returns `true` if a checkbox form field is checked.
+pub fn field_bool(form_ctx: &FormContext, name: &str) -> bool {
+    matches!(
+        form_ctx.read().get_field_value(name),
+        Some(Value::Bool(true))
+    )
+}
+
+/// Load the list of online nodes and the next free VMID.
+pub async fn load_nodes_and_next_id() -> Result<(Vec<String>, u32), Error> {
+    let nodes: Vec<ClusterNodeIndexResponse> = http_get("/nodes", None).await?;
+    let mut nodes: Vec<String> = nodes
+        .into_iter()
+        .filter(|n| n.status == ClusterNodeIndexResponseStatus::Online)
+        .map(|n| n.node)
+        .collect();
+    nodes.sort();
+
+    // the API returns the ID as string
+    let next_id: Value = http_get("/cluster/nextid", None).await?;
+    let next_id = match &next_id {
+        Value::String(id) => id.parse::<u32>()?,
+        id => id.as_u64().unwrap_or(100) as u32,
+    };
+    Ok((nodes, next_id))
+}
+
+/// Load the volume IDs of all ISO images available on a node.
+pub async fn load_iso_list(node: &str) -> Result<Vec<String>, Error> {
+    let storage_url = format!("/nodes/{}/storage", percent_encode_component(node));
+
+    let storage_list: Vec<StorageInfo> =
+        http_get(&storage_url, Some(json!({"content": "iso"}))).await?;
+    let mut iso_list = Vec::new();
+    for info in storage_list
+        .iter()
+        .filter(|info| info.enabled.unwrap_or(true))
+    {
+        let url = format!(
+            "{storage_url}/{}/content",
+            percent_encode_component(&info.storage)
+        );
+        let list: Vec<StorageEntry> = http_get(&url, Some(json!({"content": "iso"}))).await?;
+        iso_list.extend(list.into_iter().map(|item| item.volid));
+    }
+    iso_list.sort();
+
+    Ok(iso_list)
+}
+
+/// Load the names of all bridges (including OVS bridges) of a node.
+pub async fn load_bridge_list(node: &str) -> Result<Vec<String>, Error> {
+    let url = format!("/nodes/{}/network", percent_encode_component(node));
+    let interfaces: Vec<Value> = http_get(&url, Some(json!({"type": "any_bridge"}))).await?;
+    let mut bridges: Vec<String> = interfaces
+        .iter()
+        .filter_map(|iface| iface["iface"].as_str().map(String::from))
+        .collect();
+    bridges.sort();
+
+    Ok(bridges)
+}
+
+/// Text for a JSON value from the API, `-` if it is not set.
+pub fn value_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::from("-"),
+        Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// CSS class for buttons used as toggles (filled if active).
+pub fn toggle_button_class(active: bool) -> &'static str {
+    if active {
+        "pwt-button-filled"
+    } else {
+        "pwt-button-outline"
+    }
+}
+
+/// Health state, used to color a [health_label].
+#[derive(Copy, Clone, PartialEq)]
+pub enum HealthState {
+    Ok,
+    Warning,
+    Error,
+    Unknown,
+}
+
+/// Right aligned, colored health status text.
+pub fn health_label(state: HealthState, text: impl Into<AttrValue>) -> Container {
+    let text = text.into();
+    let container = Container::new().style("text-align", "end");
+    match state {
+        HealthState::Ok => container.class("pwt-color-primary").with_child(text),
+        HealthState::Warning => container
+            .class("pwt-color-warning")
+            .with_child(Fa::new("exclamation-triangle").padding_end(1))
+            .with_child(text),
+        HealthState::Error => container
+            .class("pwt-color-error")
+            .with_child(Fa::new("times-circle").padding_end(1))
+            .with_child(text),
+        HealthState::Unknown => container.style("opacity", "50%").with_child(text),
+    }
+}
+
+/// Bottom [SideDialog] with a fixed header and a scrollable content area.
+pub fn bottom_sheet(header: impl Into<Html>, content: impl Into<Html>) -> SideDialog {
+    SideDialog::new()
+        .location(SideDialogLocation::Bottom)
+        .with_child(
+            Column::new()
+                .style("max-height", "80dvh")
+                .with_child(header)
+                .with_child(
+                    Container::new()
+                        .class(pwt::css::FlexFit)
+                        .style("overflow", "auto")
+                        .with_child(content),
+                ),
+        )
+}
+
+/// Title used as [bottom_sheet] header.
+pub fn bottom_sheet_title(title: impl Into<Html>) -> Container {
+    Container::new()
+        .padding(2)
+        .class("pwt-font-size-title-large")
+        .with_child(title)
+}
 
 pub fn storage_card(
     storage: &str,
@@ -6,20 +6,23 @@ use gloo_timers::callback::Timeout;
 use pwt::touch::SnackBar;
 use serde_json::{json, Map, Value};
 
+use yew::html::{IntoEventCallback, IntoPropValue};
 use yew::virtual_dom::{VComp, VNode};
 
 use pwt::prelude::*;
 use pwt::props::{IntoOptionalInlineHtml, IntoSubmitCallback, SubmitCallback};
 use pwt::touch::SnackBarContextExt;
-use pwt::widget::{ActionIcon, Column, Fa, List, ListTile, Row};
+use pwt::widget::{ActionIcon, Column, Container, Fa, List, ListTile, Row};
 use pwt::AsyncAbortGuard;
 
+use proxmox_yew_comp::layout::list_tile::title_subtitle_column;
+use proxmox_yew_comp::layout::render_loaded_data;
 use proxmox_yew_comp::{ApiLoadCallback, IntoApiLoadCallback};
 
 use pwt_macros::builder;
 
-use crate::widgets::{title_subtitle_column, EditDialog, EditableProperty, PropertyList};
-use proxmox_yew_comp::pve_api_types::QemuPendingConfigValue;
+use crate::api_types::QemuPendingConfigValue;
+use crate::widgets::{EditDialog, EditableProperty, PropertyList};
 
 #[derive(Properties, Clone, PartialEq)]
 #[builder]
@@ -41,6 +44,16 @@ pub struct PendingPropertyList {
     #[builder_cb(IntoSubmitCallback, into_submit_callback, Value)]
     #[prop_or_default]
     pub on_submit: Option<SubmitCallback<Value>>,
+
+    /// Message shown above the list if there are pending changes.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub pending_hint: Option<AttrValue>,
+
+    /// Called with the set of configuration keys after each load.
+    #[builder_cb(IntoEventCallback, into_event_callback, HashSet<String>)]
+    #[prop_or_default]
+    pub on_load: Option<Callback<HashSet<String>>>,
 }
 
 impl PendingPropertyList {
@@ -205,8 +218,20 @@ impl PvePendingPropertyList {
                 .callback(move |_: Event| Msg::Revert(property.clone()))
         });
 
+        let action: Option<Html> = match (&property.action_icon, &property.on_action) {
+            (Some(icon), Some(on_action)) => {
+                let on_action = on_action.clone();
+                Some(
+                    ActionIcon::new(icon.clone())
+                        .on_activate(move |_| on_action.emit(()))
+                        .into(),
+                )
+            }
+            _ => None,
+        };
+
         let list_tile =
-            PendingPropertyList::render_list_tile(current, pending, property, (), on_revert);
+            PendingPropertyList::render_list_tile(current, pending, property, action, on_revert);
 
         if property.render_input_panel.is_some() {
             list_tile
@@ -244,8 +269,24 @@ impl PvePendingPropertyList {
             }
         }
 
+        // current and pending config only differ if there are pending changes
+        let pending_hint = props
+            .pending_hint
+            .as_ref()
+            .filter(|_| record != pending)
+            .map(|hint| {
+                Row::new()
+                    .padding(2)
+                    .gap(2)
+                    .class(pwt::css::AlignItems::Center)
+                    .class("pwt-color-warning")
+                    .with_child(Fa::new("exclamation-triangle"))
+                    .with_child(Container::new().with_child(hint.clone()))
+            });
+
         Column::new()
             .class(pwt::css::FlexFit)
+            .with_optional_child(pending_hint)
             .with_child(
                 List::from_tiles(tiles)
                     .virtual_scroll(Some(false))
@@ -339,6 +380,9 @@ impl Component for PvePendingPropertyList {
                     ),
                     Err(err) => Some(Err(err.to_string())),
                 };
+                if let (Some(Ok((_, _, keys))), Some(on_load)) = (&self.data, &props.on_load) {
+                    on_load.emit(keys.clone());
+                }
                 let link = ctx.link().clone();
                 self.reload_timeout = Some(Timeout::new(3000, move || {
                     link.send_message(Msg::Load);
@@ -355,7 +399,7 @@ impl Component for PvePendingPropertyList {
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        crate::widgets::render_loaded_data(&self.data, |data| self.view_property(ctx, data))
+        render_loaded_data(&self.data, |data| self.view_property(ctx, data))
     }
 }
 
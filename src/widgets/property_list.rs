@@ -11,11 +11,13 @@ use pwt::widget::{Column, Container, List, ListTile};
 use pwt::AsyncAbortGuard;
 
 use proxmox_yew_comp::utils::render_boolean;
+use proxmox_yew_comp::layout::list_tile::form_list_tile;
+use proxmox_yew_comp::layout::render_loaded_data;
 use proxmox_yew_comp::{ApiLoadCallback, IntoApiLoadCallback};
 
 use pwt_macros::builder;
 
-use crate::widgets::{form_list_tile, EditDialog, EditableProperty};
+use crate::widgets::{EditDialog, EditableProperty};
 
 #[derive(Properties, Clone, PartialEq)]
 #[builder]
@@ -207,7 +209,7 @@ impl Component for PvePropertyList {
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        crate::widgets::render_loaded_data(&self.data, |data| self.view_property(ctx, data))
+        render_loaded_data(&self.data, |data| self.view_property(ctx, data))
     }
 }
 
@@ -0,0 +1,288 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use wasm_bindgen::JsCast;
+
+use yew::prelude::*;
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::AsyncAbortGuard;
+use pwt::prelude::*;
+use pwt::props::StorageLocation;
+use pwt::state::PersistentState;
+use pwt::touch::SideDialog;
+use pwt::widget::menu::{Menu, MenuItem, SplitButton};
+
+use proxmox_yew_comp::percent_encoding::percent_encode_component;
+use proxmox_yew_comp::{ConsoleType, XTermJs, http_get, http_post};
+
+use crate::widgets::{GuestType, guest_base_url, parse_property_string};
+
+/// Console button for VMs, offering xterm.js, noVNC and SPICE.
+///
+/// The last used console type is remembered per VM. In automatic mode,
+/// xterm.js is used if the VM has a serial console (`serial0`), else noVNC.
+///
+/// The SPICE option is only shown if the display type supports SPICE (`qxl*`).
+/// It downloads a `.vv` connection file, which can be opened by SPICE clients.
+#[derive(Clone, PartialEq, Properties)]
+pub struct QemuConsoleButton {
+    node: AttrValue,
+    vmid: u32,
+}
+
+impl QemuConsoleButton {
+    pub fn new(node: impl Into<AttrValue>, vmid: u32) -> Self {
+        Self {
+            node: node.into(),
+            vmid,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum ConsoleMode {
+    #[default]
+    Auto,
+    XTermJs,
+    NoVnc,
+    NoVncEmbedded,
+    Spice,
+}
+
+pub enum Msg {
+    LoadResult(Result<Value, Error>),
+    SpiceProxyResult(Result<Value, Error>),
+    Open(ConsoleMode),
+    CloseEmbedded,
+}
+
+pub struct PveQemuConsoleButton {
+    preference: PersistentState<ConsoleMode>,
+    has_serial: bool,
+    has_spice: bool,
+    load_guard: Option<AsyncAbortGuard>,
+    spice_guard: Option<AsyncAbortGuard>,
+    show_embedded: bool,
+}
+
+impl PveQemuConsoleButton {
+    fn novnc_url(&self, ctx: &Context<Self>) -> String {
+        let props = ctx.props();
+        format!(
+            "/?console=kvm&novnc=1&vmid={}&node={}&resize=scale",
+            props.vmid,
+            percent_encode_component(&props.node),
+        )
+    }
+
+    fn open(&mut self, ctx: &Context<Self>, mode: ConsoleMode) {
+        let props = ctx.props();
+
+        match mode {
+            // fall back to the automatic selection if the display does not support SPICE
+            ConsoleMode::Spice if !self.has_spice => self.open(ctx, ConsoleMode::Auto),
+            ConsoleMode::Auto if self.has_serial => self.open(ctx, ConsoleMode::XTermJs),
+            ConsoleMode::Auto => self.open(ctx, ConsoleMode::NoVnc),
+            ConsoleMode::XTermJs => {
+                XTermJs::open_xterm_js_viewer(
+                    ConsoleType::KVM(props.vmid.into()),
+                    &props.node,
+                    true,
+                );
+            }
+            ConsoleMode::NoVnc => {
+                let url = self.novnc_url(ctx);
+                if let Err(err) = gloo_utils::window().open_with_url_and_target_and_features(
+                    &url,
+                    "_blank",
+                    "toolbar=no,location=no,status=no,menubar=no,resizable=yes",
+                ) {
+                    log::error!("unable to open noVNC console: {err:?}");
+                }
+            }
+            ConsoleMode::NoVncEmbedded => self.show_embedded = true,
+            ConsoleMode::Spice => {
+                let url = format!(
+                    "{}/spiceproxy",
+                    guest_base_url(&props.node, GuestType::Qemu, props.vmid)
+                );
+                // the proxy must be reachable by the client, so use the host we talk to
+                let proxy = gloo_utils::window().location().hostname().ok();
+                let param = proxy.map(|proxy| json!({ "proxy": proxy }));
+                let link = ctx.link().clone();
+                self.spice_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_post(&url, param).await;
+                    link.send_message(Msg::SpiceProxyResult(result));
+                }));
+            }
+        }
+    }
+}
+
+fn display_supports_spice(vga: &Value) -> bool {
+    let vga = vga.as_str().unwrap_or("");
+    let display_type = parse_property_string(vga, "type")
+        .into_iter()
+        .find_map(|(key, value)| (key == "type").then_some(value))
+        .unwrap_or_default();
+    display_type.starts_with("qxl")
+}
+
+/// Offer the spiceproxy result as `.vv` file (virt-viewer format) for download.
+fn download_spice_file(data: &Value) -> Result<(), Error> {
+    let Some(map) = data.as_object() else {
+        return Err(Error::msg(tr!("Got unexpected SPICE proxy data")));
+    };
+
+    let mut content = String::from("[virt-viewer]\n");
+    for (key, value) in map {
+        let value = match value {
+            Value::String(text) => text.clone(),
+            other => other.to_string(),
+        };
+        content.push_str(&format!("{key}={value}\n"));
+    }
+
+    let document = gloo_utils::document();
+    let link = document
+        .create_element("a")
+        .map_err(|err| Error::msg(format!("{err:?}")))?;
+    let href = format!(
+        "data:application/x-virt-viewer;charset=UTF-8,{}",
+        percent_encode_component(&content)
+    );
+    let _ = link.set_attribute("href", &href);
+    let _ = link.set_attribute("download", "pve-spice.vv");
+    link.dyn_into::<web_sys::HtmlElement>()
+        .map_err(|_| Error::msg("unable to create download link"))?
+        .click();
+
+    Ok(())
+}
+
+impl Component for PveQemuConsoleButton {
+    type Message = Msg;
+    type Properties = QemuConsoleButton;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let props = ctx.props();
+
+        let preference = PersistentState::new(StorageLocation::local(format!(
+            "pve-vm-{}-console-mode",
+            props.vmid
+        )));
+
+        let url = format!(
+            "{}/config",
+            guest_base_url(&props.node, GuestType::Qemu, props.vmid)
+        );
+        let link = ctx.link().clone();
+        let load_guard = AsyncAbortGuard::spawn(async move {
+            let result = http_get(&url, None).await;
+            link.send_message(Msg::LoadResult(result));
+        });
+
+        Self {
+            preference,
+            has_serial: false,
+            has_spice: false,
+            load_guard: Some(load_guard),
+            spice_guard: None,
+            show_embedded: false,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::LoadResult(result) => {
+                self.load_guard = None;
+                match result {
+                    Ok(config) => {
+                        self.has_serial = config["serial0"].is_string();
+                        self.has_spice = display_supports_spice(&config["vga"]);
+                    }
+                    Err(err) => log::error!("unable to load VM config: {err}"),
+                }
+            }
+            Msg::Open(mode) => {
+                self.preference.update(mode);
+                self.open(ctx, mode);
+            }
+            Msg::SpiceProxyResult(result) => {
+                self.spice_guard = None;
+                match result {
+                    Ok(data) => {
+                        if let Err(err) = download_spice_file(&data) {
+                            crate::show_failed_command_error(ctx.link(), err);
+                        }
+                    }
+                    Err(err) => crate::show_failed_command_error(ctx.link(), err),
+                }
+            }
+            Msg::CloseEmbedded => self.show_embedded = false,
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let preference = *self.preference;
+
+        let mut menu = Menu::new()
+            .with_item(
+                MenuItem::new(tr!("Automatic"))
+                    .on_select(ctx.link().callback(|_| Msg::Open(ConsoleMode::Auto))),
+            )
+            .with_item(
+                MenuItem::new("xterm.js")
+                    .on_select(ctx.link().callback(|_| Msg::Open(ConsoleMode::XTermJs))),
+            )
+            .with_item(
+                MenuItem::new(tr!("noVNC (new window)"))
+                    .on_select(ctx.link().callback(|_| Msg::Open(ConsoleMode::NoVnc))),
+            )
+            .with_item(
+                MenuItem::new(tr!("noVNC (embedded)")).on_select(
+                    ctx.link()
+                        .callback(|_| Msg::Open(ConsoleMode::NoVncEmbedded)),
+                ),
+            );
+
+        if self.has_spice {
+            menu.add_item(
+                MenuItem::new("SPICE")
+                    .disabled(self.spice_guard.is_some())
+                    .on_select(ctx.link().callback(|_| Msg::Open(ConsoleMode::Spice))),
+            );
+        }
+
+        let button = SplitButton::new(tr!("Console"))
+            .menu(menu)
+            .on_activate(ctx.link().callback(move |_| Msg::Open(preference)));
+
+        let embedded = self.show_embedded.then(|| {
+            let url = self.novnc_url(ctx);
+            SideDialog::new()
+                .location(pwt::touch::SideDialogLocation::Bottom)
+                .on_close(ctx.link().callback(|_| Msg::CloseEmbedded))
+                .with_child(html! {
+                    <iframe
+                        src={url}
+                        style="border: 0; width: 100%; height: 80dvh;"
+                        allowfullscreen=true
+                    />
+                })
+        });
+
+        html! {<>{button}{for embedded}</>}
+    }
+}
+
+impl From<QemuConsoleButton> for VNode {
+    fn from(props: QemuConsoleButton) -> Self {
+        let comp = VComp::new::<PveQemuConsoleButton>(Rc::new(props), None);
+        VNode::from(comp)
+    }
+}
@@ -0,0 +1,575 @@
+use std::rc::Rc;
+
+use anyhow::Error;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use wasm_bindgen::JsCast;
+
+use yew::prelude::*;
+use yew::virtual_dom::{VComp, VNode};
+
+use pwt::AsyncAbortGuard;
+use pwt::prelude::*;
+use pwt::props::StorageLocation;
+use pwt::state::PersistentState;
+use pwt::widget::{Button, Column, Container, MiniScroll, MiniScrollMode, Row};
+
+use proxmox_human_byte::HumanByte;
+use proxmox_yew_comp::layout::card::standard_card;
+use proxmox_yew_comp::layout::render_loaded_data;
+use proxmox_yew_comp::utils::render_epoch_short;
+use proxmox_yew_comp::{http_get, percent_encoding::percent_encode_component};
+
+use crate::widgets::{GuestType, guest_base_url, toggle_button_class};
+
+const CHART_WIDTH: f64 = 400.0;
+const CHART_HEIGHT: f64 = 120.0;
+const SERIES_COLORS: &[&str] = &[
+    "var(--pwt-color-primary, #1976d2)",
+    "var(--pwt-color-tertiary, #ef6c00)",
+];
+
+#[derive(Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum RrdTimeframe {
+    #[default]
+    Hour,
+    Day,
+    Week,
+    Month,
+    Year,
+}
+
+impl RrdTimeframe {
+    const ALL: [RrdTimeframe; 5] = [
+        RrdTimeframe::Hour,
+        RrdTimeframe::Day,
+        RrdTimeframe::Week,
+        RrdTimeframe::Month,
+        RrdTimeframe::Year,
+    ];
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            RrdTimeframe::Hour => "hour",
+            RrdTimeframe::Day => "day",
+            RrdTimeframe::Week => "week",
+            RrdTimeframe::Month => "month",
+            RrdTimeframe::Year => "year",
+        }
+    }
+
+    fn label(&self) -> String {
+        match self {
+            RrdTimeframe::Hour => tr!("Hour"),
+            RrdTimeframe::Day => tr!("Day"),
+            RrdTimeframe::Week => tr!("Week"),
+            RrdTimeframe::Month => tr!("Month"),
+            RrdTimeframe::Year => tr!("Year"),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub enum RrdConsolidation {
+    #[default]
+    Average,
+    Max,
+}
+
+impl RrdConsolidation {
+    fn as_str(&self) -> &'static str {
+        match self {
+            RrdConsolidation::Average => "AVERAGE",
+            RrdConsolidation::Max => "MAX",
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq)]
+pub enum RrdUnit {
+    /// Fraction, displayed as percentage
+    Percent,
+    Bytes,
+    BytesPerSecond,
+    Number,
+}
+
+impl RrdUnit {
+    fn format(&self, value: f64) -> String {
+        match self {
+            RrdUnit::Percent => format!("{:.1}%", value * 100.0),
+            RrdUnit::Bytes => HumanByte::new_binary(value).to_string(),
+            RrdUnit::BytesPerSecond => format!("{}/s", HumanByte::new_binary(value)),
+            RrdUnit::Number => format!("{value:.2}"),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+pub struct RrdChartDef {
+    title: String,
+    unit: RrdUnit,
+    // (label, rrd data key)
+    series: Vec<(String, &'static str)>,
+}
+
+impl RrdChartDef {
+    fn new(title: String, unit: RrdUnit, series: Vec<(String, &'static str)>) -> Self {
+        Self {
+            title,
+            unit,
+            series,
+        }
+    }
+}
+
+/// Historical performance charts from the `rrddata` API endpoints.
+///
+/// Timeframe and consolidation function are selectable and shared
+/// between all panels.
+#[derive(Clone, PartialEq, Properties)]
+pub struct RrdGraphPanel {
+    base_url: AttrValue,
+    charts: Vec<RrdChartDef>,
+}
+
+impl RrdGraphPanel {
+    /// Charts for a VM or container.
+    pub fn guest(node: impl Into<AttrValue>, guest_type: GuestType, vmid: u32) -> Self {
+        let node = node.into();
+        let mut charts = vec![
+            RrdChartDef::new(
+                tr!("CPU usage"),
+                RrdUnit::Percent,
+                vec![(tr!("CPU"), "cpu")],
+            ),
+            RrdChartDef::new(
+                tr!("Memory usage"),
+                RrdUnit::Bytes,
+                vec![(tr!("Total"), "maxmem"), (tr!("Used"), "mem")],
+            ),
+            RrdChartDef::new(
+                tr!("Network traffic"),
+                RrdUnit::BytesPerSecond,
+                vec![(tr!("In"), "netin"), (tr!("Out"), "netout")],
+            ),
+            RrdChartDef::new(
+                tr!("Disk IO"),
+                RrdUnit::BytesPerSecond,
+                vec![(tr!("Read"), "diskread"), (tr!("Write"), "diskwrite")],
+            ),
+        ];
+        if guest_type == GuestType::Lxc {
+            // VMs do not report disk usage
+            charts.push(RrdChartDef::new(
+                tr!("Disk usage"),
+                RrdUnit::Bytes,
+                vec![(tr!("Total"), "maxdisk"), (tr!("Used"), "disk")],
+            ));
+        }
+        Self {
+            base_url: guest_base_url(&node, guest_type, vmid).into(),
+            charts,
+        }
+    }
+
+    /// Charts for a cluster node.
+    pub fn node(node: impl Into<AttrValue>) -> Self {
+        let node = node.into();
+        Self {
+            base_url: format!("/nodes/{}", percent_encode_component(&node)).into(),
+            charts: vec![
+                RrdChartDef::new(
+                    tr!("CPU usage"),
+                    RrdUnit::Percent,
+                    vec![(tr!("CPU"), "cpu"), (tr!("IO delay"), "iowait")],
+                ),
+                RrdChartDef::new(
+                    tr!("Server load"),
+                    RrdUnit::Number,
+                    vec![(tr!("Load average"), "loadavg")],
+                ),
+                RrdChartDef::new(
+                    tr!("Memory usage"),
+                    RrdUnit::Bytes,
+                    vec![(tr!("Total"), "memtotal"), (tr!("Used"), "memused")],
+                ),
+                RrdChartDef::new(
+                    tr!("Network traffic"),
+                    RrdUnit::BytesPerSecond,
+                    vec![(tr!("In"), "netin"), (tr!("Out"), "netout")],
+                ),
+                RrdChartDef::new(
+                    tr!("Root Filesystem"),
+                    RrdUnit::Bytes,
+                    vec![(tr!("Total"), "roottotal"), (tr!("Used"), "rootused")],
+                ),
+            ],
+        }
+    }
+
+    /// Usage chart for a storage.
+    pub fn storage(node: impl Into<AttrValue>, storage: impl Into<AttrValue>) -> Self {
+        let node = node.into();
+        let storage = storage.into();
+        Self {
+            base_url: format!(
+                "/nodes/{}/storage/{}",
+                percent_encode_component(&node),
+                percent_encode_component(&storage)
+            )
+            .into(),
+            charts: vec![RrdChartDef::new(
+                tr!("Usage"),
+                RrdUnit::Bytes,
+                vec![(tr!("Total"), "total"), (tr!("Used"), "used")],
+            )],
+        }
+    }
+}
+
+pub enum Msg {
+    Load,
+    LoadResult(Result<Vec<Value>, Error>),
+    SetTimeframe(RrdTimeframe),
+    SetConsolidation(RrdConsolidation),
+}
+
+pub struct PveRrdGraphPanel {
+    timeframe: PersistentState<RrdTimeframe>,
+    consolidation: PersistentState<RrdConsolidation>,
+    data: Option<Result<Rc<Vec<Value>>, String>>,
+    load_guard: Option<AsyncAbortGuard>,
+}
+
+impl PveRrdGraphPanel {
+    fn view_selector(&self, ctx: &Context<Self>) -> Html {
+        let mut row = Row::new().gap(1).padding_y(1);
+
+        for timeframe in RrdTimeframe::ALL {
+            row.add_child(
+                Button::new(timeframe.label())
+                    .class(toggle_button_class(*self.timeframe == timeframe))
+                    .on_activate(ctx.link().callback(move |_| Msg::SetTimeframe(timeframe))),
+            );
+        }
+
+        let max = *self.consolidation == RrdConsolidation::Max;
+        row.add_child(
+            Button::new(tr!("Maximum"))
+                .class(toggle_button_class(max))
+                .on_activate(ctx.link().callback(move |_| {
+                    Msg::SetConsolidation(if max {
+                        RrdConsolidation::Average
+                    } else {
+                        RrdConsolidation::Max
+                    })
+                })),
+        );
+
+        MiniScroll::new(row)
+            .scroll_mode(MiniScrollMode::Native)
+            .class(pwt::css::Flex::None)
+            .into()
+    }
+
+    fn view_charts(&self, ctx: &Context<Self>, data: &Rc<Vec<Value>>) -> Html {
+        let props = ctx.props();
+
+        let time: Rc<Vec<i64>> = Rc::new(
+            data.iter()
+                .map(|item| item["time"].as_i64().unwrap_or(0))
+                .collect(),
+        );
+
+        let mut column = Column::new().gap(2);
+
+        for chart in &props.charts {
+            let series = chart
+                .series
+                .iter()
+                .map(|(label, key)| {
+                    let values: Vec<Option<f64>> =
+                        data.iter().map(|item| item[*key].as_f64()).collect();
+                    (AttrValue::from(label.clone()), Rc::new(values))
+                })
+                .collect();
+
+            column.add_child(
+                standard_card(chart.title.clone(), (), ()).with_child(RrdChart {
+                    unit: chart.unit,
+                    time: time.clone(),
+                    series,
+                }),
+            );
+        }
+
+        column.into()
+    }
+}
+
+impl Component for PveRrdGraphPanel {
+    type Message = Msg;
+    type Properties = RrdGraphPanel;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        ctx.link().send_message(Msg::Load);
+        Self {
+            timeframe: PersistentState::new(StorageLocation::local("pve-rrd-timeframe")),
+            consolidation: PersistentState::new(StorageLocation::local("pve-rrd-consolidation")),
+            data: None,
+            load_guard: None,
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let props = ctx.props();
+        match msg {
+            Msg::Load => {
+                let url = format!("{}/rrddata", props.base_url);
+                let param = json!({
+                    "timeframe": self.timeframe.as_str(),
+                    "cf": self.consolidation.as_str(),
+                });
+                let link = ctx.link().clone();
+                self.load_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_get(&url, Some(param)).await;
+                    link.send_message(Msg::LoadResult(result));
+                }));
+            }
+            Msg::LoadResult(result) => {
+                self.load_guard = None;
+                self.data = Some(result.map(Rc::new).map_err(|err| err.to_string()));
+            }
+            Msg::SetTimeframe(timeframe) => {
+                self.timeframe.update(timeframe);
+                ctx.link().send_message(Msg::Load);
+            }
+            Msg::SetConsolidation(consolidation) => {
+                self.consolidation.update(consolidation);
+                ctx.link().send_message(Msg::Load);
+            }
+        }
+        true
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
+        if ctx.props().base_url != old_props.base_url {
+            self.data = None;
+            ctx.link().send_message(Msg::Load);
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let charts = render_loaded_data(&self.data, |data| self.view_charts(ctx, data));
+
+        Column::new()
+            .with_child(self.view_selector(ctx))
+            .with_child(charts)
+            .into()
+    }
+}
+
+impl From<RrdGraphPanel> for VNode {
+    fn from(props: RrdGraphPanel) -> Self {
+        let comp = VComp::new::<PveRrdGraphPanel>(Rc::new(props), None);
+        VNode::from(comp)
+    }
+}
+
+/// A simple SVG line chart.
+///
+/// Tapping or dragging over the chart shows the values at that time.
+#[derive(Clone, PartialEq, Properties)]
+struct RrdChart {
+    unit: RrdUnit,
+    time: Rc<Vec<i64>>,
+    series: Vec<(AttrValue, Rc<Vec<Option<f64>>>)>,
+}
+
+enum ChartMsg {
+    Cursor(Option<usize>),
+}
+
+struct PveRrdChart {
+    cursor: Option<usize>,
+}
+
+fn svg_path(values: &[Option<f64>], max: f64) -> String {
+    let step = CHART_WIDTH / (values.len().max(2) - 1) as f64;
+    let mut path = String::new();
+    let mut pen_down = false;
+    for (i, value) in values.iter().enumerate() {
+        match value {
+            Some(value) => {
+                let x = i as f64 * step;
+                let y = CHART_HEIGHT - (value / max) * CHART_HEIGHT;
+                let cmd = if pen_down { 'L' } else { 'M' };
+                path.push_str(&format!("{cmd}{x:.1} {y:.1} "));
+                pen_down = true;
+            }
+            None => pen_down = false,
+        }
+    }
+    path
+}
+
+impl Component for PveRrdChart {
+    type Message = ChartMsg;
+    type Properties = RrdChart;
+
+    fn create(_ctx: &Context<Self>) -> Self {
+        Self { cursor: None }
+    }
+
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            ChartMsg::Cursor(cursor) => {
+                if self.cursor == cursor {
+                    return false;
+                }
+                self.cursor = cursor;
+            }
+        }
+        true
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let props = ctx.props();
+        let count = props.time.len();
+
+        if count == 0 {
+            return Container::new()
+                .padding(2)
+                .with_child(tr!("No data"))
+                .into();
+        }
+
+        let max = props
+            .series
+            .iter()
+            .flat_map(|(_, values)| values.iter().flatten())
+            .fold(0.0f64, |max, value| max.max(*value));
+        let max = if max > 0.0 { max } else { 1.0 };
+
+        let mut paths = Vec::new();
+        for (i, (_, values)) in props.series.iter().enumerate() {
+            let color = SERIES_COLORS[i % SERIES_COLORS.len()];
+            paths.push(html! {
+                <path
+                    d={svg_path(values, max)}
+                    fill="none"
+                    stroke={color}
+                    stroke-width="1.5"
+                    vector-effect="non-scaling-stroke"
+                />
+            });
+        }
+
+        let cursor_line = self.cursor.map(|index| {
+            let x = index as f64 * CHART_WIDTH / (count.max(2) - 1) as f64;
+            html! {
+                <line
+                    x1={x.to_string()}
+                    x2={x.to_string()}
+                    y1="0"
+                    y2={CHART_HEIGHT.to_string()}
+                    stroke="currentColor"
+                    stroke-opacity="0.5"
+                    vector-effect="non-scaling-stroke"
+                />
+            }
+        });
+
+        let onpointermove = ctx.link().batch_callback(move |event: PointerEvent| {
+            let target = event
+                .current_target()?
+                .dyn_into::<web_sys::Element>()
+                .ok()?;
+            let rect = target.get_bounding_client_rect();
+            if rect.width() <= 0.0 {
+                return None;
+            }
+            let fraction = ((event.client_x() as f64 - rect.left()) / rect.width()).clamp(0.0, 1.0);
+            let index = (fraction * (count - 1) as f64).round() as usize;
+            Some(ChartMsg::Cursor(Some(index)))
+        });
+
+        let svg = html! {
+            <svg
+                viewBox={format!("0 0 {CHART_WIDTH} {CHART_HEIGHT}")}
+                preserveAspectRatio="none"
+                style="width: 100%; height: 8em; touch-action: pan-y;"
+                onpointerdown={onpointermove.clone()}
+                onpointermove={onpointermove}
+                onpointerleave={ctx.link().callback(|_| ChartMsg::Cursor(None))}
+            >
+                <line
+                    x1="0" x2={CHART_WIDTH.to_string()}
+                    y1={(CHART_HEIGHT / 2.0).to_string()} y2={(CHART_HEIGHT / 2.0).to_string()}
+                    stroke="currentColor"
+                    stroke-opacity="0.15"
+                    vector-effect="non-scaling-stroke"
+                />
+                <line
+                    x1="0" x2={CHART_WIDTH.to_string()}
+                    y1={CHART_HEIGHT.to_string()} y2={CHART_HEIGHT.to_string()}
+                    stroke="currentColor"
+                    stroke-opacity="0.3"
+                    vector-effect="non-scaling-stroke"
+                />
+                {for paths}
+                {cursor_line}
+            </svg>
+        };
+
+        // values at the cursor position, or the latest values
+        let index = self.cursor.unwrap_or(count - 1).min(count - 1);
+
+        let mut legend = Row::new().gap(2).class(pwt::css::FlexWrap::Wrap);
+        for (i, (label, values)) in props.series.iter().enumerate() {
+            let color = SERIES_COLORS[i % SERIES_COLORS.len()];
+            let value = values
+                .get(index)
+                .copied()
+                .flatten()
+                .map(|value| props.unit.format(value))
+                .unwrap_or_else(|| String::from("-"));
+            legend.add_child(
+                Row::new()
+                    .gap(1)
+                    .class(pwt::css::AlignItems::Center)
+                    .with_child(
+                        Container::new()
+                            .style("width", "0.75em")
+                            .style("height", "0.75em")
+                            .style("border-radius", "50%")
+                            .style("background-color", color),
+                    )
+                    .with_child(format!("{label}: {value}")),
+            );
+        }
+
+        Column::new()
+            .padding(2)
+            .gap(1)
+            .with_child(
+                Row::new()
+                    .class("pwt-font-size-label-medium")
+                    .with_child(format!("{}: {}", tr!("Maximum"), props.unit.format(max)))
+                    .with_flex_spacer()
+                    .with_child(render_epoch_short(props.time[index])),
+            )
+            .with_child(svg)
+            .with_child(legend)
+            .into()
+    }
+}
+
+impl From<RrdChart> for VNode {
+    fn from(props: RrdChart) -> Self {
+        let comp = VComp::new::<PveRrdChart>(Rc::new(props), None);
+        VNode::from(comp)
+    }
+}
@@ -21,7 +21,7 @@ use proxmox_yew_comp::{http_delete_get, http_get, percent_encoding::percent_enco
 
 use pve_api_types::StorageContent;
 
-use crate::widgets::VolumeActionDialog;
+use crate::widgets::{GuestRestoreDialog, VolumeActionDialog};
 use proxmox_yew_comp::pve_api_types::StorageEntry;
 
 #[derive(Clone, PartialEq, Properties)]
@@ -72,6 +72,7 @@ pub enum Msg {
     ExtractConfig(String),
     ExtractConfigResult(Result<String, Error>),
     CloseConfigDialog,
+    ShowRestoreDialog(Option<String>),
 }
 
 pub struct PveStorageContentPanel {
@@ -81,6 +82,7 @@ pub struct PveStorageContentPanel {
     remove_guard: Option<AsyncAbortGuard>,
     extract_config_guard: Option<AsyncAbortGuard>,
     config_dialog: Option<Html>,
+    restore_volid: Option<String>,
 }
 
 fn get_content_icon(content: &str) -> &str {
@@ -122,6 +124,8 @@ impl PveStorageContentPanel {
     }
 
     fn view_content(&self, ctx: &Context<Self>, data: &[StorageEntry]) -> Html {
+        let props = ctx.props();
+
         let search = Row::new().padding_x(2).padding_bottom(2).with_child({
             let mut field = Field::new()
                 .value(self.filter.clone())
@@ -153,6 +157,10 @@ impl PveStorageContentPanel {
             .with_child(search)
             .with_child(self.view_list(ctx, &data))
             .with_optional_child(self.config_dialog.clone())
+            .with_optional_child(self.restore_volid.as_ref().map(|volid| {
+                GuestRestoreDialog::new(props.node.clone(), volid.clone())
+                    .on_close(ctx.link().callback(|_| Msg::ShowRestoreDialog(None)))
+            }))
             .into()
     }
 }
@@ -170,6 +178,7 @@ impl Component for PveStorageContentPanel {
             extract_config_guard: None,
             filter: String::new(),
             config_dialog: None,
+            restore_volid: None,
         }
     }
 
@@ -215,6 +224,10 @@ impl Component for PveStorageContentPanel {
                         let volid = volid.clone();
                         move |_| Msg::Remove(volid.clone())
                     }))
+                    .on_restore(ctx.link().callback({
+                        let volid = volid.clone();
+                        move |_| Msg::ShowRestoreDialog(Some(volid.clone()))
+                    }))
                     .on_show_config(
                         ctx.link()
                             .callback(move |_| Msg::ExtractConfig(volid.clone())),
@@ -271,6 +284,9 @@ impl Component for PveStorageContentPanel {
             Msg::CloseConfigDialog => {
                 self.config_dialog = None;
             }
+            Msg::ShowRestoreDialog(volid) => {
+                self.restore_volid = volid;
+            }
         }
         true
     }
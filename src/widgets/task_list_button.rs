@@ -34,6 +34,11 @@ pub struct TasksListButton {
     #[prop_or_default]
     /// Called to show the task list
     pub on_show_task_list: Option<Callback<MouseEvent>>,
+
+    #[builder_cb(IntoEventCallback, into_event_callback, (String, Option<String>))]
+    #[prop_or_default]
+    /// Called with the UPID and exit status when the running task finished
+    pub on_task_finished: Option<Callback<(String, Option<String>)>>,
 }
 
 impl TasksListButton {
@@ -143,6 +148,9 @@ impl Component for ProxmoxTaskListButton {
                         Ok(status) => status.exitstatus,
                         Err(_) => None,
                     };
+                    if let Some(on_task_finished) = &ctx.props().on_task_finished {
+                        on_task_finished.emit((upid.to_string(), exit_status.clone()));
+                    }
                     self.last_task_status = Some(format!(
                         "{}: {task_descr} ({})",
                         tr!("Finished"),
@@ -1,21 +1,27 @@
+use std::collections::HashSet;
 use std::rc::Rc;
 
 use anyhow::Error;
-use yew::html::IntoEventCallback;
+use gloo_timers::callback::Timeout;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::JsCast;
+use yew::html::{IntoEventCallback, IntoPropValue};
 use yew::prelude::*;
 use yew::virtual_dom::{VComp, VNode};
 
-use pwt::prelude::*;
-use pwt::widget::{Column, Container, Fa, List, ListTile};
 use pwt::AsyncAbortGuard;
+use pwt::prelude::*;
+use pwt::props::StorageLocation;
+use pwt::state::PersistentState;
+use pwt::touch::SideDialog;
+use pwt::widget::form::{Checkbox, Field};
+use pwt::widget::{ActionIcon, Column, Container, Fa, List, ListTile, Panel, Row};
 
 use pwt_macros::builder;
 
-// fixme: implement filter
-// fixme: implement reload on scroll down
-
 use proxmox_yew_comp::http_get;
 use proxmox_yew_comp::layout::render_loaded_data;
+use proxmox_yew_comp::percent_encoding::percent_encode_component;
 use proxmox_yew_comp::utils::{format_upid, render_epoch_short};
 
 use pve_api_types::ListTasksResponse;
@@ -26,6 +32,21 @@ pub struct TasksPanel {
     #[prop_or_default]
     pub base_url: AttrValue,
 
+    /// Periodically reload the task list.
+    #[builder]
+    #[prop_or_default]
+    pub auto_reload: bool,
+
+    /// Load the list page by page (requires `start`/`limit` API parameters).
+    #[builder]
+    #[prop_or(true)]
+    pub paging: bool,
+
+    /// Enable the task filter and persist it under this ID.
+    #[builder(IntoPropValue, into_prop_value)]
+    #[prop_or_default]
+    pub filter_state_id: Option<AttrValue>,
+
     #[builder_cb(IntoEventCallback, into_event_callback, (String, Option<i64>))]
     #[prop_or_default]
     /// Called when the task is opened
@@ -40,14 +61,161 @@ impl TasksPanel {
     }
 }
 
+#[derive(Clone, Debug, PartialEq, Default, Serialize, Deserialize)]
+pub struct TaskFilter {
+    pub typefilter: String,
+    pub userfilter: String,
+    pub status_ok: bool,
+    pub status_warning: bool,
+    pub status_error: bool,
+    pub status_unknown: bool,
+    pub errors: bool,
+    /// Start date (YYYY-MM-DD)
+    pub since: String,
+    /// End date (YYYY-MM-DD)
+    pub until: String,
+}
+
+impl TaskFilter {
+    fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    fn statusfilter(&self) -> Vec<&'static str> {
+        let mut list = Vec::new();
+        if self.status_ok {
+            list.push("ok");
+        }
+        if self.status_warning {
+            list.push("warning");
+        }
+        if self.status_error {
+            list.push("error");
+        }
+        if self.status_unknown {
+            list.push("unknown");
+        }
+        list
+    }
+
+    /// Build the query parameters for the task list API.
+    fn query_params(&self) -> Vec<(&'static str, String)> {
+        let mut params = Vec::new();
+
+        let typefilter = self.typefilter.trim();
+        if !typefilter.is_empty() {
+            params.push(("typefilter", typefilter.to_string()));
+        }
+        let userfilter = self.userfilter.trim();
+        if !userfilter.is_empty() {
+            params.push(("userfilter", userfilter.to_string()));
+        }
+        let statusfilter = self.statusfilter();
+        if !statusfilter.is_empty() {
+            params.push(("statusfilter", statusfilter.join(",")));
+        }
+        if self.errors {
+            params.push(("errors", String::from("1")));
+        }
+        if let Some(since) = parse_date(&self.since) {
+            params.push(("since", since.to_string()));
+        }
+        if let Some(until) = parse_date(&self.until) {
+            // include the whole day
+            params.push(("until", (until + 86399).to_string()));
+        }
+        params
+    }
+}
+
+// Parse a YYYY-MM-DD date (local time) into an epoch.
+fn parse_date(text: &str) -> Option<i64> {
+    let mut parts = text.trim().splitn(3, '-');
+    let year: u32 = parts.next()?.parse().ok()?;
+    let month: u32 = parts.next()?.parse().ok()?;
+    let day: u32 = parts.next()?.parse().ok()?;
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    let date = js_sys::Date::new_with_year_month_day(year, month as i32 - 1, day as i32);
+    let time = date.get_time();
+    if time.is_nan() {
+        return None;
+    }
+    Some((time / 1000.0) as i64)
+}
+
+// Number of tasks loaded per request
+const PAGE_SIZE: usize = 50;
+
+// Delay (in ms) before changes to the text filter fields are applied
+const FILTER_DELAY: u32 = 500;
+
+// Minimal pull distance (in pixels) to trigger a refresh
+const PULL_TO_REFRESH_DISTANCE: i32 = 80;
+
+// Remaining scroll distance (in pixels) at which the next page gets loaded
+const LOAD_MORE_DISTANCE: i32 = 200;
+
+// Check if the scrolled element is close to the end of its content.
+fn scrolled_near_bottom(event: &Event) -> bool {
+    event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::Element>().ok())
+        .map(|el| el.scroll_top() + el.client_height() >= el.scroll_height() - LOAD_MORE_DISTANCE)
+        .unwrap_or(false)
+}
+
+// Check if all scroll containers above the event target are at the top.
+fn scrolled_to_top(event: &TouchEvent) -> bool {
+    let mut element = event
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::Element>().ok());
+    while let Some(el) = element {
+        if el.scroll_top() > 0 {
+            return false;
+        }
+        element = el.parent_element();
+    }
+    true
+}
+
 pub enum Msg {
+    /// Load the first page (resets the list)
     Load,
+    /// Reload all pages loaded so far
+    Reload,
     LoadResult(Result<Vec<ListTasksResponse>, Error>),
+    LoadMore,
+    LoadMoreResult(Result<Vec<ListTasksResponse>, Error>),
+    TouchStart(Option<i32>),
+    TouchEnd(i32),
+    ShowFilterDialog,
+    CloseFilterDialog,
+    SetFilter(TaskFilter),
+    /// Update a text filter field, applied after [FILTER_DELAY]
+    EditFilter(TaskFilter),
+    ApplyFilter,
 }
 
 pub struct PveTasksPanel {
     data: Option<Result<Rc<Vec<ListTasksResponse>>, String>>,
     load_guard: Option<AsyncAbortGuard>,
+    reload_timeout: Option<Timeout>,
+    load_more_guard: Option<AsyncAbortGuard>,
+    // limit used for the last (re)load of the first page
+    load_limit: usize,
+    // number of tasks returned by the server (before removing duplicates),
+    // used as `start` offset for the next page
+    raw_count: usize,
+    // true if the last request returned a full page
+    has_more: bool,
+    touch_start_y: Option<i32>,
+    filter: Option<PersistentState<TaskFilter>>,
+    show_filter_dialog: bool,
+    // text filter changes not yet applied
+    pending_filter: Option<TaskFilter>,
+    filter_timeout: Option<Timeout>,
 }
 
 fn task_icon(task: &ListTasksResponse) -> Fa {
@@ -83,7 +251,18 @@ impl PveTasksPanel {
         let props = ctx.props();
         let on_show_task = props.on_show_task.clone();
 
-        List::new(data.len() as u64, move |pos| {
+        // add a trailing row while the next page is loading
+        let loading_more = self.load_more_guard.is_some();
+        let count = data.len() as u64 + if loading_more { 1 } else { 0 };
+
+        List::new(count, move |pos| {
+            if pos as usize >= data.len() {
+                return ListTile::new()
+                    .key("load-more")
+                    .class(pwt::css::JustifyContent::Center)
+                    .with_child(Fa::new("spinner").pulse().margin_end(1))
+                    .with_child(tr!("Loading..."));
+            }
             let item = &data[pos as usize];
             ListTile::new()
                 .interactive(true)
@@ -104,6 +283,183 @@ impl PveTasksPanel {
         .grid_template_columns("auto 1fr auto")
         .into()
     }
+
+    fn task_list_url(&self, ctx: &Context<Self>, start: usize, limit: usize) -> String {
+        let props = ctx.props();
+        let mut url = props.base_url.to_string();
+
+        let mut params = Vec::new();
+        if props.paging {
+            params.push(("start", start.to_string()));
+            params.push(("limit", limit.to_string()));
+        }
+        if let Some(filter) = &self.filter {
+            params.extend(filter.query_params());
+        }
+
+        // base_url may already contain query parameters (i.e. vmid)
+        let mut separator = if url.contains('?') { '&' } else { '?' };
+        for (name, value) in params {
+            url.push(separator);
+            url.push_str(name);
+            url.push('=');
+            url.push_str(&percent_encode_component(&value));
+            separator = '&';
+        }
+        url
+    }
+
+    fn load(&mut self, ctx: &Context<Self>, limit: usize) {
+        let link = ctx.link().clone();
+        let url = self.task_list_url(ctx, 0, limit);
+
+        self.load_limit = limit;
+        self.reload_timeout = None;
+        self.load_more_guard = None;
+        self.load_guard = Some(AsyncAbortGuard::spawn(async move {
+            let result = http_get(&url, None).await;
+            link.send_message(Msg::LoadResult(result));
+        }));
+    }
+
+    fn set_filter(&mut self, ctx: &Context<Self>, filter: TaskFilter) {
+        if let Some(state) = &mut self.filter {
+            if **state != filter {
+                state.update(filter);
+                ctx.link().send_message(Msg::Load);
+            }
+        }
+    }
+
+    fn create_filter_panel(&self, ctx: &Context<Self>, filter: &TaskFilter) -> Html {
+        let update = |modify: fn(&mut TaskFilter, bool)| {
+            let filter = filter.clone();
+            ctx.link().callback(move |value: bool| {
+                let mut filter = filter.clone();
+                modify(&mut filter, value);
+                Msg::SetFilter(filter)
+            })
+        };
+        let update_text = |modify: fn(&mut TaskFilter, String)| {
+            let filter = filter.clone();
+            ctx.link().callback(move |value: String| {
+                let mut filter = filter.clone();
+                modify(&mut filter, value);
+                Msg::EditFilter(filter)
+            })
+        };
+
+        let grid = Column::new()
+            .padding(2)
+            .gap(2)
+            .with_child(tr!("Task Type"))
+            .with_child(
+                Field::new()
+                    .value(filter.typefilter.clone())
+                    .placeholder(tr!("e.g. vzdump"))
+                    .on_change(update_text(|f, v| f.typefilter = v)),
+            )
+            .with_child(tr!("User"))
+            .with_child(
+                Field::new()
+                    .value(filter.userfilter.clone())
+                    .placeholder("root@pam")
+                    .on_change(update_text(|f, v| f.userfilter = v)),
+            )
+            .with_child(tr!("Status"))
+            .with_child(
+                Checkbox::new()
+                    .checked(filter.status_ok)
+                    .box_label(tr!("OK"))
+                    .on_change(update(|f, v| f.status_ok = v)),
+            )
+            .with_child(
+                Checkbox::new()
+                    .checked(filter.status_warning)
+                    .box_label(tr!("Warning"))
+                    .on_change(update(|f, v| f.status_warning = v)),
+            )
+            .with_child(
+                Checkbox::new()
+                    .checked(filter.status_error)
+                    .box_label(tr!("Error"))
+                    .on_change(update(|f, v| f.status_error = v)),
+            )
+            .with_child(
+                Checkbox::new()
+                    .checked(filter.status_unknown)
+                    .box_label(tr!("Unknown"))
+                    .on_change(update(|f, v| f.status_unknown = v)),
+            )
+            .with_child(
+                Checkbox::new()
+                    .checked(filter.errors)
+                    .box_label(tr!("Only Errors"))
+                    .on_change(update(|f, v| f.errors = v)),
+            )
+            .with_child(tr!("Since"))
+            .with_child(
+                Field::new()
+                    .value(filter.since.clone())
+                    .placeholder("YYYY-MM-DD")
+                    .on_change(update_text(|f, v| f.since = v)),
+            )
+            .with_child(tr!("Until"))
+            .with_child(
+                Field::new()
+                    .value(filter.until.clone())
+                    .placeholder("YYYY-MM-DD")
+                    .on_change(update_text(|f, v| f.until = v)),
+            );
+
+        Panel::new()
+            .title(tr!("Filter"))
+            .with_child(html! { <hr/>})
+            .with_child(grid)
+            .into()
+    }
+
+    fn create_filter_bar(&self, ctx: &Context<Self>, filter: &TaskFilter) -> Html {
+        let text = if filter.is_empty() {
+            tr!("All Tasks")
+        } else {
+            tr!("Filtered Tasks")
+        };
+
+        let filter_dialog = self.show_filter_dialog.then(|| {
+            SideDialog::new()
+                .location(pwt::touch::SideDialogLocation::Right)
+                .on_close(ctx.link().callback(|_| Msg::CloseFilterDialog))
+                .with_child(
+                    self.create_filter_panel(ctx, self.pending_filter.as_ref().unwrap_or(filter)),
+                )
+        });
+
+        Row::new()
+            .gap(1)
+            .padding_x(2)
+            .padding_y(1)
+            .class("pwt-align-items-center")
+            .class("pwt-border-bottom")
+            .with_child(
+                Container::new()
+                    .class("pwt-font-size-title-medium")
+                    .with_child(text),
+            )
+            .with_flex_spacer()
+            .with_optional_child((!filter.is_empty()).then(|| {
+                ActionIcon::new("fa fa-lg fa-times").on_activate(
+                    ctx.link()
+                        .callback(|_| Msg::SetFilter(TaskFilter::default())),
+                )
+            }))
+            .with_child(
+                ActionIcon::new("fa fa-lg fa-filter")
+                    .on_activate(ctx.link().callback(|_| Msg::ShowFilterDialog)),
+            )
+            .with_optional_child(filter_dialog)
+            .into()
+    }
 }
 
 impl Component for PveTasksPanel {
@@ -113,9 +469,25 @@ impl Component for PveTasksPanel {
     fn create(ctx: &Context<Self>) -> Self {
         ctx.link().send_message(Msg::Load);
 
+        let filter = ctx
+            .props()
+            .filter_state_id
+            .as_ref()
+            .map(|id| PersistentState::new(StorageLocation::local(id.to_string())));
+
         Self {
             data: None,
             load_guard: None,
+            reload_timeout: None,
+            load_more_guard: None,
+            load_limit: PAGE_SIZE,
+            raw_count: 0,
+            has_more: false,
+            touch_start_y: None,
+            filter,
+            show_filter_dialog: false,
+            pending_filter: None,
+            filter_timeout: None,
         }
     }
 
@@ -123,23 +495,118 @@ impl Component for PveTasksPanel {
         let props = ctx.props();
         match msg {
             Msg::Load => {
+                self.load(ctx, PAGE_SIZE);
+            }
+            Msg::Reload => {
+                let limit = self.raw_count.max(PAGE_SIZE);
+                self.load(ctx, limit);
+            }
+            Msg::LoadResult(result) => {
+                self.load_guard = None;
+                // a full page indicates that there may be more tasks
+                self.has_more =
+                    props.paging && matches!(&result, Ok(list) if list.len() >= self.load_limit);
+                self.raw_count = match &result {
+                    Ok(list) => list.len(),
+                    Err(_) => 0,
+                };
+                self.data = Some(
+                    result
+                        .map(|list| {
+                            let mut seen = HashSet::new();
+                            Rc::new(
+                                list.into_iter()
+                                    .filter(|task| seen.insert(task.upid.clone()))
+                                    .collect(),
+                            )
+                        })
+                        .map_err(|err| err.to_string()),
+                );
+                if props.auto_reload {
+                    let link = ctx.link().clone();
+                    self.reload_timeout = Some(Timeout::new(3000, move || {
+                        link.send_message(Msg::Reload);
+                    }));
+                }
+            }
+            Msg::LoadMore => {
+                if !self.has_more || self.load_more_guard.is_some() || self.load_guard.is_some() {
+                    return false;
+                }
                 let link = ctx.link().clone();
-                let url = props.base_url.clone();
-
-                self.load_guard = Some(AsyncAbortGuard::spawn(async move {
-                    let result = http_get(&*url, None).await;
-                    link.send_message(Msg::LoadResult(result));
+                let url = self.task_list_url(ctx, self.raw_count, PAGE_SIZE);
+                self.load_more_guard = Some(AsyncAbortGuard::spawn(async move {
+                    let result = http_get(&url, None).await;
+                    link.send_message(Msg::LoadMoreResult(result));
                 }));
             }
-            Msg::LoadResult(result) => {
-                self.data = Some(result.map(Rc::new).map_err(|err| err.to_string()));
+            Msg::LoadMoreResult(result) => {
+                self.load_more_guard = None;
+                match result {
+                    Ok(page) => {
+                        self.has_more = page.len() >= PAGE_SIZE;
+                        self.raw_count += page.len();
+                        if let Some(Ok(list)) = &mut self.data {
+                            // new tasks shift the list, so we may get duplicates
+                            let mut seen: HashSet<String> =
+                                list.iter().map(|task| task.upid.clone()).collect();
+                            let list = Rc::make_mut(list);
+                            list.extend(
+                                page.into_iter()
+                                    .filter(|task| seen.insert(task.upid.clone())),
+                            );
+                        }
+                    }
+                    Err(err) => {
+                        self.has_more = false;
+                        crate::show_failed_command_error(ctx.link(), err);
+                    }
+                }
+            }
+            Msg::TouchStart(y) => {
+                self.touch_start_y = y;
+                return false;
+            }
+            Msg::TouchEnd(y) => {
+                if let Some(start_y) = self.touch_start_y.take() {
+                    if y - start_y > PULL_TO_REFRESH_DISTANCE {
+                        ctx.link().send_message(Msg::Load);
+                    }
+                }
+                return false;
+            }
+            Msg::ShowFilterDialog => {
+                self.show_filter_dialog = true;
+            }
+            Msg::CloseFilterDialog => {
+                self.show_filter_dialog = false;
+                ctx.link().send_message(Msg::ApplyFilter);
+            }
+            Msg::EditFilter(filter) => {
+                self.pending_filter = Some(filter);
+                let link = ctx.link().clone();
+                self.filter_timeout = Some(Timeout::new(FILTER_DELAY, move || {
+                    link.send_message(Msg::ApplyFilter);
+                }));
+            }
+            Msg::ApplyFilter => {
+                self.filter_timeout = None;
+                match self.pending_filter.take() {
+                    Some(filter) => self.set_filter(ctx, filter),
+                    None => return false,
+                }
+            }
+            Msg::SetFilter(filter) => {
+                self.pending_filter = None;
+                self.filter_timeout = None;
+                self.set_filter(ctx, filter);
             }
         }
         true
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
-        render_loaded_data(&self.data, |data| {
+        let list = render_loaded_data(&self.data, |data| {
             if data.is_empty() {
                 Container::new()
                     .padding(2)
@@ -148,7 +615,37 @@ impl Component for PveTasksPanel {
             } else {
                 self.view_task_list(ctx, Rc::clone(data))
             }
-        })
+        });
+
+        let list = Container::new()
+            .class("pwt-flex-fit")
+            .class("pwt-d-flex pwt-flex-direction-column")
+            .ontouchstart(ctx.link().callback(|event: TouchEvent| {
+                let start_y = match scrolled_to_top(&event) {
+                    true => event.touches().get(0).map(|touch| touch.client_y()),
+                    false => None,
+                };
+                Msg::TouchStart(start_y)
+            }))
+            .ontouchend(ctx.link().batch_callback(|event: TouchEvent| {
+                event
+                    .changed_touches()
+                    .get(0)
+                    .map(|touch| Msg::TouchEnd(touch.client_y()))
+            }))
+            .onscroll(ctx.link().batch_callback(|event: Event| {
+                scrolled_near_bottom(&event).then_some(Msg::LoadMore)
+            }))
+            .with_child(list);
+
+        match &self.filter {
+            Some(filter) => Column::new()
+                .class("pwt-flex-fit")
+                .with_child(self.create_filter_bar(ctx, filter))
+                .with_child(list)
+                .into(),
+            None => list.into(),
+        }
     }
 }
 
@@ -14,6 +14,8 @@ use proxmox_yew_comp::ConfirmButton;
 
 use proxmox_yew_comp::pve_api_types::StorageEntry;
 
+use crate::widgets::parse_backup_volid;
+
 #[derive(Clone, PartialEq, Properties)]
 #[builder]
 pub struct VolumeActionDialog {
@@ -28,6 +30,11 @@ pub struct VolumeActionDialog {
     #[prop_or_default]
     /// Called when the task is opened
     pub on_show_config: Option<Callback<()>>,
+
+    #[builder_cb(IntoEventCallback, into_event_callback, ())]
+    #[prop_or_default]
+    /// Called to restore a backup volume
+    pub on_restore: Option<Callback<()>>,
 }
 
 impl VolumeActionDialog {
@@ -85,14 +92,6 @@ impl Component for PveVolumeActionDialog {
                             ),
                     ),
             )
-            /* fixme: implement restore?
-            .with_child(
-                Button::new(tr!("Restore"))
-                    .icon_class("fa fa-undo")
-                    .class("pwt-button-outline")
-                    .disabled(true), // fixme
-            )
-            */
             .with_child(
                 ConfirmButton::remove_entry(props.item.volid.clone())
                     .icon_class("fa fa-trash-o")
@@ -101,6 +100,17 @@ impl Component for PveVolumeActionDialog {
             );
 
         if props.item.content == "backup" {
+            if parse_backup_volid(&props.item.volid).is_some() {
+                column.add_child(
+                    Button::new(tr!("Restore"))
+                        .icon_class("fa fa-undo")
+                        .class("pwt-button-outline")
+                        .on_activate({
+                            let cb = wrap_callback(props.on_restore.clone());
+                            move |_| cb.emit(())
+                        }),
+                );
+            }
             column.add_child(
                 Button::new(tr!("Show Configuration"))
                     .icon_class("fa fa-list-alt")